@@ -0,0 +1,165 @@
+use std::{sync::OnceLock, time::Duration};
+
+use hex::ToHex;
+use hmac::{Hmac, Mac};
+use komodo_client::parsers::parse_key_value_list;
+use reqwest::{
+  header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+  RequestBuilder,
+};
+use sha2::Sha256;
+
+use super::*;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The header carrying the HMAC-SHA256 signature of the body.
+const SIGNATURE_HEADER: &str = "X-Komodo-Signature";
+
+#[instrument(level = "debug", skip(endpoint))]
+pub async fn send_alert(
+  endpoint: &CustomAlerterEndpoint,
+  alert: &Alert,
+  templated: Option<&str>,
+) -> anyhow::Result<()> {
+  let (content_type, body) = match templated {
+    // Templates producing JSON are sent as JSON, eg. for Teams / Mattermost
+    Some(body)
+      if serde_json::from_str::<serde_json::Value>(body).is_ok() =>
+    {
+      ("application/json", body.to_string())
+    }
+    Some(body) => ("text/plain", body.to_string()),
+    None => (
+      "application/json",
+      serde_json::to_string(alert)
+        .context("failed to serialize alert to json")?,
+    ),
+  };
+
+  let mut headers = endpoint.headers.clone();
+  let mut username = endpoint.username.clone();
+  let mut password = endpoint.password.clone();
+  let mut token = endpoint.token.clone();
+  let mut signing_secret = endpoint.signing_secret.clone();
+  interpolate_variables_secrets(&mut [
+    &mut headers,
+    &mut username,
+    &mut password,
+    &mut token,
+    &mut signing_secret,
+  ])
+  .await?;
+
+  let mut header_map = parse_headers(&headers)?;
+  header_map
+    .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+  if !signing_secret.is_empty() {
+    let mut mac = HmacSha256::new_from_slice(
+      signing_secret.as_bytes(),
+    )
+    .context("failed to create hmac sha256 from signing secret")?;
+    mac.update(body.as_bytes());
+    let signature =
+      mac.finalize().into_bytes().encode_hex::<String>();
+    header_map.insert(
+      SIGNATURE_HEADER,
+      HeaderValue::from_str(&format!("sha256={signature}"))
+        .context("invalid signature header value")?,
+    );
+  }
+
+  let timeout =
+    Duration::from_secs(endpoint.timeout_seconds.max(1) as u64);
+  let build_request = || {
+    let req = http_client()
+      .post(&endpoint.url)
+      .headers(header_map.clone())
+      .timeout(timeout)
+      .body(body.clone());
+    with_auth(req, &username, &password, &token)
+  };
+
+  let retries = endpoint.retries.max(0) as u32;
+  let mut attempt = 0;
+  loop {
+    let res = match build_request().send().await {
+      Ok(res) => res,
+      Err(e) if attempt < retries => {
+        warn!(
+          "post to alerter failed, retrying | attempt {} | {e:#}",
+          attempt + 1
+        );
+        backoff(attempt).await;
+        attempt += 1;
+        continue;
+      }
+      Err(e) => {
+        return Err(e).context("failed at post request to alerter")
+      }
+    };
+    let status = res.status();
+    if status.is_success() {
+      return Ok(());
+    }
+    let text = res
+      .text()
+      .await
+      .context("failed to get response text on alerter response")?;
+    if status.is_server_error() && attempt < retries {
+      warn!(
+        "post to alerter failed, retrying | attempt {} | {status} | {text}",
+        attempt + 1
+      );
+      backoff(attempt).await;
+      attempt += 1;
+      continue;
+    }
+    return Err(anyhow!(
+      "post to alerter failed | {status} | {text}"
+    ));
+  }
+}
+
+/// Basic auth takes precedence over the bearer token.
+fn with_auth(
+  req: RequestBuilder,
+  username: &str,
+  password: &str,
+  token: &str,
+) -> RequestBuilder {
+  if !username.is_empty() {
+    let password = (!password.is_empty()).then_some(password);
+    req.basic_auth(username, password)
+  } else if !token.is_empty() {
+    req.bearer_auth(token)
+  } else {
+    req
+  }
+}
+
+fn parse_headers(headers: &str) -> anyhow::Result<HeaderMap> {
+  let mut map = HeaderMap::new();
+  for (name, value) in parse_key_value_list(headers)
+    .context("failed to parse custom alerter headers")?
+  {
+    let name = HeaderName::from_bytes(name.as_bytes())
+      .with_context(|| format!("invalid header name '{name}'"))?;
+    let value = HeaderValue::from_str(&value).with_context(|| {
+      format!("invalid value for header '{name}'")
+    })?;
+    map.insert(name, value);
+  }
+  Ok(map)
+}
+
+/// Waits 1s, 2s, 4s, ... capped at 1 minute.
+async fn backoff(attempt: u32) {
+  let secs = 1u64 << attempt.min(6);
+  tokio::time::sleep(Duration::from_secs(secs.min(60))).await;
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
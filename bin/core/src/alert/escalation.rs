@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use komodo_client::entities::{
+  deployment::Deployment, komodo_timestamp, server::Server,
+  stack::Stack, ResourceTarget,
+};
+use tokio::time::MissedTickBehavior;
+
+use crate::resource;
+
+use super::{maintenance::ActiveMaintenanceWindows, *};
+
+const ESCALATION_LOOP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically re-sends unresolved alerts to alerters with
+/// a re-notify interval, and forwards them along escalation tiers.
+pub fn spawn_alert_escalation_loop() {
+  tokio::spawn(async move {
+    let mut interval =
+      tokio::time::interval(ESCALATION_LOOP_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+      interval.tick().await;
+      if let Err(e) = renotify_and_escalate().await {
+        error!("failed to run alert escalation | {e:#}");
+      }
+    }
+  });
+}
+
+async fn renotify_and_escalate() -> anyhow::Result<()> {
+  let alerters = find_collect(
+    &db_client().alerters,
+    doc! { "config.enabled": true },
+    None,
+  )
+  .await
+  .context("failed to get alerters from db")?;
+
+  // Nothing to do if no alerter re-notifies or escalates
+  if !alerters.iter().any(|alerter| {
+    alerter.config.renotify_interval_minutes > 0
+      || !alerter.config.escalations.is_empty()
+  }) {
+    return Ok(());
+  }
+
+  let alerts = find_collect(
+    &db_client().alerts,
+    doc! { "resolved": false },
+    None,
+  )
+  .await
+  .context("failed to get open alerts from db")?;
+
+  let now = komodo_timestamp();
+
+  let mut due = Vec::new();
+
+  for alert in &alerts {
+    // Acknowledged and snoozed alerts are held back
+    if alert.acknowledged.is_some() || alert.snoozed_until > now {
+      continue;
+    }
+
+    let mut notify = HashSet::<&str>::new();
+
+    for alerter in &alerters {
+      // Re-notify
+      let interval = alerter.config.renotify_interval_minutes;
+      if interval > 0
+        && (alerter_accepts_alert(alerter, alert)
+          || alert.last_notified.contains_key(&alerter.id))
+      {
+        let last_notified = alert
+          .last_notified
+          .get(&alerter.id)
+          .copied()
+          .unwrap_or(alert.ts);
+        if now - last_notified >= interval * 60_000 {
+          notify.insert(&alerter.id);
+        }
+      }
+
+      // Escalate
+      if !alerter_accepts_alert(alerter, alert) {
+        continue;
+      }
+      for escalation in &alerter.config.escalations {
+        if now - alert.ts < escalation.after_minutes * 60_000 {
+          continue;
+        }
+        let Some(target) = alerters.iter().find(|target| {
+          target.id == escalation.alerter
+            || target.name == escalation.alerter
+        }) else {
+          continue;
+        };
+        if !alert.last_notified.contains_key(&target.id) {
+          notify.insert(&target.id);
+        }
+      }
+    }
+
+    if !notify.is_empty() {
+      due.push((alert, notify));
+    }
+  }
+
+  if due.is_empty() {
+    return Ok(());
+  }
+
+  // Only reads the active windows. Alerts are marked suppressed
+  // when they are first held back, not here.
+  let windows = ActiveMaintenanceWindows::load().await?;
+
+  let mut handles = Vec::new();
+
+  for (alert, notify) in due {
+    // Hold back alerts under an active maintenance window
+    if windows.find(&alert.target).await.is_some() {
+      continue;
+    }
+    if !target_sends_alert(alert).await {
+      continue;
+    }
+    for alerter_id in notify {
+      let Some(alerter) =
+        alerters.iter().find(|alerter| alerter.id == alerter_id)
+      else {
+        continue;
+      };
+      handles.push(renotify(alerter, alert, now));
+    }
+  }
+
+  // Send concurrently, so one slow alerter retrying
+  // doesn't hold up the rest of the run.
+  join_all(handles).await;
+
+  Ok(())
+}
+
+async fn renotify(alerter: &Alerter, alert: &Alert, ts: i64) {
+  if let Err(e) = send_alert_to_alerter(alerter, alert).await {
+    error!("{e:#}");
+    return;
+  }
+  if let Err(e) = record_notified(&alert.id, &alerter.id, ts).await {
+    warn!("{e:#}");
+  }
+}
+
+/// Alerts are recorded even when the resource has these alerts
+/// turned off, and the config may have changed since the alert opened.
+/// Re-notify and escalation follow the current config.
+async fn target_sends_alert(alert: &Alert) -> bool {
+  match &alert.target {
+    ResourceTarget::Server(id) => {
+      let Ok(server) = resource::get::<Server>(id).await else {
+        return false;
+      };
+      let config = server.config;
+      match alert.data {
+        AlertData::ServerUnreachable { .. } => {
+          config.send_unreachable_alerts
+        }
+        AlertData::ServerCpu { .. } => config.send_cpu_alerts,
+        AlertData::ServerMem { .. } => config.send_mem_alerts,
+        AlertData::ServerDisk { .. } => config.send_disk_alerts,
+        AlertData::ServerNetwork { .. } => config.send_network_alerts,
+        AlertData::ServerProcessMissing { .. } => {
+          config.send_process_alerts
+        }
+        _ => true,
+      }
+    }
+    ResourceTarget::Deployment(id) => {
+      let Ok(deployment) = resource::get::<Deployment>(id).await
+      else {
+        return false;
+      };
+      let config = deployment.config;
+      config.send_alerts
+        && (!matches!(
+          alert.data,
+          AlertData::ContainerRestartLoop { .. }
+        ) || config.send_restart_loop_alerts)
+    }
+    ResourceTarget::Stack(id) => {
+      let Ok(stack) = resource::get::<Stack>(id).await else {
+        return false;
+      };
+      let config = stack.config;
+      config.send_alerts
+        && (!matches!(
+          alert.data,
+          AlertData::ContainerRestartLoop { .. }
+        ) || config.send_restart_loop_alerts)
+    }
+    _ => true,
+  }
+}
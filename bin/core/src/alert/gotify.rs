@@ -0,0 +1,72 @@
+use std::sync::OnceLock;
+
+use serde_json::json;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint))]
+pub async fn send_alert(
+  endpoint: &GotifyAlerterEndpoint,
+  alert: &Alert,
+  templated: Option<&str>,
+) -> anyhow::Result<()> {
+  let content = alert_content(alert, templated);
+  if content.is_empty() {
+    return Ok(());
+  }
+
+  let (title, message) = standard_alert_title_body(&content);
+  let mut extras = json!({
+    "client::display": { "contentType": "text/markdown" },
+  });
+  if let Some(link) = alert_link(alert) {
+    extras["client::notification"] =
+      json!({ "click": { "url": link } });
+  }
+  let body = json!({
+    "title": title,
+    "message": message,
+    "priority": gotify_priority(alert.level),
+    "extras": extras,
+  });
+
+  let mut token = endpoint.token.clone();
+  interpolate_variables_secrets(&mut [&mut token]).await?;
+
+  let url = format!("{}/message", endpoint.url.trim_end_matches('/'));
+  let mut req = http_client().post(url).json(&body);
+  if !token.is_empty() {
+    req = req.header("X-Gotify-Key", token);
+  }
+
+  let response =
+    req.send().await.context("Failed to send message")?;
+
+  let status = response.status();
+
+  if status.is_success() {
+    Ok(())
+  } else {
+    let text = response.text().await.with_context(|| {
+      format!("Failed to send message to Gotify | {status} | failed to get response text")
+    })?;
+    Err(anyhow!(
+      "Failed to send message to Gotify | {status} | {text}"
+    ))
+  }
+}
+
+/// Gotify priorities range from 0 to 10.
+/// Android clients pop up a notification from 8 upwards.
+fn gotify_priority(level: SeverityLevel) -> u8 {
+  match level {
+    SeverityLevel::Ok => 2,
+    SeverityLevel::Warning => 5,
+    SeverityLevel::Critical => 8,
+  }
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
@@ -0,0 +1,281 @@
+use std::{collections::HashMap, str::FromStr};
+
+use komodo_client::entities::{
+  action::Action, build::Build, builder::Builder,
+  deployment::Deployment, komodo_timestamp,
+  maintenance::MaintenanceWindow, procedure::Procedure, repo::Repo,
+  server::Server, server_template::ServerTemplate, stack::Stack,
+  sync::ResourceSync, ResourceTarget,
+};
+use mungos::mongodb::bson::oid::ObjectId;
+
+use crate::{
+  helpers::query::get_all_tags, resource,
+  schedule::find_next_cron_occurrence,
+};
+
+use super::*;
+
+/// Whether the maintenance window is active at the given unix ms timestamp.
+pub fn maintenance_window_active(
+  window: &MaintenanceWindow,
+  ts: i64,
+) -> anyhow::Result<bool> {
+  if !window.enabled
+    || (window.start_ts > 0 && ts < window.start_ts)
+    || (window.end_ts > 0 && ts >= window.end_ts)
+  {
+    return Ok(false);
+  }
+  let schedule = window.schedule.trim();
+  if schedule.is_empty() {
+    return Ok(true);
+  }
+  if window.duration_minutes <= 0 {
+    return Err(anyhow!(
+      "Recurring maintenance window must have a positive 'duration_minutes'"
+    ));
+  }
+  // The window is open if an occurrence started within the last duration.
+  let duration = window.duration_minutes * 60_000;
+  let occurrence = find_next_cron_occurrence(
+    schedule,
+    &window.schedule_timezone,
+    ts - duration,
+    true,
+  )?;
+  Ok(occurrence <= ts)
+}
+
+/// Get the maintenance windows which are currently active.
+async fn get_active_maintenance_windows(
+) -> anyhow::Result<Vec<MaintenanceWindow>> {
+  let ts = komodo_timestamp();
+  let windows = find_collect(
+    &db_client().maintenance_windows,
+    doc! { "enabled": true },
+    None,
+  )
+  .await
+  .context("failed to query db for maintenance windows")?
+  .into_iter()
+  .filter(|window| {
+    maintenance_window_active(window, ts)
+      .inspect_err(|e| {
+        warn!(
+          "failed to check maintenance window {} | {e:#}",
+          window.name
+        )
+      })
+      .unwrap_or_default()
+  })
+  .collect();
+  Ok(windows)
+}
+
+/// The maintenance windows active at the time they were loaded.
+/// Checking a target against them doesn't write to the db.
+pub struct ActiveMaintenanceWindows {
+  windows: Vec<MaintenanceWindow>,
+  tag_names: HashMap<String, String>,
+}
+
+impl ActiveMaintenanceWindows {
+  pub async fn load() -> anyhow::Result<Self> {
+    let windows = get_active_maintenance_windows().await?;
+    let tag_names = if windows.is_empty() {
+      Default::default()
+    } else {
+      get_all_tags(None)
+        .await
+        .inspect_err(|e| warn!("{e:#}"))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| (tag.id, tag.name))
+        .collect()
+    };
+    Ok(Self { windows, tag_names })
+  }
+
+  /// The active window covering the target, if any.
+  pub async fn find(
+    &self,
+    target: &ResourceTarget,
+  ) -> Option<&MaintenanceWindow> {
+    if self.windows.is_empty() {
+      return None;
+    }
+    find_matching_window(&self.windows, target, &self.tag_names).await
+  }
+}
+
+/// Removes the alerts opening on targets under an active maintenance
+/// window, returning the alerts to send. Resolutions of alerts which
+/// were delivered still go out, so receivers can close them, while
+/// resolutions of alerts which were held back are never sent.
+/// The held back alerts are marked as suppressed on the db, and the
+/// ones not yet recorded (eg. build / repo failures) are recorded
+/// as suppressed.
+pub async fn filter_suppressed_alerts(
+  alerts: &[Alert],
+) -> Vec<&Alert> {
+  let alerts = alerts
+    .iter()
+    .filter(|alert| {
+      let held_back = resolves_suppressed_alert(alert);
+      if held_back {
+        info!(
+          "holding back {:?} resolution on {:?} | open alert was suppressed",
+          alert.data.extract_variant(),
+          alert.target,
+        );
+      }
+      !held_back
+    })
+    .collect::<Vec<_>>();
+
+  let windows = match ActiveMaintenanceWindows::load().await {
+    Ok(windows) => windows,
+    Err(e) => {
+      error!("failed to get active maintenance windows | {e:#}");
+      return alerts;
+    }
+  };
+  if windows.windows.is_empty() {
+    return alerts;
+  }
+
+  let mut to_send = Vec::new();
+  let mut suppressed = Vec::new();
+  let mut unrecorded = Vec::new();
+
+  for alert in alerts {
+    if alert.resolved && !alert.last_notified.is_empty() {
+      to_send.push(alert);
+      continue;
+    }
+    let Some(window) = windows.find(&alert.target).await else {
+      to_send.push(alert);
+      continue;
+    };
+    info!(
+      "holding back {:?} alert on {:?} | maintenance window '{}'",
+      alert.data.extract_variant(),
+      alert.target,
+      window.name
+    );
+    if let Ok(id) = ObjectId::from_str(&alert.id) {
+      suppressed.push(id);
+    } else {
+      unrecorded.push(Alert {
+        suppressed: true,
+        ..alert.clone()
+      });
+    }
+  }
+
+  if !unrecorded.is_empty() {
+    let _ = record_alerts(&mut unrecorded).await.inspect_err(|e| {
+      warn!("failed to record suppressed alerts on db | {e:#}")
+    });
+  }
+
+  if !suppressed.is_empty() {
+    let _ = db_client()
+      .alerts
+      .update_many(
+        doc! { "_id": { "$in": &suppressed } },
+        doc! { "$set": { "suppressed": true } },
+      )
+      .await
+      .inspect_err(|e| {
+        warn!("failed to mark alerts as suppressed on db | {e:#}")
+      });
+  }
+
+  to_send
+}
+
+/// Whether the alert resolves an alert which was held back,
+/// so no alerter knows about the alert being resolved.
+fn resolves_suppressed_alert(alert: &Alert) -> bool {
+  alert.resolved && alert.suppressed && alert.last_notified.is_empty()
+}
+
+async fn find_matching_window<'a>(
+  windows: &'a [MaintenanceWindow],
+  target: &ResourceTarget,
+  tag_names: &HashMap<String, String>,
+) -> Option<&'a MaintenanceWindow> {
+  let (variant, id) = target.extract_variant_id();
+  let (name, tags) =
+    get_target_name_and_tags(target).await.unwrap_or_default();
+  windows.iter().find(|window| {
+    let target_match = window.targets.iter().any(|window_target| {
+      let (window_variant, window_id) =
+        window_target.extract_variant_id();
+      window_variant == variant
+        && (window_id == id
+          || (!name.is_empty() && window_id == &name))
+    });
+    let tag_match = window.tags.iter().any(|window_tag| {
+      tags.iter().any(|tag| {
+        tag == window_tag || tag_names.get(tag) == Some(window_tag)
+      })
+    });
+    target_match || tag_match
+  })
+}
+
+async fn get_target_name_and_tags(
+  target: &ResourceTarget,
+) -> anyhow::Result<(String, Vec<String>)> {
+  let res = match target {
+    ResourceTarget::System(_) => Default::default(),
+    ResourceTarget::Server(id) => {
+      let server = resource::get::<Server>(id).await?;
+      (server.name, server.tags)
+    }
+    ResourceTarget::Stack(id) => {
+      let stack = resource::get::<Stack>(id).await?;
+      (stack.name, stack.tags)
+    }
+    ResourceTarget::Deployment(id) => {
+      let deployment = resource::get::<Deployment>(id).await?;
+      (deployment.name, deployment.tags)
+    }
+    ResourceTarget::Build(id) => {
+      let build = resource::get::<Build>(id).await?;
+      (build.name, build.tags)
+    }
+    ResourceTarget::Repo(id) => {
+      let repo = resource::get::<Repo>(id).await?;
+      (repo.name, repo.tags)
+    }
+    ResourceTarget::Procedure(id) => {
+      let procedure = resource::get::<Procedure>(id).await?;
+      (procedure.name, procedure.tags)
+    }
+    ResourceTarget::Action(id) => {
+      let action = resource::get::<Action>(id).await?;
+      (action.name, action.tags)
+    }
+    ResourceTarget::Builder(id) => {
+      let builder = resource::get::<Builder>(id).await?;
+      (builder.name, builder.tags)
+    }
+    ResourceTarget::Alerter(id) => {
+      let alerter = resource::get::<Alerter>(id).await?;
+      (alerter.name, alerter.tags)
+    }
+    ResourceTarget::ServerTemplate(id) => {
+      let template = resource::get::<ServerTemplate>(id).await?;
+      (template.name, template.tags)
+    }
+    ResourceTarget::ResourceSync(id) => {
+      let sync = resource::get::<ResourceSync>(id).await?;
+      (sync.name, sync.tags)
+    }
+  };
+  Ok(res)
+}
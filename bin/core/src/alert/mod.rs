@@ -1,3 +1,5 @@
+use std::{collections::HashSet, str::FromStr};
+
 use ::slack::types::Block;
 use anyhow::{anyhow, Context};
 use derive_variants::ExtractVariant;
@@ -6,17 +8,41 @@ use komodo_client::entities::{
   alert::{Alert, AlertData, SeverityLevel},
   alerter::*,
   deployment::DeploymentState,
+  komodo_timestamp,
   stack::StackState,
   ResourceTargetVariant,
 };
-use mungos::{find::find_collect, mongodb::bson::doc};
+use mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
 use tracing::Instrument;
 
-use crate::{config::core_config, state::db_client};
+use crate::{
+  config::core_config,
+  helpers::{
+    interpolate::interpolate_variables_secrets_into_string,
+    query::get_variables_and_secrets,
+  },
+  state::db_client,
+};
 
+mod custom;
 mod discord;
+mod escalation;
+mod gotify;
+mod maintenance;
+mod ntfy;
 mod slack;
+mod smtp;
+mod template;
+
+pub use escalation::spawn_alert_escalation_loop;
+pub use maintenance::maintenance_window_active;
 
+/// Delivery runs in the background, so slow alerters
+/// (eg. custom endpoints with retries) don't hold up
+/// the caller, like the monitoring loop.
 pub async fn send_alerts(alerts: &[Alert]) {
   if alerts.is_empty() {
     return;
@@ -39,10 +65,21 @@ pub async fn send_alerts(alerts: &[Alert]) {
       return;
     };
 
-    let handles =
-      alerts.iter().map(|alert| send_alert(&alerters, alert));
+    // Hold back alerts under an active maintenance window
+    let alerts = maintenance::filter_suppressed_alerts(alerts)
+      .await
+      .into_iter()
+      .cloned()
+      .collect::<Vec<_>>();
 
-    join_all(handles).await;
+    tokio::spawn(
+      async move {
+        let handles =
+          alerts.iter().map(|alert| send_alert(&alerters, alert));
+        join_all(handles).await;
+      }
+      .in_current_span(),
+    );
   }
   .instrument(span)
   .await
@@ -54,88 +91,566 @@ async fn send_alert(alerters: &[Alerter], alert: &Alert) {
     return;
   }
 
-  let alert_type = alert.data.extract_variant();
+  let handles = alerters
+    .iter()
+    .filter(|alerter| {
+      alerter_accepts_alert(alerter, alert)
+        // Alerters notified about the open alert, eg. by escalation,
+        // get the resolution even if they don't accept the alert.
+        || (alert.resolved
+          && alert.last_notified.contains_key(&alerter.id))
+    })
+    .map(|alerter| async {
+      send_alert_to_alerter(alerter, alert).await?;
+      // Re-notify and escalation count from the first delivery
+      if !alert.resolved && !alert.id.is_empty() {
+        record_notified(&alert.id, &alerter.id, komodo_timestamp())
+          .await?;
+      }
+      anyhow::Ok(())
+    });
 
-  let handles = alerters.iter().map(|alerter| async {
-    // Don't send if not enabled
-    if !alerter.config.enabled {
-      return Ok(());
-    }
+  join_all(handles)
+    .await
+    .into_iter()
+    .filter_map(|res| res.err())
+    .for_each(|e| error!("{e:#}"));
+}
 
-    // Don't send if alert type not configured on the alerter
-    if !alerter.config.alert_types.is_empty()
-      && !alerter.config.alert_types.contains(&alert_type)
-    {
-      return Ok(());
-    }
+/// Records when the alerter was last notified about the alert.
+async fn record_notified(
+  alert_id: &str,
+  alerter_id: &str,
+  ts: i64,
+) -> anyhow::Result<()> {
+  let id = ObjectId::from_str(alert_id)
+    .context("failed to parse alert id as ObjectId")?;
+  db_client()
+    .alerts
+    .update_one(
+      doc! { "_id": id },
+      doc! { "$set": { format!("last_notified.{alerter_id}"): ts } },
+    )
+    .await
+    .context("failed to record alert notification time on db")?;
+  Ok(())
+}
 
-    // Don't send if resource is in the blacklist
-    if alerter.config.except_resources.contains(&alert.target) {
-      return Ok(());
-    }
+/// Whether the alerter is configured to send the alert.
+fn alerter_accepts_alert(alerter: &Alerter, alert: &Alert) -> bool {
+  // Don't send if not enabled
+  if !alerter.config.enabled {
+    return false;
+  }
 
-    // Don't send if whitelist configured and target is not included
-    if !alerter.config.resources.is_empty()
-      && !alerter.config.resources.contains(&alert.target)
-    {
-      return Ok(());
-    }
+  // Don't send if alert type not configured on the alerter
+  if !alerter.config.alert_types.is_empty()
+    && !alerter
+      .config
+      .alert_types
+      .contains(&alert.data.extract_variant())
+  {
+    return false;
+  }
+
+  // Don't send if resource is in the blacklist
+  if alerter.config.except_resources.contains(&alert.target) {
+    return false;
+  }
+
+  // Don't send if whitelist configured and target is not included
+  if !alerter.config.resources.is_empty()
+    && !alerter.config.resources.contains(&alert.target)
+  {
+    return false;
+  }
+
+  true
+}
 
-    match &alerter.config.endpoint {
-      AlerterEndpoint::Custom(CustomAlerterEndpoint { url }) => {
-        send_custom_alert(url, alert).await.with_context(|| {
+async fn send_alert_to_alerter(
+  alerter: &Alerter,
+  alert: &Alert,
+) -> anyhow::Result<()> {
+  // A broken template shouldn't stop the alerts, fall back
+  // to the standard content (or raw JSON for custom alerters).
+  let templated = if alerter.config.template.is_empty() {
+    None
+  } else {
+    template::render_alert_template(&alerter.config.template, alert)
+      .inspect_err(|e| {
+        warn!(
+          "failed to render alert template for alerter {}, using the standard content | {e:#}",
+          alerter.name
+        )
+      })
+      .ok()
+  };
+  let templated = templated.as_deref();
+  match &alerter.config.endpoint {
+    AlerterEndpoint::Custom(endpoint) => {
+      custom::send_alert(endpoint, alert, templated)
+        .await
+        .with_context(|| {
           format!(
             "failed to send alert to custom alerter {}",
             alerter.name
           )
         })
-      }
-      AlerterEndpoint::Slack(SlackAlerterEndpoint { url }) => {
-        slack::send_alert(url, alert).await.with_context(|| {
+    }
+    AlerterEndpoint::Slack(SlackAlerterEndpoint { url }) => {
+      slack::send_alert(url, alert, templated).await.with_context(
+        || {
           format!(
             "failed to send alert to slack alerter {}",
             alerter.name
           )
-        })
-      }
-      AlerterEndpoint::Discord(DiscordAlerterEndpoint { url }) => {
-        discord::send_alert(url, alert).await.with_context(|| {
+        },
+      )
+    }
+    AlerterEndpoint::Discord(DiscordAlerterEndpoint { url }) => {
+      discord::send_alert(url, alert, templated)
+        .await
+        .with_context(|| {
           format!(
             "failed to send alert to Discord alerter {}",
             alerter.name
           )
         })
-      }
     }
-  });
+    AlerterEndpoint::Smtp(endpoint) => {
+      smtp::send_alert(endpoint, alert, templated)
+        .await
+        .with_context(|| {
+          format!(
+            "failed to send alert to SMTP alerter {}",
+            alerter.name
+          )
+        })
+    }
+    AlerterEndpoint::Ntfy(endpoint) => {
+      ntfy::send_alert(endpoint, alert, templated)
+        .await
+        .with_context(|| {
+          format!(
+            "failed to send alert to ntfy alerter {}",
+            alerter.name
+          )
+        })
+    }
+    AlerterEndpoint::Gotify(endpoint) => {
+      gotify::send_alert(endpoint, alert, templated)
+        .await
+        .with_context(|| {
+          format!(
+            "failed to send alert to Gotify alerter {}",
+            alerter.name
+          )
+        })
+    }
+  }
+}
 
-  join_all(handles)
+/// Records the alert on the db, setting the alert id.
+/// Record alerts before sending them, so any held back
+/// by a maintenance window can be marked as suppressed.
+pub async fn record_alert(alert: &mut Alert) -> anyhow::Result<()> {
+  alert.id = db_client()
+    .alerts
+    .insert_one(&*alert)
     .await
-    .into_iter()
-    .filter_map(|res| res.err())
-    .for_each(|e| error!("{e:#}"));
+    .context("failed to record alert on db")?
+    .inserted_id
+    .as_object_id()
+    .context("inserted_id is not ObjectId")?
+    .to_hex();
+  Ok(())
 }
 
-#[instrument(level = "debug")]
-async fn send_custom_alert(
-  url: &str,
-  alert: &Alert,
+/// Records the alerts on the db, setting the alert ids.
+pub async fn record_alerts(
+  alerts: &mut [Alert],
 ) -> anyhow::Result<()> {
-  let res = reqwest::Client::new()
-    .post(url)
-    .json(alert)
-    .send()
+  let res = db_client()
+    .alerts
+    .insert_many(alerts.iter())
     .await
-    .context("failed at post request to alerter")?;
-  let status = res.status();
-  if !status.is_success() {
-    let text = res
-      .text()
-      .await
-      .context("failed to get response text on alerter response")?;
-    return Err(anyhow!(
-      "post to alerter failed | {status} | {text}"
-    ));
+    .context("failed to record alerts on db")?;
+  for (i, id) in res.inserted_ids {
+    if let (Some(alert), Some(id)) =
+      (alerts.get_mut(i), id.as_object_id())
+    {
+      alert.id = id.to_hex();
+    }
+  }
+  Ok(())
+}
+
+/// The rendered alerter template if configured,
+/// otherwise the standard alert content.
+fn alert_content(alert: &Alert, templated: Option<&str>) -> String {
+  templated
+    .map(str::to_string)
+    .unwrap_or_else(|| standard_alert_content(alert))
+}
+
+/// Markdown formatted alert content, shared by the
+/// endpoints which don't have their own message format.
+fn standard_alert_content(alert: &Alert) -> String {
+  let level = fmt_level(alert.level);
+  match &alert.data {
+    AlertData::ServerUnreachable {
+      id,
+      name,
+      region,
+      err,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => {
+          format!(
+            "{level} | **{name}**{region} is now **reachable**\n{link}"
+          )
+        }
+        SeverityLevel::Critical => {
+          let err = err
+            .as_ref()
+            .map(|e| format!("\n**error**: {e:#?}"))
+            .unwrap_or_default();
+          format!(
+            "{level} | **{name}**{region} is **unreachable** ❌\n{link}{err}"
+          )
+        }
+        _ => unreachable!(),
+      }
+    }
+    AlertData::ServerCpu {
+      id,
+      name,
+      region,
+      percentage,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      format!(
+        "{level} | **{name}**{region} cpu usage at **{percentage:.1}%**\n{link}"
+      )
+    }
+    AlertData::ServerMem {
+      id,
+      name,
+      region,
+      used_gb,
+      total_gb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = 100.0 * used_gb / total_gb;
+      format!(
+        "{level} | **{name}**{region} memory usage at **{percentage:.1}%** 💾\n\nUsing **{used_gb:.1} GiB** / **{total_gb:.1} GiB**\n{link}"
+      )
+    }
+    AlertData::ServerDisk {
+      id,
+      name,
+      region,
+      path,
+      used_gb,
+      total_gb,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      let percentage = 100.0 * used_gb / total_gb;
+      format!(
+        "{level} | **{name}**{region} disk usage at **{percentage:.1}%** 💿\nmount point: `{path:?}`\nusing **{used_gb:.1} GiB** / **{total_gb:.1} GiB**\n{link}"
+      )
+    }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      interface,
+      ingress_mb_per_sec,
+      egress_mb_per_sec,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | **{name}**{region} network throughput is back to normal 📶\ninterface: `{interface}`\ningress: **{ingress_mb_per_sec:.1} MB/s** | egress: **{egress_mb_per_sec:.1} MB/s**\n{link}"
+        ),
+        _ => format!(
+          "{level} | **{name}**{region} has sustained high network throughput 📶\ninterface: `{interface}`\ningress: **{ingress_mb_per_sec:.1} MB/s** | egress: **{egress_mb_per_sec:.1} MB/s**\n{link}"
+        ),
+      }
+    }
+    AlertData::ServerProcessMissing {
+      id,
+      name,
+      region,
+      process,
+    } => {
+      let region = fmt_region(region);
+      let link = resource_link(ResourceTargetVariant::Server, id);
+      match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Process **{process}** is running on **{name}**{region}\n{link}"
+        ),
+        _ => format!(
+          "{level} | Process **{process}** is not running on **{name}**{region} ⚙️\n{link}"
+        ),
+      }
+    }
+    AlertData::ContainerStateChange {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      from,
+      to,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      let to = fmt_docker_container_state(to);
+      format!("📦 Deployment **{name}** is now **{to}**\nserver: **{server_name}**\nprevious: **{from}**\n{link}")
+    }
+    AlertData::ContainerUnhealthy {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      service,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: **{service}**"))
+        .unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Container **{container}** on **{name}** is now **healthy**\nserver: **{server_name}**{service}\n{link}"),
+        _ => format!("{level} | Container **{container}** on **{name}** is **unhealthy** 🩺\nserver: **{server_name}**{service}\n{link}"),
+      }
+    }
+    AlertData::ContainerCpu {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      service,
+      percentage,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: **{service}**"))
+        .unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Container **{container}** on **{name}** cpu usage at **{percentage:.1}%**\nserver: **{server_name}**{service}\n{link}"),
+        _ => format!("{level} | Container **{container}** on **{name}** cpu usage at **{percentage:.1}%** 📈\nserver: **{server_name}**{service}\n{link}"),
+      }
+    }
+    AlertData::ContainerMem {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      service,
+      used_mb,
+      limit_mb,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: **{service}**"))
+        .unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Container **{container}** on **{name}** memory usage at **{used_mb:.0}/{limit_mb:.0} MB**\nserver: **{server_name}**{service}\n{link}"),
+        _ => format!("{level} | Container **{container}** on **{name}** memory usage at **{used_mb:.0}/{limit_mb:.0} MB** 💾\nserver: **{server_name}**{service}\n{link}"),
+      }
+    }
+    AlertData::ContainerRestartLoop {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      container,
+      service,
+      restarts,
+      window_minutes,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: **{service}**"))
+        .unwrap_or_default();
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Container **{container}** on **{name}** has **stopped restarting**\nserver: **{server_name}**{service}\n{link}"),
+        _ => format!("{level} | Container **{container}** on **{name}** is in a **restart loop** 🔁\nserver: **{server_name}**{service}\nrestarts: **{restarts}** in **{window_minutes} minutes**\n{link}"),
+      }
+    }
+    AlertData::DeploymentImageUpdateAvailable {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      image,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      format!("⬆ Deployment **{name}** has an update available\nserver: **{server_name}**\nimage: **{image}**\n{link}")
+    }
+    AlertData::DeploymentAutoUpdated {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      image,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Deployment, id);
+      format!("⬆ Deployment **{name}** was updated automatically ⏫\nserver: **{server_name}**\nimage: **{image}**\n{link}")
+    }
+    AlertData::StackStateChange {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      from,
+      to,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Stack, id);
+      let to = fmt_stack_state(to);
+      format!("🥞 Stack **{name}** is now {to}\nserver: **{server_name}**\nprevious: **{from}**\n{link}")
+    }
+    AlertData::StackImageUpdateAvailable {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      service,
+      image,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Stack, id);
+      format!("⬆ Stack **{name}** has an update available\nserver: **{server_name}**\nservice: **{service}**\nimage: **{image}**\n{link}")
+    }
+    AlertData::StackAutoUpdated {
+      id,
+      name,
+      server_id: _server_id,
+      server_name,
+      images,
+    } => {
+      let link = resource_link(ResourceTargetVariant::Stack, id);
+      let images_label =
+        if images.len() > 1 { "images" } else { "image" };
+      let images = images.join(", ");
+      format!("⬆ Stack **{name}** was updated automatically ⏫\nserver: **{server_name}**\n{images_label}: **{images}**\n{link}")
+    }
+    AlertData::CertificateExpiring {
+      id,
+      name,
+      url,
+      days_remaining,
+      ..
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let expiry = fmt_certificate_expiry(*days_remaining);
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Certificate for **{name}** was renewed\nurl: {url}\n{link}"),
+        _ => format!("{level} | Certificate for **{name}** {expiry} 🔐\nurl: {url}\n{link}"),
+      }
+    }
+    AlertData::EndpointDown {
+      id,
+      name,
+      endpoint,
+      error,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      match alert.level {
+        SeverityLevel::Ok => format!("{level} | Endpoint **{endpoint}** on **{name}** is no longer down\n{link}"),
+        _ => format!("{level} | Endpoint **{endpoint}** on **{name}** is down 🔌\n{error}\n{link}"),
+      }
+    }
+    AlertData::EndpointRecovered {
+      id,
+      name,
+      endpoint,
+      down_ms,
+    } => {
+      let link = resource_link(alert.target.extract_variant(), id);
+      let down = fmt_duration_ms(*down_ms);
+      format!("{level} | Endpoint **{endpoint}** on **{name}** has recovered\ndown for: **{down}**\n{link}")
+    }
+    AlertData::AwsBuilderTerminationFailed {
+      instance_id,
+      message,
+    } => {
+      format!("{level} | Failed to terminated AWS builder instance\ninstance id: **{instance_id}**\n{message}")
+    }
+    AlertData::ResourceSyncPendingUpdates { id, name } => {
+      let link =
+        resource_link(ResourceTargetVariant::ResourceSync, id);
+      format!(
+        "{level} | Pending resource sync updates on **{name}**\n{link}"
+      )
+    }
+    AlertData::BuildFailed { id, name, version } => {
+      let link = resource_link(ResourceTargetVariant::Build, id);
+      format!("{level} | Build **{name}** failed\nversion: **v{version}**\n{link}")
+    }
+    AlertData::RepoBuildFailed { id, name } => {
+      let link = resource_link(ResourceTargetVariant::Repo, id);
+      format!("{level} | Repo build for **{name}** failed\n{link}")
+    }
+    AlertData::None {} => Default::default(),
+  }
+}
+
+/// Splits the standard alert content into
+/// a plain title (the first line) and the remaining markdown body.
+fn standard_alert_title_body(content: &str) -> (String, String) {
+  let (title, body) =
+    content.split_once('\n').unwrap_or((content, ""));
+  let title = strip_markdown(title).trim().to_string();
+  let body = body.trim();
+  let body = if body.is_empty() {
+    title.clone()
+  } else {
+    body.to_string()
+  };
+  (title, body)
+}
+
+fn strip_markdown(content: &str) -> String {
+  content.replace("**", "").replace('`', "")
+}
+
+/// Link to the alert target in the Komodo UI, if it has one.
+fn alert_link(alert: &Alert) -> Option<String> {
+  let (variant, id) = alert.target.extract_variant_id();
+  if matches!(variant, ResourceTargetVariant::System) {
+    return None;
+  }
+  Some(resource_link(variant, id))
+}
+
+/// Interpolates Core variables and secrets into
+/// alerter endpoint fields, like tokens and passwords.
+async fn interpolate_variables_secrets(
+  targets: &mut [&mut String],
+) -> anyhow::Result<()> {
+  let vars_and_secrets = get_variables_and_secrets().await?;
+  let mut global_replacers = HashSet::new();
+  let mut secret_replacers = HashSet::new();
+  for target in targets {
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      target,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
   }
   Ok(())
 }
@@ -167,6 +682,26 @@ fn fmt_stack_state(state: &StackState) -> String {
   }
 }
 
+fn fmt_certificate_expiry(days_remaining: i64) -> String {
+  match days_remaining {
+    ..0 => String::from("has expired"),
+    0 => String::from("expires today"),
+    1 => String::from("expires in 1 day"),
+    days => format!("expires in {days} days"),
+  }
+}
+
+fn fmt_duration_ms(ms: i64) -> String {
+  let secs = ms / 1000;
+  if secs < 60 {
+    format!("{secs}s")
+  } else if secs < 3600 {
+    format!("{}m {}s", secs / 60, secs % 60)
+  } else {
+    format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+  }
+}
+
 fn fmt_level(level: SeverityLevel) -> &'static str {
   match level {
     SeverityLevel::Critical => "CRITICAL 🚨",
@@ -0,0 +1,90 @@
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint))]
+pub async fn send_alert(
+  endpoint: &NtfyAlerterEndpoint,
+  alert: &Alert,
+  templated: Option<&str>,
+) -> anyhow::Result<()> {
+  let content = alert_content(alert, templated);
+  if content.is_empty() {
+    return Ok(());
+  }
+
+  if endpoint.url.trim().is_empty() {
+    return Err(anyhow!("ntfy alerter has no topic url configured"));
+  }
+
+  // ntfy takes the topic in the JSON body when publishing
+  // to the server root, which allows for non-ascii titles.
+  let url = endpoint.url.trim().trim_end_matches('/');
+  let (server, topic) = url
+    .rsplit_once('/')
+    .filter(|(server, topic)| server.contains("://") && !topic.is_empty())
+    .with_context(|| {
+      format!("Invalid ntfy topic url '{url}'. Use eg. 'https://ntfy.sh/<topic>'")
+    })?;
+
+  let (title, message) = standard_alert_title_body(&content);
+  let body = NtfyMessageBody {
+    topic,
+    title: &title,
+    message: &message,
+    priority: ntfy_priority(alert.level),
+    click: alert_link(alert),
+    markdown: true,
+  };
+
+  let mut token = endpoint.token.clone();
+  interpolate_variables_secrets(&mut [&mut token]).await?;
+
+  let mut req = http_client().post(server).json(&body);
+  if !token.is_empty() {
+    req = req.bearer_auth(token);
+  }
+
+  let response =
+    req.send().await.context("Failed to send message")?;
+
+  let status = response.status();
+
+  if status.is_success() {
+    Ok(())
+  } else {
+    let text = response.text().await.with_context(|| {
+      format!("Failed to send message to ntfy | {status} | failed to get response text")
+    })?;
+    Err(anyhow!(
+      "Failed to send message to ntfy | {status} | {text}"
+    ))
+  }
+}
+
+/// ntfy priorities range from 1 (min) to 5 (max), 3 is the default.
+fn ntfy_priority(level: SeverityLevel) -> u8 {
+  match level {
+    SeverityLevel::Ok => 3,
+    SeverityLevel::Warning => 4,
+    SeverityLevel::Critical => 5,
+  }
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  CLIENT.get_or_init(reqwest::Client::new)
+}
+
+#[derive(Serialize)]
+struct NtfyMessageBody<'a> {
+  topic: &'a str,
+  title: &'a str,
+  message: &'a str,
+  priority: u8,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  click: Option<String>,
+  markdown: bool,
+}
@@ -4,7 +4,15 @@ use super::*;
 pub async fn send_alert(
   url: &str,
   alert: &Alert,
+  templated: Option<&str>,
 ) -> anyhow::Result<()> {
+  if let Some(text) = templated {
+    if !text.is_empty() {
+      let slack = ::slack::Client::new(url);
+      slack.send_message(text, None::<Vec<Block>>).await?;
+    }
+    return Ok(());
+  }
   let level = fmt_level(alert.level);
   let (text, blocks): (_, Option<_>) = match &alert.data {
     AlertData::ServerUnreachable {
@@ -173,6 +181,54 @@ pub async fn send_alert(
         }
       }
     }
+    AlertData::ServerNetwork {
+      id,
+      name,
+      region,
+      interface,
+      ingress_mb_per_sec,
+      egress_mb_per_sec,
+    } => {
+      let region = fmt_region(region);
+      let text = match alert.level {
+        SeverityLevel::Ok => format!("{level} | *{name}*{region} network throughput is back to normal | interface: *{interface}* 📶"),
+        _ => format!("{level} | *{name}*{region} has sustained high network throughput | interface: *{interface}* 📶"),
+      };
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!(
+          "ingress: *{ingress_mb_per_sec:.1} MB/s* | egress: *{egress_mb_per_sec:.1} MB/s*"
+        )),
+        Block::section(resource_link(ResourceTargetVariant::Server, id)),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ServerProcessMissing {
+      id,
+      name,
+      region,
+      process,
+    } => {
+      let region = fmt_region(region);
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Process *{process}* is running on *{name}*{region}"
+        ),
+        _ => format!(
+          "{level} | Process *{process}* is not running on *{name}*{region} ⚙️"
+        ),
+      };
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(resource_link(
+          ResourceTargetVariant::Server,
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::ContainerStateChange {
       name,
       server_name,
@@ -195,6 +251,137 @@ pub async fn send_alert(
       ];
       (text, blocks.into())
     }
+    AlertData::ContainerUnhealthy {
+      id,
+      name,
+      server_name,
+      container,
+      service,
+      ..
+    } => {
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Container *{container}* on *{name}* is now *healthy*"
+        ),
+        _ => format!(
+          "{level} | Container *{container}* on *{name}* is *unhealthy* 🩺"
+        ),
+      };
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: *{service}*"))
+        .unwrap_or_default();
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!("server: *{server_name}*{service}")),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ContainerCpu {
+      id,
+      name,
+      server_name,
+      container,
+      service,
+      percentage,
+      ..
+    } => {
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Container *{container}* on *{name}* cpu usage at *{percentage:.1}%*"
+        ),
+        _ => format!(
+          "{level} | Container *{container}* on *{name}* cpu usage at *{percentage:.1}%* 📈"
+        ),
+      };
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: *{service}*"))
+        .unwrap_or_default();
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!("server: *{server_name}*{service}")),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ContainerMem {
+      id,
+      name,
+      server_name,
+      container,
+      service,
+      used_mb,
+      limit_mb,
+      ..
+    } => {
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Container *{container}* on *{name}* memory usage at *{used_mb:.0}/{limit_mb:.0} MB*"
+        ),
+        _ => format!(
+          "{level} | Container *{container}* on *{name}* memory usage at *{used_mb:.0}/{limit_mb:.0} MB* 💾"
+        ),
+      };
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: *{service}*"))
+        .unwrap_or_default();
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!("server: *{server_name}*{service}")),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::ContainerRestartLoop {
+      id,
+      name,
+      server_name,
+      container,
+      service,
+      restarts,
+      window_minutes,
+      ..
+    } => {
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Container *{container}* on *{name}* has *stopped restarting*"
+        ),
+        _ => format!(
+          "{level} | Container *{container}* on *{name}* is in a *restart loop* 🔁"
+        ),
+      };
+      let service = service
+        .as_ref()
+        .map(|service| format!("\nservice: *{service}*"))
+        .unwrap_or_default();
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!(
+          "server: *{server_name}*{service}\nrestarts: *{restarts}* in *{window_minutes} minutes*"
+        )),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::DeploymentImageUpdateAvailable {
       id,
       name,
@@ -304,6 +491,81 @@ pub async fn send_alert(
       ];
       (text, blocks.into())
     }
+    AlertData::CertificateExpiring {
+      id,
+      name,
+      url,
+      days_remaining,
+      ..
+    } => {
+      let expiry = fmt_certificate_expiry(*days_remaining);
+      let text = match alert.level {
+        SeverityLevel::Ok => {
+          format!("{level} | Certificate for *{name}* was renewed")
+        }
+        _ => {
+          format!("{level} | Certificate for *{name}* {expiry} 🔐")
+        }
+      };
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!("url: {url}")),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::EndpointDown {
+      id,
+      name,
+      endpoint,
+      error,
+    } => {
+      let text = match alert.level {
+        SeverityLevel::Ok => format!(
+          "{level} | Endpoint *{endpoint}* on *{name}* is no longer down"
+        ),
+        _ => format!(
+          "{level} | Endpoint *{endpoint}* on *{name}* is down 🔌"
+        ),
+      };
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(error),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
+    AlertData::EndpointRecovered {
+      id,
+      name,
+      endpoint,
+      down_ms,
+    } => {
+      let text = format!(
+        "{level} | Endpoint *{endpoint}* on *{name}* has recovered"
+      );
+      let blocks = vec![
+        Block::header(level),
+        Block::section(text.clone()),
+        Block::section(format!(
+          "down for: *{}*",
+          fmt_duration_ms(*down_ms)
+        )),
+        Block::section(resource_link(
+          alert.target.extract_variant(),
+          id,
+        )),
+      ];
+      (text, blocks.into())
+    }
     AlertData::AwsBuilderTerminationFailed {
       instance_id,
       message,
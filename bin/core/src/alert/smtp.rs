@@ -0,0 +1,272 @@
+use std::sync::OnceLock;
+
+use lettre::{
+  message::{Mailbox, MultiPart},
+  transport::smtp::authentication::Credentials,
+  AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use regex::Regex;
+
+use crate::helpers::query::VariablesAndSecrets;
+
+use super::*;
+
+#[instrument(level = "debug", skip(endpoint))]
+pub async fn send_alert(
+  endpoint: &SmtpAlerterEndpoint,
+  alert: &Alert,
+  templated: Option<&str>,
+) -> anyhow::Result<()> {
+  let content = alert_content(alert, templated);
+  if content.is_empty() {
+    return Ok(());
+  }
+  let vars_and_secrets = get_variables_and_secrets().await?;
+  send_email(endpoint, &vars_and_secrets, &content).await
+}
+
+async fn send_email(
+  endpoint: &SmtpAlerterEndpoint,
+  vars_and_secrets: &VariablesAndSecrets,
+  content: &str,
+) -> anyhow::Result<()> {
+  let recipients = endpoint
+    .to
+    .iter()
+    .map(|to| to.trim())
+    .filter(|to| !to.is_empty())
+    .collect::<Vec<_>>();
+  if recipients.is_empty() {
+    return Err(anyhow!("SMTP alerter has no recipients configured"));
+  }
+
+  let SmtpAlerterEndpoint {
+    mut username,
+    mut password,
+    mut from,
+    ..
+  } = endpoint.clone();
+
+  let mut global_replacers = HashSet::new();
+  let mut secret_replacers = HashSet::new();
+  for target in [&mut username, &mut password, &mut from] {
+    interpolate_variables_secrets_into_string(
+      vars_and_secrets,
+      target,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+  }
+
+  let mut message = Message::builder()
+    .from(
+      from
+        .parse::<Mailbox>()
+        .with_context(|| format!("Invalid 'from' mailbox: {from}"))?,
+    )
+    .subject(email_subject(content));
+  for to in recipients {
+    message = message.to(
+      to.parse::<Mailbox>()
+        .with_context(|| format!("Invalid 'to' mailbox: {to}"))?,
+    );
+  }
+  let message = message
+    .multipart(MultiPart::alternative_plain_html(
+      email_plain_body(content),
+      email_html_body(content),
+    ))
+    .context("Failed to build email")?;
+
+  smtp_transport(endpoint, username, password)?
+    .send(message)
+    .await
+    .context("Failed to send email")?;
+
+  Ok(())
+}
+
+fn smtp_transport(
+  endpoint: &SmtpAlerterEndpoint,
+  username: String,
+  password: String,
+) -> anyhow::Result<AsyncSmtpTransport<Tokio1Executor>> {
+  let host = endpoint.host.as_str();
+  let mut builder = match endpoint.tls {
+    SmtpTlsMode::None => {
+      AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+    }
+    SmtpTlsMode::StartTls => {
+      AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+        .with_context(|| {
+          format!("Failed to init STARTTLS transport to {host}")
+        })?
+    }
+    SmtpTlsMode::Tls => {
+      AsyncSmtpTransport::<Tokio1Executor>::relay(host).with_context(
+        || format!("Failed to init TLS transport to {host}"),
+      )?
+    }
+  }
+  .port(endpoint.port);
+  if !username.is_empty() {
+    builder =
+      builder.credentials(Credentials::new(username, password));
+  }
+  Ok(builder.build())
+}
+
+/// The first line of the alert content, without markdown.
+fn email_subject(content: &str) -> String {
+  strip_markdown(content.lines().next().unwrap_or_default())
+    .trim()
+    .to_string()
+}
+
+fn email_plain_body(content: &str) -> String {
+  strip_markdown(content)
+}
+
+fn email_html_body(content: &str) -> String {
+  let body = content
+    .lines()
+    .map(|line| {
+      let line = escape_html(line);
+      if line.starts_with("http://") || line.starts_with("https://") {
+        return format!("<a href=\"{line}\">{line}</a>");
+      }
+      let line = bold_regex().replace_all(&line, "<b>$1</b>");
+      code_regex()
+        .replace_all(&line, "<code>$1</code>")
+        .into_owned()
+    })
+    .collect::<Vec<_>>()
+    .join("<br>\n");
+  format!(
+    "<!DOCTYPE html>\n<html>\n<body style=\"font-family: sans-serif;\">\n{body}\n</body>\n</html>\n"
+  )
+}
+
+fn escape_html(line: &str) -> String {
+  line
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+fn bold_regex() -> &'static Regex {
+  static BOLD_REGEX: OnceLock<Regex> = OnceLock::new();
+  BOLD_REGEX.get_or_init(|| {
+    Regex::new(r"\*\*(.+?)\*\*").expect("invalid bold regex")
+  })
+}
+
+fn code_regex() -> &'static Regex {
+  static CODE_REGEX: OnceLock<Regex> = OnceLock::new();
+  CODE_REGEX.get_or_init(|| {
+    Regex::new(r"`(.+?)`").expect("invalid code regex")
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use base64::Engine;
+  use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+  };
+
+  use super::*;
+
+  /// Accepts a single email, returning the
+  /// SMTP commands and the message data.
+  async fn smtp_sink(listener: TcpListener) -> (Vec<String>, String) {
+    let (stream, _) = listener.accept().await.unwrap();
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+    let mut commands = Vec::new();
+    while let Some(command) = lines.next_line().await.unwrap() {
+      let verb = command
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+      commands.push(command);
+      let reply: &[u8] = match verb.as_str() {
+        "EHLO" => b"250-localhost\r\n250 AUTH PLAIN LOGIN\r\n",
+        "AUTH" => b"235 2.7.0 Authentication successful\r\n",
+        "DATA" => {
+          write.write_all(b"354 End data with .\r\n").await.unwrap();
+          let mut data = String::new();
+          while let Some(line) = lines.next_line().await.unwrap() {
+            if line == "." {
+              break;
+            }
+            data.push_str(&line);
+            data.push('\n');
+          }
+          write.write_all(b"250 2.0.0 Ok: queued\r\n").await.unwrap();
+          return (commands, data);
+        }
+        _ => b"250 2.0.0 Ok\r\n",
+      };
+      write.write_all(reply).await.unwrap();
+    }
+    panic!("connection closed before DATA");
+  }
+
+  #[tokio::test]
+  async fn sends_email_to_local_smtp_sink() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let sink = tokio::spawn(smtp_sink(listener));
+
+    let endpoint = SmtpAlerterEndpoint {
+      host: String::from("127.0.0.1"),
+      port,
+      tls: SmtpTlsMode::None,
+      username: String::from("[[SMTP_USER]]"),
+      password: String::from("[[SMTP_PASSWORD]]"),
+      from: String::from("Komodo <[[SMTP_FROM]]>"),
+      to: vec![String::from("ops@example.com"), String::from(" ")],
+    };
+    let vars_and_secrets = VariablesAndSecrets {
+      variables: HashMap::from([(
+        String::from("SMTP_FROM"),
+        String::from("alerts@example.com"),
+      )]),
+      secrets: HashMap::from([
+        (String::from("SMTP_USER"), String::from("user")),
+        (String::from("SMTP_PASSWORD"), String::from("hunter2")),
+      ]),
+    };
+
+    send_email(
+      &endpoint,
+      &vars_and_secrets,
+      "**server-1** disk is **full**\nhttps://komodo.example.com",
+    )
+    .await
+    .unwrap();
+
+    let (commands, data) = sink.await.unwrap();
+
+    let auth = base64::engine::general_purpose::STANDARD
+      .encode("\0user\0hunter2");
+    assert!(commands.contains(&format!("AUTH PLAIN {auth}")));
+    assert!(commands.iter().any(|command| {
+      command.starts_with("MAIL FROM:<alerts@example.com>")
+    }));
+    let recipients = commands
+      .iter()
+      .filter(|command| command.starts_with("RCPT TO:"))
+      .collect::<Vec<_>>();
+    assert_eq!(recipients, ["RCPT TO:<ops@example.com>"]);
+    assert!(data.contains("Subject: server-1 disk is full\n"));
+    assert!(data.contains("From: Komodo <alerts@example.com>\n"));
+  }
+}
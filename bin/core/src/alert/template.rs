@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use super::*;
+
+/// The variables available to alerter templates.
+#[derive(Serialize)]
+struct AlertTemplateContext<'a> {
+  alert: &'a Alert,
+  name: String,
+  level: SeverityLevel,
+  link: String,
+  message: String,
+}
+
+/// Renders the alerter `template` for the alert.
+pub fn render_alert_template(
+  template: &str,
+  alert: &Alert,
+) -> anyhow::Result<String> {
+  let context = AlertTemplateContext {
+    alert,
+    name: alert_target_name(alert),
+    level: alert.level,
+    link: alert_link(alert).unwrap_or_default(),
+    message: standard_alert_content(alert),
+  };
+  minijinja::Environment::new()
+    .render_str(template, context)
+    .context("failed to render alerter template")
+}
+
+/// Most alert data includes the target name as `name`.
+fn alert_target_name(alert: &Alert) -> String {
+  serde_json::to_value(&alert.data)
+    .ok()
+    .and_then(|data| {
+      data.get("data")?.get("name")?.as_str().map(str::to_string)
+    })
+    .unwrap_or_default()
+}
@@ -28,6 +28,7 @@ use crate::{
   api::execute::ExecuteRequest,
   config::core_config,
   helpers::{
+    arguments::{arguments_object, resolve_arguments},
     interpolate::{
       add_interp_update_log,
       interpolate_variables_secrets_into_string,
@@ -43,7 +44,7 @@ use crate::{
 impl super::BatchExecute for BatchRunAction {
   type Resource = Action;
   fn single_request(action: String) -> ExecuteRequest {
-    ExecuteRequest::RunAction(RunAction { action })
+    ExecuteRequest::RunAction(RunAction { action, args: None })
   }
 }
 
@@ -62,7 +63,7 @@ impl Resolve<RunAction, (User, Update)> for State {
   #[instrument(name = "RunAction", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
     &self,
-    RunAction { action }: RunAction,
+    RunAction { action, args }: RunAction,
     (user, mut update): (User, Update),
   ) -> anyhow::Result<Update> {
     let mut action = resource::get_check_permissions::<Action>(
@@ -72,6 +73,8 @@ impl Resolve<RunAction, (User, Update)> for State {
     )
     .await?;
 
+    let args = resolve_arguments(&action.config.parameters, args)?;
+
     // get the action state for the action (or insert default).
     let action_state = action_states()
       .action
@@ -97,15 +100,22 @@ impl Resolve<RunAction, (User, Update)> for State {
 
     let contents = &mut action.config.file_contents;
 
-    // Wrap the file contents in the execution context.
-    *contents = full_contents(contents, &key, &secret);
-
+    // Only interpolate into the user written contents,
+    // so run arguments can't pull in variables / secrets.
     let replacers =
       interpolate(contents, &mut update, key.clone(), secret.clone())
         .await?
         .into_iter()
         .collect::<Vec<_>>();
 
+    // Wrap the file contents in the execution context.
+    *contents = full_contents(
+      contents,
+      &arguments_object(&args),
+      &key,
+      &secret,
+    );
+
     let file = format!("{}.ts", random_string(10));
     let path = core_config().action_directory.join(&file);
 
@@ -196,7 +206,12 @@ async fn interpolate(
   Ok(secret_replacers)
 }
 
-fn full_contents(contents: &str, key: &str, secret: &str) -> String {
+fn full_contents(
+  contents: &str,
+  args: &serde_json::Value,
+  key: &str,
+  secret: &str,
+) -> String {
   let CoreConfig {
     port, ssl_enabled, ..
   } = core_config();
@@ -221,6 +236,8 @@ const TOML = {{
   parseCargoToml: __TOML__.parse,
 }}
 
+const ARGS = {args};
+
 const komodo = KomodoClient('{base_url}', {{
   type: 'api-key',
   params: {{ key: '{key}', secret: '{secret}' }}
@@ -403,6 +403,7 @@ impl Resolve<RunBuild, (User, Update)> for State {
             name: build.name,
             version,
           },
+          ..Default::default()
         };
         send_alerts(&[alert]).await
       });
@@ -452,6 +453,7 @@ async fn handle_early_return(
           name: build_name,
           version,
         },
+        ..Default::default()
       };
       send_alerts(&[alert]).await
     });
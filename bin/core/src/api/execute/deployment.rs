@@ -23,6 +23,7 @@ use resolver_api::Resolve;
 
 use crate::{
   helpers::{
+    arguments::{interpolate_arguments_into_string, Argument},
     interpolate::{
       add_interp_update_log,
       interpolate_variables_secrets_into_extra_args,
@@ -91,77 +92,86 @@ impl Resolve<Deploy, (User, Update)> for State {
   #[instrument(name = "Deploy", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
     &self,
-    Deploy {
-      deployment,
-      stop_signal,
-      stop_time,
-    }: Deploy,
-    (user, mut update): (User, Update),
+    req: Deploy,
+    (user, update): (User, Update),
   ) -> anyhow::Result<Update> {
-    let (mut deployment, server) =
-      setup_deployment_execution(&deployment, &user).await?;
-
-    // get the action state for the deployment (or insert default).
-    let action_state = action_states()
-      .deployment
-      .get_or_insert_default(&deployment.id)
-      .await;
+    deploy_inner(req, user, update, &[]).await
+  }
+}
 
-    // Will check to ensure deployment not already busy before updating, and return Err if so.
-    // The returned guard will set the action state back to default when dropped.
-    let _action_guard =
-      action_state.update(|state| state.deploying = true)?;
+/// Procedures pass the run arguments,
+/// which are interpolated into the deployment image for this deploy only.
+pub async fn deploy_inner(
+  Deploy {
+    deployment,
+    stop_signal,
+    stop_time,
+  }: Deploy,
+  user: User,
+  mut update: Update,
+  args: &[Argument],
+) -> anyhow::Result<Update> {
+  let (mut deployment, server) =
+    setup_deployment_execution(&deployment, &user).await?;
+
+  // get the action state for the deployment (or insert default).
+  let action_state = action_states()
+    .deployment
+    .get_or_insert_default(&deployment.id)
+    .await;
 
-    // Send update after setting action state, this way frontend gets correct state.
-    update_update(update.clone()).await?;
+  // Will check to ensure deployment not already busy before updating, and return Err if so.
+  // The returned guard will set the action state back to default when dropped.
+  let _action_guard =
+    action_state.update(|state| state.deploying = true)?;
+
+  // Send update after setting action state, this way frontend gets correct state.
+  update_update(update.clone()).await?;
+
+  // Before resolving the registry, as the image may use arguments.
+  // Only the image, as the rest of the config ends up in a shell command.
+  if let DeploymentImage::Image { image } =
+    &mut deployment.config.image
+  {
+    interpolate_arguments_into_string(image, args)?;
+    if !args.is_empty() && !is_valid_image(image) {
+      return Err(anyhow!(
+        "Image '{image}' is not valid after interpolating arguments"
+      ));
+    }
+  }
 
-    // This block resolves the attached Build to an actual versioned image
-    let (version, registry_token) = match &deployment.config.image {
-      DeploymentImage::Build { build_id, version } => {
-        let build = resource::get::<Build>(build_id).await?;
-        let image_name = get_image_name(&build)
-          .context("failed to create image name")?;
-        let version = if version.is_none() {
-          build.config.version
-        } else {
-          *version
-        };
-        let version_str = version.to_string();
-        // Potentially add the build image_tag postfix
-        let version_str = if build.config.image_tag.is_empty() {
-          version_str
-        } else {
-          format!("{version_str}-{}", build.config.image_tag)
-        };
-        // replace image with corresponding build image.
-        deployment.config.image = DeploymentImage::Image {
-          image: format!("{image_name}:{version_str}"),
-        };
-        if build.config.image_registry.domain.is_empty() {
-          (version, None)
-        } else {
-          let ImageRegistryConfig {
-            domain, account, ..
-          } = build.config.image_registry;
-          if deployment.config.image_registry_account.is_empty() {
-            deployment.config.image_registry_account = account
-          }
-          let token = if !deployment
-            .config
-            .image_registry_account
-            .is_empty()
-          {
-            registry_token(&domain, &deployment.config.image_registry_account).await.with_context(
-              || format!("Failed to get git token in call to db. Stopping run. | {domain} | {}", deployment.config.image_registry_account),
-            )?
-          } else {
-            None
-          };
-          (version, token)
+  // This block resolves the attached Build to an actual versioned image
+  let (version, registry_token) = match &deployment.config.image {
+    DeploymentImage::Build { build_id, version } => {
+      let build = resource::get::<Build>(build_id).await?;
+      let image_name = get_image_name(&build)
+        .context("failed to create image name")?;
+      let version = if version.is_none() {
+        build.config.version
+      } else {
+        *version
+      };
+      let version_str = version.to_string();
+      // Potentially add the build image_tag postfix
+      let version_str = if build.config.image_tag.is_empty() {
+        version_str
+      } else {
+        format!("{version_str}-{}", build.config.image_tag)
+      };
+      // replace image with corresponding build image.
+      deployment.config.image = DeploymentImage::Image {
+        image: format!("{image_name}:{version_str}"),
+      };
+      if build.config.image_registry.domain.is_empty() {
+        (version, None)
+      } else {
+        let ImageRegistryConfig {
+          domain, account, ..
+        } = build.config.image_registry;
+        if deployment.config.image_registry_account.is_empty() {
+          deployment.config.image_registry_account = account
         }
-      }
-      DeploymentImage::Image { image } => {
-        let domain = extract_registry_domain(image)?;
         let token = if !deployment
           .config
           .image_registry_account
@@ -173,93 +183,114 @@ impl Resolve<Deploy, (User, Update)> for State {
         } else {
           None
         };
-        (Version::default(), token)
+        (version, token)
       }
-    };
+    }
+    DeploymentImage::Image { image } => {
+      let domain = extract_registry_domain(image)?;
+      let token = if !deployment
+        .config
+        .image_registry_account
+        .is_empty()
+      {
+        registry_token(&domain, &deployment.config.image_registry_account).await.with_context(
+          || format!("Failed to get git token in call to db. Stopping run. | {domain} | {}", deployment.config.image_registry_account),
+        )?
+      } else {
+        None
+      };
+      (Version::default(), token)
+    }
+  };
 
-    // interpolate variables / secrets, returning the sanitizing replacers to send to
-    // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
-    let secret_replacers = if !deployment.config.skip_secret_interp {
-      let vars_and_secrets = get_variables_and_secrets().await?;
-
-      let mut global_replacers = HashSet::new();
-      let mut secret_replacers = HashSet::new();
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.environment,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.ports,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.volumes,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_extra_args(
-        &vars_and_secrets,
-        &mut deployment.config.extra_args,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      interpolate_variables_secrets_into_string(
-        &vars_and_secrets,
-        &mut deployment.config.command,
-        &mut global_replacers,
-        &mut secret_replacers,
-      )?;
-
-      add_interp_update_log(
-        &mut update,
-        &global_replacers,
-        &secret_replacers,
-      );
-
-      secret_replacers
-    } else {
-      Default::default()
-    };
+  // interpolate variables / secrets, returning the sanitizing replacers to send to
+  // periphery so it may sanitize the final command for safe logging (avoids exposing secret values)
+  let secret_replacers = if !deployment.config.skip_secret_interp {
+    let vars_and_secrets = get_variables_and_secrets().await?;
+
+    let mut global_replacers = HashSet::new();
+    let mut secret_replacers = HashSet::new();
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.environment,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.ports,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.volumes,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_extra_args(
+      &vars_and_secrets,
+      &mut deployment.config.extra_args,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    interpolate_variables_secrets_into_string(
+      &vars_and_secrets,
+      &mut deployment.config.command,
+      &mut global_replacers,
+      &mut secret_replacers,
+    )?;
+
+    add_interp_update_log(
+      &mut update,
+      &global_replacers,
+      &secret_replacers,
+    );
+
+    secret_replacers
+  } else {
+    Default::default()
+  };
 
-    update.version = version;
-    update_update(update.clone()).await?;
+  update.version = version;
+  update_update(update.clone()).await?;
 
-    match periphery_client(&server)?
-      .request(api::container::Deploy {
-        deployment,
-        stop_signal,
-        stop_time,
-        registry_token,
-        replacers: secret_replacers.into_iter().collect(),
-      })
-      .await
-    {
-      Ok(log) => update.logs.push(log),
-      Err(e) => {
-        update.push_error_log(
-          "Deploy Container",
-          format_serror(&e.into()),
-        );
-      }
-    };
+  match periphery_client(&server)?
+    .request(api::container::Deploy {
+      deployment,
+      stop_signal,
+      stop_time,
+      registry_token,
+      replacers: secret_replacers.into_iter().collect(),
+    })
+    .await
+  {
+    Ok(log) => update.logs.push(log),
+    Err(e) => {
+      update
+        .push_error_log("Deploy Container", format_serror(&e.into()));
+    }
+  };
 
-    update_cache_for_server(&server).await;
+  update_cache_for_server(&server).await;
 
-    update.finalize();
-    update_update(update.clone()).await?;
+  update.finalize();
+  update_update(update.clone()).await?;
 
-    Ok(update)
-  }
+  Ok(update)
+}
+
+fn is_valid_image(image: &str) -> bool {
+  !image.is_empty()
+    && image.chars().all(|c| {
+      c.is_ascii_alphanumeric()
+        || matches!(c, '.' | '_' | '-' | ':' | '/' | '@')
+    })
 }
 
 /// Wait this long after a pull to allow another pull through
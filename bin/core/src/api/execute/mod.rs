@@ -39,7 +39,11 @@ mod stack;
 mod sync;
 
 pub use {
-  deployment::pull_deployment_inner, stack::pull_stack_inner,
+  deployment::{deploy_inner, pull_deployment_inner},
+  stack::{
+    deploy_stack_if_changed_inner, deploy_stack_inner,
+    pull_stack_inner,
+  },
 };
 
 #[typeshare]
@@ -1,4 +1,4 @@
-use std::pin::Pin;
+use std::{collections::HashMap, pin::Pin};
 
 use formatting::{bold, colored, format_serror, muted, Color};
 use komodo_client::{
@@ -6,8 +6,11 @@ use komodo_client::{
     BatchExecutionResponse, BatchRunProcedure, RunProcedure,
   },
   entities::{
-    permission::PermissionLevel, procedure::Procedure,
-    update::Update, user::User,
+    komodo_timestamp,
+    permission::PermissionLevel,
+    procedure::{Procedure, ProcedureRun, ProcedureState},
+    update::Update,
+    user::User,
   },
 };
 use mungos::{by_id::update_one_by_id, mongodb::bson::to_document};
@@ -15,7 +18,16 @@ use resolver_api::Resolve;
 use tokio::sync::Mutex;
 
 use crate::{
-  helpers::{procedure::execute_procedure, update::update_update},
+  helpers::{
+    arguments::{
+      arguments_object, check_no_target_arguments,
+      interpolate_arguments, resolve_arguments,
+    },
+    procedure::{
+      add_procedure_run, execute_procedure, update_procedure_run,
+    },
+    update::update_update,
+  },
   resource::{self, refresh_procedure_state_cache},
   state::{action_states, db_client, State},
 };
@@ -25,7 +37,10 @@ use super::ExecuteRequest;
 impl super::BatchExecute for BatchRunProcedure {
   type Resource = Procedure;
   fn single_request(procedure: String) -> ExecuteRequest {
-    ExecuteRequest::RunProcedure(RunProcedure { procedure })
+    ExecuteRequest::RunProcedure(RunProcedure {
+      procedure,
+      args: None,
+    })
   }
 }
 
@@ -44,15 +59,16 @@ impl Resolve<RunProcedure, (User, Update)> for State {
   #[instrument(name = "RunProcedure", skip(self, user, update), fields(user_id = user.id, update_id = update.id))]
   async fn resolve(
     &self,
-    RunProcedure { procedure }: RunProcedure,
+    RunProcedure { procedure, args }: RunProcedure,
     (user, update): (User, Update),
   ) -> anyhow::Result<Update> {
-    resolve_inner(procedure, user, update).await
+    resolve_inner(procedure, args, user, update).await
   }
 }
 
 fn resolve_inner(
   procedure: String,
+  args: Option<HashMap<String, String>>,
   user: User,
   mut update: Update,
 ) -> Pin<
@@ -61,7 +77,7 @@ fn resolve_inner(
   >,
 > {
   Box::pin(async move {
-    let procedure = resource::get_check_permissions::<Procedure>(
+    let mut procedure = resource::get_check_permissions::<Procedure>(
       &procedure,
       &user,
       PermissionLevel::Execute,
@@ -71,14 +87,30 @@ fn resolve_inner(
     // Need to push the initial log, as execute_procedure
     // assumes first log is already created
     // and will panic otherwise.
-    update.push_simple_log(
-      "Execute procedure",
-      format!(
-        "{}: executing procedure '{}'",
+    // The scheduler will have already pushed it.
+    if update.logs.is_empty() {
+      update.push_simple_log(
+        "Execute procedure",
+        format!(
+          "{}: executing procedure '{}'",
+          muted("INFO"),
+          bold(&procedure.name)
+        ),
+      );
+    }
+
+    let args = resolve_arguments(&procedure.config.parameters, args)?;
+    check_no_target_arguments(&procedure.config.stages)?;
+    interpolate_arguments(&mut procedure.config.stages, &args)?;
+    if !args.is_empty() {
+      let log = &mut update.logs[0];
+      log.stdout.push('\n');
+      log.stdout.push_str(&format!(
+        "{}: running with arguments {}",
         muted("INFO"),
-        bold(&procedure.name)
-      ),
-    );
+        bold(arguments_object(&args))
+      ));
+    }
 
     // get the action state for the procedure (or insert default).
     let action_state = action_states()
@@ -93,12 +125,37 @@ fn resolve_inner(
 
     update_update(update.clone()).await?;
 
+    let mut run = ProcedureRun {
+      procedure_id: procedure.id.clone(),
+      update_id: update.id.clone(),
+      start_ts: komodo_timestamp(),
+      state: ProcedureState::Running,
+      ..Default::default()
+    };
+    match add_procedure_run(&run).await {
+      Ok(id) => run.id = id,
+      Err(e) => warn!("Failed to record procedure run | {e:#}"),
+    }
+
     let update = Mutex::new(update);
 
-    let res = execute_procedure(&procedure, &update).await;
+    let res =
+      execute_procedure(&procedure, &args, &update, &mut run).await;
 
     let mut update = update.into_inner();
 
+    if !run.id.is_empty() {
+      run.end_ts = Some(komodo_timestamp());
+      run.state = if res.is_ok() {
+        ProcedureState::Ok
+      } else {
+        ProcedureState::Failed
+      };
+      if let Err(e) = update_procedure_run(&run).await {
+        warn!("Failed to save procedure run | {e:#}");
+      }
+    }
+
     match res {
       Ok(_) => {
         update.push_simple_log(
@@ -532,6 +532,7 @@ impl Resolve<BuildRepo, (User, Update)> for State {
             id: repo.id,
             name: repo.name,
           },
+          ..Default::default()
         };
         send_alerts(&[alert]).await
       });
@@ -579,6 +580,7 @@ async fn handle_builder_early_return(
           id: repo_id,
           name: repo_name,
         },
+        ..Default::default()
       };
       send_alerts(&[alert]).await
     });
@@ -372,6 +372,24 @@ impl Resolve<RunSync, (User, Update)> for State {
     } else {
       Default::default()
     };
+    let (
+      maintenance_windows_to_create,
+      maintenance_windows_to_update,
+      maintenance_windows_to_delete,
+    ) = if match_resource_type.is_none()
+      && match_resources.is_none()
+      && sync.config.match_tags.is_empty()
+    {
+      crate::sync::maintenance::get_updates_for_execution(
+        resources.maintenance_windows,
+        delete,
+        &all_resources,
+        &id_to_tags,
+      )
+      .await?
+    } else {
+      Default::default()
+    };
 
     if deploy_cache.is_empty()
       && resource_syncs_to_create.is_empty()
@@ -413,6 +431,9 @@ impl Resolve<RunSync, (User, Update)> for State {
       && variables_to_create.is_empty()
       && variables_to_update.is_empty()
       && variables_to_delete.is_empty()
+      && maintenance_windows_to_create.is_empty()
+      && maintenance_windows_to_update.is_empty()
+      && maintenance_windows_to_delete.is_empty()
     {
       update.push_simple_log(
         "No Changes",
@@ -438,6 +459,15 @@ impl Resolve<RunSync, (User, Update)> for State {
       )
       .await,
     );
+    maybe_extend(
+      &mut update.logs,
+      crate::sync::maintenance::run_updates(
+        maintenance_windows_to_create,
+        maintenance_windows_to_update,
+        maintenance_windows_to_delete,
+      )
+      .await,
+    );
     maybe_extend(
       &mut update.logs,
       crate::sync::user_groups::run_updates(
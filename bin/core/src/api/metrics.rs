@@ -0,0 +1,451 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use anyhow::{anyhow, Context};
+use axum::{
+  http::{header::CONTENT_TYPE, HeaderMap},
+  response::IntoResponse,
+  routing::get,
+  Router,
+};
+use base64::Engine;
+use cache::TimeoutCache;
+use futures::TryStreamExt;
+use komodo_client::entities::{
+  komodo_timestamp, stats::SystemStats, user::User,
+};
+use metrics::Metrics;
+use mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{doc, Bson, Document},
+    options::FindOptions,
+    Collection,
+  },
+};
+use reqwest::StatusCode;
+use serror::AddStatusCode;
+
+use crate::{
+  auth::{auth_api_key_check_enabled, authenticate_check_enabled},
+  state::{
+    db_client, deployment_status_cache, server_status_cache,
+    stack_status_cache,
+  },
+};
+
+pub fn router() -> Router {
+  Router::new().route("/", get(handler))
+}
+
+async fn handler(
+  headers: HeaderMap,
+) -> serror::Result<impl IntoResponse> {
+  let user = authenticate_metrics_request(&headers)
+    .await
+    .status_code(StatusCode::UNAUTHORIZED)?;
+  if !user.admin {
+    return Err(anyhow!("only admins can access metrics"))
+      .status_code(StatusCode::FORBIDDEN);
+  }
+  let metrics = collect_metrics().await?;
+  Ok((
+    [(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+    metrics,
+  ))
+}
+
+/// Accepts the usual jwt / api key headers, as well as
+/// api key and secret passed as basic auth username and password,
+/// which is what Prometheus scrape configs support out of the box.
+async fn authenticate_metrics_request(
+  headers: &HeaderMap,
+) -> anyhow::Result<User> {
+  let basic = headers
+    .get("authorization")
+    .and_then(|auth| auth.to_str().ok())
+    .and_then(|auth| auth.strip_prefix("Basic "));
+  let Some(basic) = basic else {
+    return authenticate_check_enabled(headers).await;
+  };
+  let decoded = base64::engine::general_purpose::STANDARD
+    .decode(basic.trim())
+    .context("failed to decode basic auth")?;
+  let decoded =
+    String::from_utf8(decoded).context("basic auth is not utf8")?;
+  let (key, secret) = decoded
+    .split_once(':')
+    .context("basic auth must be of form key:secret")?;
+  auth_api_key_check_enabled(key, secret).await
+}
+
+async fn collect_metrics() -> anyhow::Result<String> {
+  let mut metrics = Metrics::default();
+
+  let db = db_client();
+  let server_names = names(&db.servers).await?;
+  let deployment_names = names(&db.deployments).await?;
+  let stack_names = names(&db.stacks).await?;
+  let build_names = names(&db.builds).await?;
+
+  // SERVERS
+
+  let servers = server_status_cache().get_list().await;
+
+  metrics.header(
+    "komodo_server_state",
+    "The server state, 1 for the current state.",
+    "gauge",
+  );
+  for status in &servers {
+    let server = name(&server_names, &status.id);
+    let state = status.state.to_string();
+    metrics.sample(
+      "komodo_server_state",
+      &[
+        ("server_id", &status.id),
+        ("server", server),
+        ("state", &state),
+      ],
+      1.0,
+    );
+  }
+
+  let stats = servers
+    .iter()
+    .filter_map(|status| Some((status, status.stats.as_ref()?)))
+    .collect::<Vec<_>>();
+
+  let gauges: [StatsGauge; 5] = [
+    (
+      "komodo_server_cpu_percent",
+      "Server cpu usage percentage.",
+      |stats| stats.cpu_perc as f64,
+    ),
+    (
+      "komodo_server_mem_used_gb",
+      "Server used memory in GB.",
+      |stats| stats.mem_used_gb,
+    ),
+    (
+      "komodo_server_mem_total_gb",
+      "Server total memory in GB.",
+      |stats| stats.mem_total_gb,
+    ),
+    (
+      "komodo_server_network_ingress_bytes",
+      "Server network ingress in bytes.",
+      |stats| stats.network_ingress_bytes,
+    ),
+    (
+      "komodo_server_network_egress_bytes",
+      "Server network egress in bytes.",
+      |stats| stats.network_egress_bytes,
+    ),
+  ];
+  for (metric, help, value) in gauges {
+    metrics.header(metric, help, "gauge");
+    for (status, stats) in &stats {
+      let server = name(&server_names, &status.id);
+      metrics.sample(
+        metric,
+        &[("server_id", &status.id), ("server", server)],
+        value(stats),
+      );
+    }
+  }
+
+  metrics.header(
+    "komodo_server_disk_used_gb",
+    "Server disk used in GB, by mount point.",
+    "gauge",
+  );
+  for (status, stats) in &stats {
+    let server = name(&server_names, &status.id);
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      metrics.sample(
+        "komodo_server_disk_used_gb",
+        &[
+          ("server_id", &status.id),
+          ("server", server),
+          ("mount", &mount),
+        ],
+        disk.used_gb,
+      );
+    }
+  }
+
+  metrics.header(
+    "komodo_server_disk_total_gb",
+    "Server disk size in GB, by mount point.",
+    "gauge",
+  );
+  for (status, stats) in &stats {
+    let server = name(&server_names, &status.id);
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      metrics.sample(
+        "komodo_server_disk_total_gb",
+        &[
+          ("server_id", &status.id),
+          ("server", server),
+          ("mount", &mount),
+        ],
+        disk.total_gb,
+      );
+    }
+  }
+
+  // DEPLOYMENTS
+
+  metrics.header(
+    "komodo_deployment_state",
+    "The deployment state, 1 for the current state.",
+    "gauge",
+  );
+  for status in deployment_status_cache().get_list().await {
+    let deployment = name(&deployment_names, &status.curr.id);
+    let state = status.curr.state.to_string();
+    metrics.sample(
+      "komodo_deployment_state",
+      &[
+        ("deployment_id", &status.curr.id),
+        ("deployment", deployment),
+        ("state", &state),
+      ],
+      1.0,
+    );
+  }
+
+  // STACKS
+
+  metrics.header(
+    "komodo_stack_state",
+    "The stack state, 1 for the current state.",
+    "gauge",
+  );
+  for status in stack_status_cache().get_list().await {
+    let stack = name(&stack_names, &status.curr.id);
+    let state = status.curr.state.to_string();
+    metrics.sample(
+      "komodo_stack_state",
+      &[
+        ("stack_id", &status.curr.id),
+        ("stack", stack),
+        ("state", &state),
+      ],
+      1.0,
+    );
+  }
+
+  // BUILDS
+
+  let UpdateMetrics { builds, updates } = update_metrics().await?;
+
+  metrics.header(
+    "komodo_build_runs_total",
+    "The number of completed builds, by result.",
+    "counter",
+  );
+  for group in &builds {
+    let (build_id, result) = build_group_labels(group);
+    metrics.sample(
+      "komodo_build_runs_total",
+      &[
+        ("build_id", build_id),
+        ("build", name(&build_names, build_id)),
+        ("result", result),
+      ],
+      number(group, "count"),
+    );
+  }
+
+  metrics.header(
+    "komodo_build_duration_seconds_total",
+    "The total time spent on completed builds, by result.",
+    "counter",
+  );
+  for group in &builds {
+    let (build_id, result) = build_group_labels(group);
+    metrics.sample(
+      "komodo_build_duration_seconds_total",
+      &[
+        ("build_id", build_id),
+        ("build", name(&build_names, build_id)),
+        ("result", result),
+      ],
+      number(group, "duration_ms") / 1000.0,
+    );
+  }
+
+  // ALERTS
+
+  let alerts = aggregate(
+    &db.alerts,
+    [
+      doc! { "$match": { "resolved": false } },
+      doc! { "$group": { "_id": "$level", "count": { "$sum": 1 } } },
+    ],
+  )
+  .await?;
+
+  metrics.header(
+    "komodo_open_alerts",
+    "The number of open alerts, by severity level.",
+    "gauge",
+  );
+  for group in &alerts {
+    metrics.sample(
+      "komodo_open_alerts",
+      &[("level", group.get_str("_id").unwrap_or("UNKNOWN"))],
+      number(group, "count"),
+    );
+  }
+
+  // UPDATES
+
+  metrics.header(
+    "komodo_updates_total",
+    "The number of updates, by operation.",
+    "counter",
+  );
+  for group in &updates {
+    metrics.sample(
+      "komodo_updates_total",
+      &[("operation", group.get_str("_id").unwrap_or("None"))],
+      number(group, "count"),
+    );
+  }
+
+  Ok(metrics.into_string())
+}
+
+/// The aggregations over the updates collection,
+/// which grows without bound.
+#[derive(Clone, Default)]
+struct UpdateMetrics {
+  builds: Vec<Document>,
+  updates: Vec<Document>,
+}
+
+/// Scrape at most this often to aggregate the updates again.
+const UPDATE_METRICS_TIMEOUT: i64 = 60_000;
+
+fn update_metrics_cache() -> &'static TimeoutCache<(), UpdateMetrics>
+{
+  static UPDATE_METRICS_CACHE: OnceLock<
+    TimeoutCache<(), UpdateMetrics>,
+  > = OnceLock::new();
+  UPDATE_METRICS_CACHE.get_or_init(Default::default)
+}
+
+async fn update_metrics() -> anyhow::Result<UpdateMetrics> {
+  let lock = update_metrics_cache().get_lock(()).await;
+
+  // Concurrent scrapes wait for the first to finish,
+  // then use its cached result.
+  let mut locked = lock.lock().await;
+  if locked.last_ts + UPDATE_METRICS_TIMEOUT > komodo_timestamp() {
+    return locked.clone_res();
+  }
+
+  let res = async {
+    let db = db_client();
+    let builds = aggregate(
+      &db.updates,
+      [
+        doc! { "$match": { "operation": "RunBuild", "status": "Complete" } },
+        doc! { "$group": {
+          "_id": { "build": "$target.id", "success": "$success" },
+          "count": { "$sum": 1 },
+          "duration_ms": { "$sum": { "$subtract": ["$end_ts", "$start_ts"] } },
+        } },
+      ],
+    )
+    .await?;
+    // Sorting on the indexed operation lets the count
+    // be read from the index, without loading the updates.
+    let updates = aggregate(
+      &db.updates,
+      [
+        doc! { "$sort": { "operation": 1 } },
+        doc! { "$group": { "_id": "$operation", "count": { "$sum": 1 } } },
+      ],
+    )
+    .await?;
+    anyhow::Ok(UpdateMetrics { builds, updates })
+  }
+  .await;
+
+  locked.set(&res, komodo_timestamp());
+
+  res
+}
+
+/// (metric, help, value)
+type StatsGauge =
+  (&'static str, &'static str, fn(&SystemStats) -> f64);
+
+async fn names<T: Send + Sync>(
+  collection: &Collection<T>,
+) -> anyhow::Result<HashMap<String, String>> {
+  let names = find_collect(
+    &collection.clone_with_type::<Document>(),
+    None,
+    FindOptions::builder()
+      .projection(doc! { "name": 1 })
+      .build(),
+  )
+  .await
+  .context("failed to get resource names from db")?
+  .into_iter()
+  .filter_map(|doc| {
+    Some((
+      doc.get_object_id("_id").ok()?.to_hex(),
+      doc.get_str("name").ok()?.to_string(),
+    ))
+  })
+  .collect();
+  Ok(names)
+}
+
+fn name<'a>(names: &'a HashMap<String, String>, id: &str) -> &'a str {
+  names.get(id).map(String::as_str).unwrap_or("unknown")
+}
+
+async fn aggregate<T: Send + Sync>(
+  collection: &Collection<T>,
+  pipeline: impl IntoIterator<Item = Document>,
+) -> anyhow::Result<Vec<Document>> {
+  collection
+    .aggregate(pipeline)
+    .await
+    .context("failed to aggregate metrics on db")?
+    .try_collect()
+    .await
+    .context("failed to collect metrics aggregation")
+}
+
+fn build_group_labels(group: &Document) -> (&str, &str) {
+  let id = group.get_document("_id").ok();
+  let build_id = id
+    .and_then(|id| id.get_str("build").ok())
+    .unwrap_or_default();
+  let result =
+    if id.and_then(|id| id.get_bool("success").ok()) == Some(true) {
+      "success"
+    } else {
+      "failure"
+    };
+  (build_id, result)
+}
+
+/// Aggregation numbers may come back as i32, i64, or f64.
+fn number(doc: &Document, key: &str) -> f64 {
+  match doc.get(key) {
+    Some(Bson::Int32(n)) => *n as f64,
+    Some(Bson::Int64(n)) => *n as f64,
+    Some(Bson::Double(n)) => *n,
+    _ => 0.0,
+  }
+}
@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod execute;
+pub mod metrics;
 pub mod read;
 pub mod user;
 pub mod write;
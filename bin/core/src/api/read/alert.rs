@@ -1,19 +1,31 @@
 use anyhow::Context;
+use futures::TryStreamExt;
 use komodo_client::{
   api::read::{
-    GetAlert, GetAlertResponse, ListAlerts, ListAlertsResponse,
+    AlertStats, GetAlert, GetAlertResponse, GetAlertStats,
+    GetAlertStatsResponse, ListAlerts, ListAlertsResponse,
   },
   entities::{
-    deployment::Deployment, server::Server, stack::Stack,
-    sync::ResourceSync, user::User,
+    alert::{AlertDataVariant, SeverityLevel},
+    deployment::Deployment,
+    komodo_timestamp,
+    server::Server,
+    stack::Stack,
+    sync::ResourceSync,
+    user::User,
+    ResourceTarget,
   },
 };
 use mungos::{
   by_id::find_one_by_id,
   find::find_collect,
-  mongodb::{bson::doc, options::FindOptions},
+  mongodb::{
+    bson::{doc, from_document, to_bson, Document},
+    options::FindOptions,
+  },
 };
 use resolver_api::Resolve;
+use serde::Deserialize;
 
 use crate::{
   config::core_config,
@@ -26,27 +38,28 @@ const NUM_ALERTS_PER_PAGE: u64 = 100;
 impl Resolve<ListAlerts, User> for State {
   async fn resolve(
     &self,
-    ListAlerts { query, page }: ListAlerts,
+    ListAlerts {
+      query,
+      acknowledged,
+      page,
+    }: ListAlerts,
     user: User,
   ) -> anyhow::Result<ListAlertsResponse> {
     let mut query = query.unwrap_or_default();
-    if !user.admin && !core_config().transparent_mode {
-      let server_ids =
-        get_resource_ids_for_user::<Server>(&user).await?;
-      let stack_ids =
-        get_resource_ids_for_user::<Stack>(&user).await?;
-      let deployment_ids =
-        get_resource_ids_for_user::<Deployment>(&user).await?;
-      let sync_ids =
-        get_resource_ids_for_user::<ResourceSync>(&user).await?;
-      query.extend(doc! {
-        "$or": [
-          { "target.type": "Server", "target.id": { "$in": &server_ids } },
-          { "target.type": "Stack", "target.id": { "$in": &stack_ids } },
-          { "target.type": "Deployment", "target.id": { "$in": &deployment_ids } },
-          { "target.type": "ResourceSync", "target.id": { "$in": &sync_ids } },
-        ]
-      });
+    match acknowledged {
+      Some(true) => {
+        query.insert("acknowledged", doc! { "$type": "object" });
+      }
+      Some(false) => {
+        query.insert(
+          "acknowledged",
+          doc! { "$not": { "$type": "object" } },
+        );
+      }
+      None => {}
+    }
+    if let Some(filter) = user_alerts_filter(&user).await? {
+      query.extend(filter);
     }
 
     let alerts = find_collect(
@@ -85,3 +98,145 @@ impl Resolve<GetAlert, User> for State {
       .context("no alert found with given id")
   }
 }
+
+const ONE_WEEK_MS: i64 = 7 * 86400000;
+
+impl Resolve<GetAlertStats, User> for State {
+  async fn resolve(
+    &self,
+    GetAlertStats {
+      from,
+      to,
+      targets,
+      alert_types,
+    }: GetAlertStats,
+    user: User,
+  ) -> anyhow::Result<GetAlertStatsResponse> {
+    let now = komodo_timestamp();
+    let to = to.unwrap_or(now);
+    let from = from.unwrap_or(to - ONE_WEEK_MS);
+
+    let mut filter = doc! { "ts": { "$gte": from, "$lt": to } };
+    if !targets.is_empty() {
+      let targets = targets
+        .iter()
+        .map(to_bson)
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to serialize targets")?;
+      filter.insert("target", doc! { "$in": targets });
+    }
+    if !alert_types.is_empty() {
+      let alert_types = alert_types
+        .iter()
+        .map(to_bson)
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to serialize alert types")?;
+      filter.insert("data.type", doc! { "$in": alert_types });
+    }
+    if let Some(user_filter) = user_alerts_filter(&user).await? {
+      filter.extend(user_filter);
+    }
+
+    let groups = db_client()
+      .alerts
+      .aggregate([
+        doc! { "$match": filter },
+        doc! { "$sort": { "ts": 1 } },
+        doc! { "$group": {
+          "_id": {
+            "target": "$target",
+            "alert_type": "$data.type",
+            "level": "$level",
+          },
+          "name": { "$last": { "$ifNull": ["$data.data.name", ""] } },
+          "count": { "$sum": 1 },
+          "resolved": { "$sum": { "$cond": ["$resolved", 1, 0] } },
+          "mean_time_to_resolve_ms": { "$avg": {
+            "$cond": [
+              { "$and": ["$resolved", "$resolved_ts"] },
+              { "$subtract": ["$resolved_ts", "$ts"] },
+              null,
+            ]
+          } },
+          "oldest_open_ts": { "$min": {
+            "$cond": ["$resolved", null, "$ts"]
+          } },
+        } },
+        doc! { "$sort": { "count": -1 } },
+      ])
+      .await
+      .context("failed to aggregate alert stats")?
+      .try_collect::<Vec<_>>()
+      .await
+      .context("failed to collect alert stats")?;
+
+    let stats = groups
+      .into_iter()
+      .map(|group| {
+        from_document::<AlertStatsGroup>(group)
+          .context("failed to parse alert stats")
+          .map(|group| group.into_stats(now))
+      })
+      .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(GetAlertStatsResponse { from, to, stats })
+  }
+}
+
+#[derive(Deserialize)]
+struct AlertStatsGroup {
+  #[serde(rename = "_id")]
+  id: AlertStatsGroupId,
+  name: String,
+  count: i64,
+  resolved: i64,
+  mean_time_to_resolve_ms: Option<f64>,
+  oldest_open_ts: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct AlertStatsGroupId {
+  target: ResourceTarget,
+  alert_type: AlertDataVariant,
+  level: SeverityLevel,
+}
+
+impl AlertStatsGroup {
+  fn into_stats(self, now: i64) -> AlertStats {
+    AlertStats {
+      target: self.id.target,
+      name: self.name,
+      alert_type: self.id.alert_type,
+      level: self.id.level,
+      count: self.count,
+      resolved: self.resolved,
+      mean_time_to_resolve_ms: self.mean_time_to_resolve_ms,
+      open: self.count - self.resolved,
+      open_duration_ms: self.oldest_open_ts.map(|ts| now - ts),
+    }
+  }
+}
+
+/// Non admins can only see alerts on the resources they have access to,
+/// unless Core is in transparent mode.
+async fn user_alerts_filter(
+  user: &User,
+) -> anyhow::Result<Option<Document>> {
+  if user.admin || core_config().transparent_mode {
+    return Ok(None);
+  }
+  let server_ids = get_resource_ids_for_user::<Server>(user).await?;
+  let stack_ids = get_resource_ids_for_user::<Stack>(user).await?;
+  let deployment_ids =
+    get_resource_ids_for_user::<Deployment>(user).await?;
+  let sync_ids =
+    get_resource_ids_for_user::<ResourceSync>(user).await?;
+  Ok(Some(doc! {
+    "$or": [
+      { "target.type": "Server", "target.id": { "$in": &server_ids } },
+      { "target.type": "Stack", "target.id": { "$in": &stack_ids } },
+      { "target.type": "Deployment", "target.id": { "$in": &deployment_ids } },
+      { "target.type": "ResourceSync", "target.id": { "$in": &sync_ids } },
+    ]
+  }))
+}
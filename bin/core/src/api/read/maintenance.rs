@@ -0,0 +1,54 @@
+use anyhow::Context;
+use komodo_client::{
+  api::read::{
+    GetMaintenanceWindow, GetMaintenanceWindowResponse,
+    ListMaintenanceWindows, ListMaintenanceWindowsResponse,
+  },
+  entities::{komodo_timestamp, user::User},
+};
+use mongo_indexed::doc;
+use mungos::{find::find_collect, mongodb::options::FindOptions};
+use resolver_api::Resolve;
+
+use crate::{
+  alert::maintenance_window_active,
+  helpers::query::get_maintenance_window,
+  state::{db_client, State},
+};
+
+impl Resolve<GetMaintenanceWindow, User> for State {
+  async fn resolve(
+    &self,
+    GetMaintenanceWindow { name }: GetMaintenanceWindow,
+    _: User,
+  ) -> anyhow::Result<GetMaintenanceWindowResponse> {
+    get_maintenance_window(&name).await
+  }
+}
+
+impl Resolve<ListMaintenanceWindows, User> for State {
+  async fn resolve(
+    &self,
+    ListMaintenanceWindows { active }: ListMaintenanceWindows,
+    _: User,
+  ) -> anyhow::Result<ListMaintenanceWindowsResponse> {
+    let windows = find_collect(
+      &db_client().maintenance_windows,
+      None,
+      FindOptions::builder().sort(doc! { "name": 1 }).build(),
+    )
+    .await
+    .context("failed to query db for maintenance windows")?;
+    if !active {
+      return Ok(windows);
+    }
+    let ts = komodo_timestamp();
+    let windows = windows
+      .into_iter()
+      .filter(|window| {
+        maintenance_window_active(window, ts).unwrap_or_default()
+      })
+      .collect();
+    Ok(windows)
+  }
+}
@@ -35,7 +35,9 @@ mod alerter;
 mod build;
 mod builder;
 mod deployment;
+mod maintenance;
 mod permission;
+mod probe;
 mod procedure;
 mod provider;
 mod repo;
@@ -86,6 +88,8 @@ enum ReadRequest {
   GetProceduresSummary(GetProceduresSummary),
   GetProcedure(GetProcedure),
   GetProcedureActionState(GetProcedureActionState),
+  GetProcedureRun(GetProcedureRun),
+  ListProcedureRuns(ListProcedureRuns),
   ListProcedures(ListProcedures),
   ListFullProcedures(ListFullProcedures),
 
@@ -109,6 +113,7 @@ enum ReadRequest {
   GetPeripheryVersion(GetPeripheryVersion),
   GetServerActionState(GetServerActionState),
   GetHistoricalServerStats(GetHistoricalServerStats),
+  GetHistoricalContainerStats(GetHistoricalContainerStats),
   ListServers(ListServers),
   ListFullServers(ListFullServers),
   InspectDockerContainer(InspectDockerContainer),
@@ -210,6 +215,10 @@ enum ReadRequest {
   // ==== ALERT ====
   ListAlerts(ListAlerts),
   GetAlert(GetAlert),
+  GetAlertStats(GetAlertStats),
+
+  // ==== PROBE ====
+  ListProbeRecords(ListProbeRecords),
 
   // ==== SERVER STATS ====
   #[to_string_resolver]
@@ -223,6 +232,10 @@ enum ReadRequest {
   GetVariable(GetVariable),
   ListVariables(ListVariables),
 
+  // ==== MAINTENANCE WINDOW ====
+  GetMaintenanceWindow(GetMaintenanceWindow),
+  ListMaintenanceWindows(ListMaintenanceWindows),
+
   // ==== PROVIDER ====
   GetGitProviderAccount(GetGitProviderAccount),
   ListGitProviderAccounts(ListGitProviderAccounts),
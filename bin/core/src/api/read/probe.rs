@@ -0,0 +1,88 @@
+use anyhow::{anyhow, Context};
+use komodo_client::{
+  api::read::{ListProbeRecords, ListProbeRecordsResponse},
+  entities::{
+    deployment::Deployment, permission::PermissionLevel,
+    stack::Stack, user::User, ResourceTarget,
+  },
+};
+use mungos::{
+  find::find_collect,
+  mongodb::{bson::doc, options::FindOptions},
+};
+use resolver_api::Resolve;
+
+use crate::{
+  resource,
+  state::{db_client, State},
+};
+
+const PROBE_RECORDS_PER_PAGE: u64 = 200;
+
+impl Resolve<ListProbeRecords, User> for State {
+  async fn resolve(
+    &self,
+    ListProbeRecords {
+      target,
+      endpoint,
+      page,
+    }: ListProbeRecords,
+    user: User,
+  ) -> anyhow::Result<ListProbeRecordsResponse> {
+    let (variant, id) = match &target {
+      ResourceTarget::Deployment(id) => {
+        let deployment =
+          resource::get_check_permissions::<Deployment>(
+            id,
+            &user,
+            PermissionLevel::Read,
+          )
+          .await?;
+        ("Deployment", deployment.id)
+      }
+      ResourceTarget::Stack(id) => {
+        let stack = resource::get_check_permissions::<Stack>(
+          id,
+          &user,
+          PermissionLevel::Read,
+        )
+        .await?;
+        ("Stack", stack.id)
+      }
+      _ => {
+        return Err(anyhow!(
+          "probe records target must be a Deployment or Stack"
+        ))
+      }
+    };
+
+    let mut filter = doc! {
+      "target.type": variant,
+      "target.id": id,
+    };
+    if let Some(endpoint) = endpoint {
+      filter.insert("endpoint", endpoint);
+    }
+
+    let records = find_collect(
+      &db_client().probes,
+      filter,
+      FindOptions::builder()
+        .sort(doc! { "ts": -1 })
+        .skip(page as u64 * PROBE_RECORDS_PER_PAGE)
+        .limit(PROBE_RECORDS_PER_PAGE as i64)
+        .build(),
+    )
+    .await
+    .context("failed to get probe records from db")?;
+
+    let next_page =
+      if records.len() == PROBE_RECORDS_PER_PAGE as usize {
+        Some(page + 1)
+      } else {
+        None
+      };
+
+    Ok(ListProbeRecordsResponse { records, next_page })
+  }
+}
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use anyhow::Context;
 use komodo_client::{
   api::read::*,
@@ -7,12 +9,19 @@ use komodo_client::{
     user::User,
   },
 };
+use mungos::{
+  find::find_collect,
+  mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::FindOptions,
+  },
+};
 use resolver_api::Resolve;
 
 use crate::{
   helpers::query::get_all_tags,
   resource,
-  state::{action_states, procedure_state_cache, State},
+  state::{action_states, db_client, procedure_state_cache, State},
 };
 
 impl Resolve<GetProcedure, User> for State {
@@ -129,3 +138,68 @@ impl Resolve<GetProcedureActionState, User> for State {
     Ok(action_state)
   }
 }
+
+const NUM_RUNS_PER_PAGE: u64 = 50;
+
+impl Resolve<ListProcedureRuns, User> for State {
+  async fn resolve(
+    &self,
+    ListProcedureRuns { procedure, page }: ListProcedureRuns,
+    user: User,
+  ) -> anyhow::Result<ListProcedureRunsResponse> {
+    let procedure = resource::get_check_permissions::<Procedure>(
+      &procedure,
+      &user,
+      PermissionLevel::Read,
+    )
+    .await?;
+
+    let runs = find_collect(
+      &db_client().procedure_runs,
+      doc! { "procedure_id": &procedure.id },
+      FindOptions::builder()
+        .sort(doc! { "start_ts": -1 })
+        .limit(NUM_RUNS_PER_PAGE as i64)
+        .skip(page * NUM_RUNS_PER_PAGE)
+        .build(),
+    )
+    .await
+    .context("failed to get procedure runs from db")?;
+
+    let next_page = if runs.len() < NUM_RUNS_PER_PAGE as usize {
+      None
+    } else {
+      Some((page + 1) as i64)
+    };
+
+    Ok(ListProcedureRunsResponse { runs, next_page })
+  }
+}
+
+impl Resolve<GetProcedureRun, User> for State {
+  async fn resolve(
+    &self,
+    GetProcedureRun { id }: GetProcedureRun,
+    user: User,
+  ) -> anyhow::Result<GetProcedureRunResponse> {
+    let filter = match ObjectId::from_str(&id) {
+      Ok(oid) => {
+        doc! { "$or": [{ "_id": oid }, { "update_id": &id }] }
+      }
+      Err(_) => doc! { "update_id": &id },
+    };
+    let run = db_client()
+      .procedure_runs
+      .find_one(filter)
+      .await
+      .context("failed to query db for procedure run")?
+      .context("no procedure run found with given id")?;
+    resource::get_check_permissions::<Procedure>(
+      &run.procedure_id,
+      &user,
+      PermissionLevel::Read,
+    )
+    .await?;
+    Ok(run)
+  }
+}
@@ -7,6 +7,7 @@ use std::{
 use anyhow::{anyhow, Context};
 use async_timing_util::{
   get_timelength_in_ms, unix_timestamp_ms, FIFTEEN_SECONDS_MS,
+  FIVE_MIN_MS, ONE_DAY_MS, ONE_HOUR_MS,
 };
 use komodo_client::{
   api::read::*,
@@ -23,14 +24,18 @@ use komodo_client::{
       Server, ServerActionState, ServerListItem, ServerState,
     },
     stack::{Stack, StackServiceNames},
+    stats::{SystemStatsRecord, SystemStatsRollup},
     update::Log,
     user::User,
-    ResourceTarget,
+    ResourceTarget, Timelength,
   },
 };
 use mungos::{
   find::find_collect,
-  mongodb::{bson::doc, options::FindOptions},
+  mongodb::{
+    bson::{doc, Bson},
+    options::FindOptions,
+  },
 };
 use periphery_client::api::{
   self as periphery,
@@ -43,9 +48,13 @@ use resolver_api::{Resolve, ResolveToString};
 use tokio::sync::Mutex;
 
 use crate::{
+  config::core_config,
   helpers::{periphery_client, query::get_all_tags},
   resource,
-  stack::compose_container_match_regex,
+  stack::{
+    compose_container_match_regex,
+    services::extract_services_from_stack,
+  },
   state::{action_states, db_client, server_status_cache, State},
 };
 
@@ -325,10 +334,35 @@ impl Resolve<GetHistoricalServerStats, User> for State {
       get_timelength_in_ms(granularity.to_string().parse().unwrap())
         as i64;
     let mut ts_vec = Vec::<i64>::new();
-    let curr_ts = unix_timestamp_ms() as i64;
-    let mut curr_ts = curr_ts
-      - curr_ts % granularity
+    let now = unix_timestamp_ms() as i64;
+    let mut curr_ts = now
+      - now % granularity
       - granularity * STATS_PER_PAGE * page as i64;
+
+    // Coarse granularities, and time ranges which reach past
+    // the raw stats retention, are served from the rollups.
+    let oldest_ts = curr_ts - granularity * (STATS_PER_PAGE - 1);
+    let keep_stats_for_days =
+      core_config().keep_stats_for_days as i64;
+    let raw_available = keep_stats_for_days == 0
+      || oldest_ts >= now - keep_stats_for_days * ONE_DAY_MS as i64;
+    if granularity >= FIVE_MIN_MS as i64 || !raw_available {
+      let rollup = if granularity >= ONE_HOUR_MS as i64 {
+        Timelength::OneHour
+      } else {
+        Timelength::FiveMinutes
+      };
+      return get_stats_from_rollups(
+        &server.id,
+        rollup,
+        granularity,
+        oldest_ts,
+        curr_ts + granularity,
+        page,
+      )
+      .await;
+    }
+
     for _ in 0..STATS_PER_PAGE {
       ts_vec.push(curr_ts);
       curr_ts -= granularity;
@@ -358,6 +392,190 @@ impl Resolve<GetHistoricalServerStats, User> for State {
   }
 }
 
+impl Resolve<GetHistoricalContainerStats, User> for State {
+  async fn resolve(
+    &self,
+    GetHistoricalContainerStats {
+      target,
+      service,
+      granularity,
+      page,
+    }: GetHistoricalContainerStats,
+    user: User,
+  ) -> anyhow::Result<GetHistoricalContainerStatsResponse> {
+    let (server_id, name_filter) = match target {
+      ResourceTarget::Deployment(deployment) => {
+        let deployment =
+          resource::get_check_permissions::<Deployment>(
+            &deployment,
+            &user,
+            PermissionLevel::Read,
+          )
+          .await?;
+        (deployment.config.server_id, Bson::String(deployment.name))
+      }
+      ResourceTarget::Stack(stack) => {
+        let service = service.context(
+          "must pass the service to get container stats for a stack",
+        )?;
+        let stack = resource::get_check_permissions::<Stack>(
+          &stack,
+          &user,
+          PermissionLevel::Read,
+        )
+        .await?;
+        let container_name = extract_services_from_stack(&stack)
+          .into_iter()
+          .find(|s| s.service_name == service)
+          .with_context(|| {
+            format!("did not find service {service} on stack")
+          })?
+          .container_name;
+        let regex = compose_container_match_regex(&container_name)?;
+        (
+          stack.config.server_id,
+          Bson::Document(doc! { "$regex": regex.as_str() }),
+        )
+      }
+      _ => {
+        return Err(anyhow!(
+          "container stats target must be a Deployment or Stack"
+        ))
+      }
+    };
+    if server_id.is_empty() {
+      return Err(anyhow!("target has no server attached"));
+    }
+
+    let granularity =
+      get_timelength_in_ms(granularity.to_string().parse().unwrap())
+        as i64;
+    let mut ts_vec = Vec::<i64>::new();
+    let curr_ts = unix_timestamp_ms() as i64;
+    let mut curr_ts = curr_ts
+      - curr_ts % granularity
+      - granularity * STATS_PER_PAGE * page as i64;
+    for _ in 0..STATS_PER_PAGE {
+      ts_vec.push(curr_ts);
+      curr_ts -= granularity;
+    }
+
+    let stats = find_collect(
+      &db_client().container_stats,
+      doc! {
+        "sid": &server_id,
+        "name": &name_filter,
+        "ts": { "$in": ts_vec },
+      },
+      FindOptions::builder().sort(doc! { "ts": -1 }).build(),
+    )
+    .await
+    .context("failed to pull container stats from db")?;
+
+    let older = db_client()
+      .container_stats
+      .find_one(doc! {
+        "sid": &server_id,
+        "name": &name_filter,
+        "ts": { "$lte": curr_ts },
+      })
+      .await
+      .context("failed to query db for older container stats")?;
+    let next_page = older.map(|_| page + 1);
+
+    Ok(GetHistoricalContainerStatsResponse { stats, next_page })
+  }
+}
+
+/// Gets the stats between `start_ts` and `end_ts` from the rollups,
+/// combining them into periods of length `granularity`.
+async fn get_stats_from_rollups(
+  server_id: &str,
+  rollup: Timelength,
+  granularity: i64,
+  start_ts: i64,
+  end_ts: i64,
+  page: u32,
+) -> anyhow::Result<GetHistoricalServerStatsResponse> {
+  let rollups = find_collect(
+    &db_client().stats_rollups,
+    doc! {
+      "sid": server_id,
+      "granularity": rollup.to_string(),
+      "ts": { "$gte": start_ts, "$lt": end_ts },
+    },
+    FindOptions::builder().sort(doc! { "ts": -1 }).build(),
+  )
+  .await
+  .context("failed to pull stats rollups from db")?;
+
+  let mut stats = Vec::<SystemStatsRecord>::new();
+  let mut samples = 0;
+  for rollup in rollups {
+    let ts = rollup.ts - rollup.ts % granularity;
+    match stats.last_mut() {
+      Some(curr) if curr.ts == ts => {
+        combine_rollup(curr, samples, &rollup);
+        samples += rollup.samples;
+      }
+      _ => {
+        samples = rollup.samples;
+        let mut record = SystemStatsRecord::from(rollup);
+        record.ts = ts;
+        stats.push(record);
+      }
+    }
+  }
+
+  let older = db_client()
+    .stats_rollups
+    .find_one(doc! {
+      "sid": server_id,
+      "granularity": rollup.to_string(),
+      "ts": { "$lt": start_ts },
+    })
+    .await
+    .context("failed to query db for older stats rollups")?;
+  let next_page = older.map(|_| page + 1);
+
+  Ok(GetHistoricalServerStatsResponse { stats, next_page })
+}
+
+/// Adds the rollup into the record, where the record
+/// is already the average over `samples` raw stats.
+fn combine_rollup(
+  record: &mut SystemStatsRecord,
+  samples: i64,
+  rollup: &SystemStatsRollup,
+) {
+  let total = (samples + rollup.samples).max(1) as f64;
+  let weight = samples as f64 / total;
+  let rollup_weight = rollup.samples as f64 / total;
+  let avg =
+    |curr: f64, other: f64| curr * weight + other * rollup_weight;
+  record.cpu_perc =
+    avg(record.cpu_perc as f64, rollup.cpu_perc as f64) as f32;
+  record.mem_used_gb = avg(record.mem_used_gb, rollup.mem_used_gb);
+  record.mem_total_gb = record.mem_total_gb.max(rollup.mem_total_gb);
+  record.disk_used_gb = avg(record.disk_used_gb, rollup.disk_used_gb);
+  record.disk_total_gb =
+    record.disk_total_gb.max(rollup.disk_total_gb);
+  record.network_ingress_bytes =
+    avg(record.network_ingress_bytes, rollup.network_ingress_bytes);
+  record.network_egress_bytes =
+    avg(record.network_egress_bytes, rollup.network_egress_bytes);
+  let max = record.max.get_or_insert_with(Default::default);
+  max.cpu_perc = max.cpu_perc.max(rollup.max.cpu_perc);
+  max.mem_used_gb = max.mem_used_gb.max(rollup.max.mem_used_gb);
+  max.disk_used_gb = max.disk_used_gb.max(rollup.max.disk_used_gb);
+  max.network_ingress_bytes = max
+    .network_ingress_bytes
+    .max(rollup.max.network_ingress_bytes);
+  max.network_egress_bytes = max
+    .network_egress_bytes
+    .max(rollup.max.network_egress_bytes);
+}
+
 impl ResolveToString<ListDockerContainers, User> for State {
   async fn resolve_to_string(
     &self,
@@ -119,7 +119,8 @@ impl Resolve<GetResourceSyncsSummary, User> for State {
       if !(resource_sync.info.pending_deploy.to_deploy == 0
         && resource_sync.info.resource_updates.is_empty()
         && resource_sync.info.variable_updates.is_empty()
-        && resource_sync.info.user_group_updates.is_empty())
+        && resource_sync.info.user_group_updates.is_empty()
+        && resource_sync.info.maintenance_window_updates.is_empty())
       {
         res.pending += 1;
         continue;
@@ -24,6 +24,7 @@ use crate::{
   resource,
   state::{db_client, State},
   sync::{
+    maintenance::convert_maintenance_window,
     toml::{convert_resource, ToToml, TOML_PRETTY_OPTIONS},
     user_groups::convert_user_groups,
     AllResourcesById,
@@ -173,6 +174,7 @@ impl Resolve<ExportAllResourcesToToml, User> for State {
           targets,
           user_groups,
           include_variables: tags.is_empty(),
+          include_maintenance_windows: tags.is_empty(),
         },
         user,
       )
@@ -187,6 +189,7 @@ impl Resolve<ExportResourcesToToml, User> for State {
       targets,
       user_groups,
       include_variables,
+      include_maintenance_windows,
     }: ExportResourcesToToml,
     user: User,
   ) -> anyhow::Result<ExportResourcesToTomlResponse> {
@@ -387,6 +390,18 @@ impl Resolve<ExportResourcesToToml, User> for State {
           .collect();
     }
 
+    if include_maintenance_windows {
+      res.maintenance_windows =
+        find_collect(&db_client().maintenance_windows, None, None)
+          .await
+          .context("failed to get maintenance windows from db")?
+          .into_iter()
+          .map(|window| {
+            convert_maintenance_window(window, &all, &id_to_tags)
+          })
+          .collect();
+    }
+
     let toml = serialize_resources_toml(res)
       .context("failed to serialize resources to toml")?;
 
@@ -518,6 +533,17 @@ fn serialize_resources_toml(
     );
   }
 
+  for window in &resources.maintenance_windows {
+    if !toml.is_empty() {
+      toml.push_str("\n\n##\n\n");
+    }
+    toml.push_str("[[maintenance_window]]\n");
+    toml.push_str(
+      &toml_pretty::to_string(window, TOML_PRETTY_OPTIONS)
+        .context("failed to serialize maintenance windows to toml")?,
+    );
+  }
+
   for user_group in &resources.user_groups {
     if !toml.is_empty() {
       toml.push_str("\n\n##\n\n");
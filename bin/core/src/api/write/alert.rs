@@ -0,0 +1,145 @@
+use anyhow::{anyhow, Context};
+use derive_variants::ExtractVariant;
+use komodo_client::{
+  api::write::{
+    AcknowledgeAlert, AcknowledgeAlertResponse, SnoozeAlert,
+    SnoozeAlertResponse,
+  },
+  entities::{
+    alert::{Alert, AlertAcknowledgement},
+    komodo_timestamp,
+    permission::PermissionLevel,
+    user::User,
+    Operation,
+  },
+};
+use mungos::{
+  by_id::{find_one_by_id, update_one_by_id},
+  mongodb::bson::{doc, to_bson},
+};
+use resolver_api::Resolve;
+
+use crate::{
+  helpers::{
+    query::get_user_permission_on_target,
+    update::{add_update, make_update},
+  },
+  state::{db_client, State},
+};
+
+impl Resolve<AcknowledgeAlert, User> for State {
+  #[instrument(name = "AcknowledgeAlert", skip(self, user))]
+  async fn resolve(
+    &self,
+    AcknowledgeAlert { id }: AcknowledgeAlert,
+    user: User,
+  ) -> anyhow::Result<AcknowledgeAlertResponse> {
+    let alert = get_open_alert_check_permissions(&id, &user).await?;
+
+    let acknowledgement = AlertAcknowledgement {
+      user_id: user.id.clone(),
+      ts: komodo_timestamp(),
+    };
+
+    update_one_by_id(
+      &db_client().alerts,
+      &id,
+      doc! { "$set": {
+        "acknowledged": to_bson(&acknowledgement)
+          .context("failed to serialize acknowledgement")?
+      } },
+      None,
+    )
+    .await
+    .context("failed to acknowledge alert on db")?;
+
+    let mut update =
+      make_update(alert.target, Operation::AcknowledgeAlert, &user);
+    update.push_simple_log(
+      "acknowledge alert",
+      format!(
+        "acknowledged {:?} alert | id: {id}",
+        alert.data.extract_variant()
+      ),
+    );
+    update.finalize();
+    add_update(update).await?;
+
+    get_alert(&id).await
+  }
+}
+
+impl Resolve<SnoozeAlert, User> for State {
+  #[instrument(name = "SnoozeAlert", skip(self, user))]
+  async fn resolve(
+    &self,
+    SnoozeAlert { id, minutes }: SnoozeAlert,
+    user: User,
+  ) -> anyhow::Result<SnoozeAlertResponse> {
+    if minutes < 0 {
+      return Err(anyhow!("snooze minutes cannot be negative"));
+    }
+
+    let alert = get_open_alert_check_permissions(&id, &user).await?;
+
+    let snoozed_until = if minutes == 0 {
+      0
+    } else {
+      komodo_timestamp() + minutes * 60_000
+    };
+
+    update_one_by_id(
+      &db_client().alerts,
+      &id,
+      doc! { "$set": { "snoozed_until": snoozed_until } },
+      None,
+    )
+    .await
+    .context("failed to snooze alert on db")?;
+
+    let mut update =
+      make_update(alert.target, Operation::SnoozeAlert, &user);
+    let log = if minutes == 0 {
+      format!(
+        "ended snooze on {:?} alert | id: {id}",
+        alert.data.extract_variant()
+      )
+    } else {
+      format!(
+        "snoozed {:?} alert for {minutes} minutes | id: {id}",
+        alert.data.extract_variant()
+      )
+    };
+    update.push_simple_log("snooze alert", log);
+    update.finalize();
+    add_update(update).await?;
+
+    get_alert(&id).await
+  }
+}
+
+async fn get_open_alert_check_permissions(
+  id: &str,
+  user: &User,
+) -> anyhow::Result<Alert> {
+  let alert = get_alert(id).await?;
+  if alert.resolved {
+    return Err(anyhow!("alert is already resolved"));
+  }
+  if !user.admin
+    && get_user_permission_on_target(user, &alert.target).await?
+      < PermissionLevel::Execute
+  {
+    return Err(anyhow!(
+      "user does not have execute permissions on the alert target"
+    ));
+  }
+  Ok(alert)
+}
+
+async fn get_alert(id: &str) -> anyhow::Result<Alert> {
+  find_one_by_id(&db_client().alerts, id)
+    .await
+    .context("failed to query db for alert")?
+    .context("no alert found with given id")
+}
@@ -0,0 +1,177 @@
+use anyhow::{anyhow, Context};
+use komodo_client::{
+  api::write::{
+    CreateMaintenanceWindow, CreateMaintenanceWindowResponse,
+    DeleteMaintenanceWindow, DeleteMaintenanceWindowResponse,
+    UpdateMaintenanceWindow, UpdateMaintenanceWindowResponse,
+  },
+  entities::{
+    komodo_timestamp, maintenance::MaintenanceWindow, user::User,
+    Operation, ResourceTarget,
+  },
+};
+use mungos::mongodb::bson::{doc, to_document};
+use resolver_api::Resolve;
+
+use crate::{
+  alert::maintenance_window_active,
+  helpers::{
+    query::get_maintenance_window,
+    update::{add_update, make_update},
+  },
+  state::{db_client, State},
+};
+
+impl Resolve<CreateMaintenanceWindow, User> for State {
+  #[instrument(name = "CreateMaintenanceWindow", skip(self, user))]
+  async fn resolve(
+    &self,
+    CreateMaintenanceWindow { window }: CreateMaintenanceWindow,
+    user: User,
+  ) -> anyhow::Result<CreateMaintenanceWindowResponse> {
+    if !user.admin {
+      return Err(anyhow!(
+        "only admins can create maintenance windows"
+      ));
+    }
+
+    validate_maintenance_window(&window)?;
+
+    db_client()
+      .maintenance_windows
+      .insert_one(&window)
+      .await
+      .context("failed to create maintenance window on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::CreateMaintenanceWindow,
+      &user,
+    );
+
+    update.push_simple_log(
+      "create maintenance window",
+      format!("{window:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    get_maintenance_window(&window.name).await
+  }
+}
+
+impl Resolve<UpdateMaintenanceWindow, User> for State {
+  #[instrument(name = "UpdateMaintenanceWindow", skip(self, user))]
+  async fn resolve(
+    &self,
+    UpdateMaintenanceWindow { window }: UpdateMaintenanceWindow,
+    user: User,
+  ) -> anyhow::Result<UpdateMaintenanceWindowResponse> {
+    if !user.admin {
+      return Err(anyhow!(
+        "only admins can update maintenance windows"
+      ));
+    }
+
+    let original = get_maintenance_window(&window.name).await?;
+
+    if original == window {
+      return Ok(original);
+    }
+
+    validate_maintenance_window(&window)?;
+
+    db_client()
+      .maintenance_windows
+      .update_one(
+        doc! { "name": &window.name },
+        doc! { "$set": to_document(&window)
+        .context("failed to serialize maintenance window")? },
+      )
+      .await
+      .context("failed to update maintenance window on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::UpdateMaintenanceWindow,
+      &user,
+    );
+
+    update.push_simple_log(
+      "update maintenance window",
+      format!("from: {original:#?}\nto: {window:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    get_maintenance_window(&window.name).await
+  }
+}
+
+impl Resolve<DeleteMaintenanceWindow, User> for State {
+  #[instrument(name = "DeleteMaintenanceWindow", skip(self, user))]
+  async fn resolve(
+    &self,
+    DeleteMaintenanceWindow { name }: DeleteMaintenanceWindow,
+    user: User,
+  ) -> anyhow::Result<DeleteMaintenanceWindowResponse> {
+    if !user.admin {
+      return Err(anyhow!(
+        "only admins can delete maintenance windows"
+      ));
+    }
+
+    let window = get_maintenance_window(&name).await?;
+
+    db_client()
+      .maintenance_windows
+      .delete_one(doc! { "name": &name })
+      .await
+      .context("failed to delete maintenance window on db")?;
+
+    let mut update = make_update(
+      ResourceTarget::system(),
+      Operation::DeleteMaintenanceWindow,
+      &user,
+    );
+
+    update.push_simple_log(
+      "delete maintenance window",
+      format!("{window:#?}"),
+    );
+    update.finalize();
+
+    add_update(update).await?;
+
+    Ok(window)
+  }
+}
+
+fn validate_maintenance_window(
+  window: &MaintenanceWindow,
+) -> anyhow::Result<()> {
+  if window.name.is_empty() {
+    return Err(anyhow!("maintenance window must have a name"));
+  }
+  if window.start_ts > 0
+    && window.end_ts > 0
+    && window.end_ts <= window.start_ts
+  {
+    return Err(anyhow!(
+      "maintenance window 'end_ts' must be after 'start_ts'"
+    ));
+  }
+  // Check the schedule is valid
+  maintenance_window_active(
+    &MaintenanceWindow {
+      enabled: true,
+      start_ts: 0,
+      end_ts: 0,
+      ..window.clone()
+    },
+    komodo_timestamp(),
+  )?;
+  Ok(())
+}
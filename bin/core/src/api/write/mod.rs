@@ -14,11 +14,13 @@ use uuid::Uuid;
 use crate::{auth::auth_request, state::State};
 
 mod action;
+mod alert;
 mod alerter;
 mod build;
 mod builder;
 mod deployment;
 mod description;
+mod maintenance;
 mod permissions;
 mod procedure;
 mod provider;
@@ -119,6 +121,10 @@ pub enum WriteRequest {
   CreateRepoWebhook(CreateRepoWebhook),
   DeleteRepoWebhook(DeleteRepoWebhook),
 
+  // ==== ALERT ====
+  AcknowledgeAlert(AcknowledgeAlert),
+  SnoozeAlert(SnoozeAlert),
+
   // ==== ALERTER ====
   CreateAlerter(CreateAlerter),
   CopyAlerter(CopyAlerter),
@@ -176,6 +182,11 @@ pub enum WriteRequest {
   UpdateVariableIsSecret(UpdateVariableIsSecret),
   DeleteVariable(DeleteVariable),
 
+  // ==== MAINTENANCE WINDOW ====
+  CreateMaintenanceWindow(CreateMaintenanceWindow),
+  UpdateMaintenanceWindow(UpdateMaintenanceWindow),
+  DeleteMaintenanceWindow(DeleteMaintenanceWindow),
+
   // ==== PROVIDERS ====
   CreateGitProviderAccount(CreateGitProviderAccount),
   UpdateGitProviderAccount(UpdateGitProviderAccount),
@@ -41,7 +41,7 @@ use resolver_api::Resolve;
 use tokio::fs;
 
 use crate::{
-  alert::send_alerts,
+  alert::{record_alert, send_alerts},
   config::core_config,
   helpers::{
     query::get_id_to_tags,
@@ -625,11 +625,25 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
         Default::default()
       };
 
+      let maintenance_window_updates =
+        if sync.config.match_tags.is_empty() {
+          crate::sync::maintenance::get_updates_for_view(
+            &resources.maintenance_windows,
+            delete,
+            &all_resources,
+            &id_to_tags,
+          )
+          .await?
+        } else {
+          Default::default()
+        };
+
       anyhow::Ok((
         diffs,
         deploy_updates,
         variable_updates,
         user_group_updates,
+        maintenance_window_updates,
       ))
     }
     .await;
@@ -639,14 +653,16 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
       deploy_updates,
       variable_updates,
       user_group_updates,
+      maintenance_window_updates,
       pending_error,
     ) = match res {
-      Ok(res) => (res.0, res.1, res.2, res.3, None),
+      Ok(res) => (res.0, res.1, res.2, res.3, res.4, None),
       Err(e) => (
         Default::default(),
         Default::default(),
         Default::default(),
         Default::default(),
+        Default::default(),
         Some(format_serror(&e.into())),
       ),
     };
@@ -654,7 +670,8 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
     let has_updates = !resource_updates.is_empty()
       || !deploy_updates.to_deploy == 0
       || !variable_updates.is_empty()
-      || !user_group_updates.is_empty();
+      || !user_group_updates.is_empty()
+      || !maintenance_window_updates.is_empty();
 
     let info = ResourceSyncInfo {
       last_sync_ts: sync.info.last_sync_ts,
@@ -668,6 +685,7 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
       resource_updates,
       variable_updates,
       user_group_updates,
+      maintenance_window_updates,
       pending_error,
     };
 
@@ -704,7 +722,7 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
       match (existing, has_updates) {
         // OPEN A NEW ALERT
         (None, true) => {
-          let alert = Alert {
+          let mut alert = Alert {
             id: Default::default(),
             ts: komodo_timestamp(),
             resolved: false,
@@ -712,9 +730,9 @@ impl Resolve<RefreshResourceSyncPending, User> for State {
             target: ResourceTarget::ResourceSync(id.clone()),
             data: AlertData::ResourceSyncPendingUpdates { id, name },
             resolved_ts: None,
+            ..Default::default()
           };
-          db.alerts
-            .insert_one(&alert)
+          record_alert(&mut alert)
             .await
             .context("failed to open existing pending resource sync updates alert")
             .inspect_err(|e| warn!("{e:#}"))
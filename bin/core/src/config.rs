@@ -162,9 +162,21 @@ pub fn core_config() -> &'static CoreConfig {
       keep_stats_for_days: env
         .komodo_keep_stats_for_days
         .unwrap_or(config.keep_stats_for_days),
+      keep_stats_rollups_for_days: env
+        .komodo_keep_stats_rollups_for_days
+        .unwrap_or(config.keep_stats_rollups_for_days),
       keep_alerts_for_days: env
         .komodo_keep_alerts_for_days
         .unwrap_or(config.keep_alerts_for_days),
+      keep_procedure_runs_for_days: env
+        .komodo_keep_procedure_runs_for_days
+        .unwrap_or(config.keep_procedure_runs_for_days),
+      certificate_expiry_warning_days: env
+        .komodo_certificate_expiry_warning_days
+        .unwrap_or(config.certificate_expiry_warning_days),
+      certificate_expiry_critical_days: env
+        .komodo_certificate_expiry_critical_days
+        .unwrap_or(config.certificate_expiry_critical_days),
       webhook_base_url: env
         .komodo_webhook_base_url
         .unwrap_or(config.webhook_base_url),
@@ -7,14 +7,18 @@ use komodo_client::entities::{
   builder::Builder,
   config::core::DatabaseConfig,
   deployment::Deployment,
+  maintenance::MaintenanceWindow,
   permission::Permission,
-  procedure::Procedure,
+  probe::ProbeRecord,
+  procedure::{Procedure, ProcedureRun},
   provider::{DockerRegistryAccount, GitProviderAccount},
   repo::Repo,
   server::Server,
   server_template::ServerTemplate,
   stack::Stack,
-  stats::SystemStatsRecord,
+  stats::{
+    ContainerStatsRecord, SystemStatsRecord, SystemStatsRollup,
+  },
   sync::ResourceSync,
   tag::Tag,
   update::Update,
@@ -36,11 +40,16 @@ pub struct DbClient {
   pub api_keys: Collection<ApiKey>,
   pub tags: Collection<Tag>,
   pub variables: Collection<Variable>,
+  pub maintenance_windows: Collection<MaintenanceWindow>,
   pub git_accounts: Collection<GitProviderAccount>,
   pub registry_accounts: Collection<DockerRegistryAccount>,
   pub updates: Collection<Update>,
   pub alerts: Collection<Alert>,
   pub stats: Collection<SystemStatsRecord>,
+  pub stats_rollups: Collection<SystemStatsRollup>,
+  pub container_stats: Collection<ContainerStatsRecord>,
+  pub probes: Collection<ProbeRecord>,
+  pub procedure_runs: Collection<ProcedureRun>,
   // RESOURCES
   pub servers: Collection<Server>,
   pub deployments: Collection<Deployment>,
@@ -104,11 +113,17 @@ impl DbClient {
       api_keys: mongo_indexed::collection(&db, true).await?,
       tags: mongo_indexed::collection(&db, true).await?,
       variables: mongo_indexed::collection(&db, true).await?,
+      maintenance_windows: mongo_indexed::collection(&db, true)
+        .await?,
       git_accounts: mongo_indexed::collection(&db, true).await?,
       registry_accounts: mongo_indexed::collection(&db, true).await?,
       updates: mongo_indexed::collection(&db, true).await?,
       alerts: mongo_indexed::collection(&db, true).await?,
       stats: mongo_indexed::collection(&db, true).await?,
+      stats_rollups: mongo_indexed::collection(&db, true).await?,
+      container_stats: mongo_indexed::collection(&db, true).await?,
+      probes: mongo_indexed::collection(&db, true).await?,
+      procedure_runs: mongo_indexed::collection(&db, true).await?,
       // RESOURCES
       servers: resource_collection(&db, "Server").await?,
       deployments: resource_collection(&db, "Deployment").await?,
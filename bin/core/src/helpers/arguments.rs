@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use komodo_client::{
+  entities::{procedure::ProcedureStage, Parameter, ParameterType},
+  parsers::parse_key_value_list,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+/// An argument after it is checked against its [Parameter].
+pub struct Argument {
+  pub name: String,
+  pub value: Value,
+}
+
+/// Matches the given args to the declared parameters,
+/// falling back to the parameter defaults.
+/// Fails if a required argument is missing, an argument is not
+/// a declared parameter, or a value does not parse as the parameter type.
+pub fn resolve_arguments(
+  parameters: &[Parameter],
+  args: Option<HashMap<String, String>>,
+) -> anyhow::Result<Vec<Argument>> {
+  let mut args = args.unwrap_or_default();
+  let mut resolved = Vec::with_capacity(parameters.len());
+  for Parameter {
+    name,
+    kind,
+    default,
+    ..
+  } in parameters
+  {
+    let value =
+      args.remove(name).or_else(|| default.clone()).with_context(
+        || format!("Missing required argument '{name}'"),
+      )?;
+    let value = parse_argument(&value, *kind).with_context(|| {
+      format!("Argument '{name}' must be a {kind}, got '{value}'")
+    })?;
+    resolved.push(Argument {
+      name: name.clone(),
+      value,
+    });
+  }
+  if let Some(name) = args.keys().next() {
+    return Err(anyhow!(
+      "Got argument '{name}', which is not a declared parameter"
+    ));
+  }
+  Ok(resolved)
+}
+
+fn parse_argument(
+  value: &str,
+  kind: ParameterType,
+) -> anyhow::Result<Value> {
+  let value = match kind {
+    ParameterType::String => Value::String(value.to_string()),
+    ParameterType::Number => {
+      Value::Number(serde_json::from_str(value.trim())?)
+    }
+    ParameterType::Boolean => Value::Bool(value.trim().parse()?),
+  };
+  Ok(value)
+}
+
+/// The arguments as a JSON object, passed to Actions as `ARGS`.
+pub fn arguments_object(args: &[Argument]) -> Value {
+  Value::Object(
+    args
+      .iter()
+      .map(|arg| (arg.name.clone(), arg.value.clone()))
+      .collect(),
+  )
+}
+
+/// Replaces `[[ARG_<NAME>]]` in all the string fields of target.
+pub fn interpolate_arguments<T: Serialize + DeserializeOwned>(
+  target: &mut T,
+  args: &[Argument],
+) -> anyhow::Result<()> {
+  if args.is_empty() {
+    return Ok(());
+  }
+  let replacers = args
+    .iter()
+    .map(|arg| {
+      anyhow::Ok((
+        format!("ARG_{}", arg.name),
+        json_escaped(&arg.value)?,
+      ))
+    })
+    .collect::<anyhow::Result<HashMap<_, _>>>()?;
+  let json = serde_json::to_string(target)
+    .context("Failed to serialize arguments target")?;
+  let (res, _) = svi::interpolate_variables(
+    &json,
+    &replacers,
+    svi::Interpolator::DoubleBrackets,
+    false,
+  )
+  .context("Failed to interpolate arguments")?;
+  *target = serde_json::from_str(&res).context(
+    "Failed to parse target after interpolating arguments",
+  )?;
+  Ok(())
+}
+
+/// Replaces `[[ARG_<NAME>]]` in a resource config field,
+/// for the child executions of a Procedure run.
+/// Values with line breaks are rejected, so they can't add lines
+/// to files like the stack env file.
+pub fn interpolate_arguments_into_string(
+  target: &mut String,
+  args: &[Argument],
+) -> anyhow::Result<()> {
+  if args.is_empty() || target.is_empty() {
+    return Ok(());
+  }
+  let replacers = args
+    .iter()
+    .map(|arg| {
+      let value = match &arg.value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+      };
+      if value.contains(['\n', '\r']) {
+        return Err(anyhow!(
+          "Argument '{}' can't contain line breaks",
+          arg.name
+        ));
+      }
+      Ok((format!("ARG_{}", arg.name), value))
+    })
+    .collect::<anyhow::Result<HashMap<_, _>>>()?;
+  let (res, _) = svi::interpolate_variables(
+    target,
+    &replacers,
+    svi::Interpolator::DoubleBrackets,
+    false,
+  )
+  .context("Failed to interpolate arguments")?;
+  *target = res;
+  Ok(())
+}
+
+/// The stages run with the admin procedure user, so arguments from
+/// the caller, who may only have Execute on the Procedure,
+/// must not choose the targets (or Batch patterns) of the executions.
+/// They are only allowed in the `args` passed on to Actions and Procedures.
+pub fn check_no_target_arguments(
+  stages: &[ProcedureStage],
+) -> anyhow::Result<()> {
+  for stage in stages {
+    let mut stage_json = serde_json::to_value(stage)
+      .context("Failed to serialize stage")?;
+    if let Some(executions) = stage_json
+      .get_mut("executions")
+      .and_then(Value::as_array_mut)
+    {
+      for exec in executions {
+        if let Some(params) = exec
+          .pointer_mut("/execution/params")
+          .and_then(Value::as_object_mut)
+        {
+          params.remove("args");
+        }
+      }
+    }
+    if stage_json.to_string().contains("[[ARG_") {
+      return Err(anyhow!(
+        "Stage '{}' uses arguments outside of the execution args. Arguments can't be used to choose targets.",
+        stage.name
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// The argument as it is written into a JSON string.
+fn json_escaped(value: &Value) -> anyhow::Result<String> {
+  let value = match value {
+    Value::String(value) => serde_json::to_string(value)?,
+    value => return Ok(value.to_string()),
+  };
+  // Strip the wrapping quotes
+  Ok(value[1..value.len() - 1].to_string())
+}
+
+/// Extracts arguments from a webhook payload using
+/// the `PARAMETER = path.to.field` lines in the mapping.
+/// Fields missing from the payload fall back to the parameter default.
+pub fn webhook_arguments(
+  mapping: &str,
+  body: &str,
+) -> anyhow::Result<Option<HashMap<String, String>>> {
+  let mapping = parse_key_value_list(mapping)
+    .context("Failed to parse webhook args")?;
+  if mapping.is_empty() {
+    return Ok(None);
+  }
+  let payload = serde_json::from_str::<Value>(body)
+    .context("Failed to parse webhook payload as JSON")?;
+  let args = mapping
+    .into_iter()
+    .filter_map(|(name, path)| {
+      let pointer = format!("/{}", path.replace('.', "/"));
+      let value = match payload.pointer(&pointer)? {
+        Value::Null => return None,
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+      };
+      Some((name, value))
+    })
+    .collect();
+  Ok(Some(args))
+}
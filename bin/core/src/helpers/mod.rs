@@ -31,6 +31,7 @@ use crate::{
 };
 
 pub mod action_state;
+pub mod arguments;
 pub mod builder;
 pub mod cache;
 pub mod channel;
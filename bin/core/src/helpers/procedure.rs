@@ -1,210 +1,960 @@
-use std::time::{Duration, Instant};
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    OnceLock,
+  },
+  time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, Context};
 use formatting::{bold, colored, format_serror, muted, Color};
 use futures::future::join_all;
 use komodo_client::{
-  api::execute::*,
+  api::{execute::*, write::RefreshStackCache},
   entities::{
     action::Action,
     build::Build,
     deployment::Deployment,
-    procedure::Procedure,
+    komodo_timestamp,
+    procedure::{
+      EnabledExecution, Procedure, ProcedureExecutionRun,
+      ProcedureFailurePolicy, ProcedureRun, ProcedureStage,
+      ProcedureStageRun, StageCondition, StageResult,
+    },
     repo::Repo,
+    server::Server,
     stack::Stack,
     update::{Log, Update},
-    user::procedure_user,
+    user::{procedure_user, User},
+    I64,
   },
 };
-use mungos::by_id::find_one_by_id;
+use mungos::{
+  by_id::{find_one_by_id, update_one_by_id},
+  mongodb::bson::to_document,
+};
 use resolver_api::Resolve;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
 
 use crate::{
-  api::execute::ExecuteRequest,
-  resource::{list_full_for_user_using_pattern, KomodoResource},
+  api::execute::{
+    deploy_inner, deploy_stack_if_changed_inner, deploy_stack_inner,
+    ExecuteRequest,
+  },
+  resource::{
+    self, list_full_for_user_using_pattern, KomodoResource,
+  },
+  stack::compose_contents_changed,
   state::{db_client, State},
 };
 
-use super::update::{init_execution_update, update_update};
+use super::{
+  arguments::Argument,
+  query::{get_deployment_state, get_stack_state, get_variable},
+  update::{init_execution_update, update_update},
+};
 
 #[instrument(skip_all)]
 pub async fn execute_procedure(
   procedure: &Procedure,
+  args: &[Argument],
   update: &Mutex<Update>,
+  run: &mut ProcedureRun,
 ) -> anyhow::Result<()> {
+  let mut previous = None::<StageResult>;
   for stage in &procedure.config.stages {
+    if stage.cleanup {
+      continue;
+    }
     if !stage.enabled {
+      previous = Some(StageResult::Skipped);
+      record_skipped_stage(run, stage).await;
       continue;
     }
-    add_line_to_update(
-      update,
-      &format!(
-        "{}: Executing stage: '{}'",
-        muted("INFO"),
-        bold(&stage.name)
-      ),
-    )
-    .await;
     let timer = Instant::now();
-    execute_stage(
-      stage
-        .executions
-        .iter()
-        .filter(|item| item.enabled)
-        .map(|item| item.execution.clone())
-        .collect(),
-      &procedure.id,
-      &procedure.name,
-      update,
-    )
-    .await
-    .with_context(|| {
-      format!(
-        "Failed stage '{}' execution after {:?}",
-        bold(&stage.name),
-        timer.elapsed(),
-      )
-    })?;
+    let start_ts = komodo_timestamp();
+    let mut executions = Vec::new();
+    let res = match check_stage_condition(stage, previous, update)
+      .await
+    {
+      Ok(true) => {
+        add_line_to_update(
+          update,
+          &format!(
+            "{}: Executing stage: '{}'",
+            muted("INFO"),
+            bold(&stage.name)
+          ),
+        )
+        .await;
+        execute_stage(
+          stage,
+          &procedure.id,
+          &procedure.name,
+          args,
+          update,
+          &mut executions,
+        )
+        .await
+      }
+      Ok(false) => {
+        previous = Some(StageResult::Skipped);
+        record_skipped_stage(run, stage).await;
+        continue;
+      }
+      // A condition which can't be checked fails the stage,
+      // handled according to the stage `on_failure`.
+      Err(error) => match stage.on_failure {
+        ProcedureFailurePolicy::Continue => {
+          add_line_to_update(
+            update,
+            &format!(
+              "{}: Continuing after failed stage condition | {error:#}",
+              colored("WARN", Color::Red),
+            ),
+          )
+          .await;
+          Ok(StageResult::Failed)
+        }
+        on_failure => Err(StageFailure {
+          error,
+          run_cleanup: on_failure == ProcedureFailurePolicy::Cleanup,
+        }),
+      },
+    };
+    record_stage(run, stage, &res, start_ts, executions).await;
+    let result = match res {
+      Ok(result) => result,
+      Err(StageFailure { error, run_cleanup }) => {
+        let error = error.context(format!(
+          "Failed stage '{}' execution after {:?}",
+          bold(&stage.name),
+          timer.elapsed(),
+        ));
+        if run_cleanup {
+          execute_cleanup_stages(procedure, args, update, run).await;
+        }
+        return Err(error);
+      }
+    };
+    let finished = match result {
+      StageResult::Failed => {
+        colored("Finished with failures", Color::Red)
+      }
+      _ => colored("Finished", Color::Green),
+    };
     add_line_to_update(
       update,
       &format!(
-        "{}: {} stage '{}' execution in {:?}",
+        "{}: {finished} stage '{}' execution in {:?}",
         muted("INFO"),
-        colored("Finished", Color::Green),
         bold(&stage.name),
         timer.elapsed()
       ),
     )
     .await;
+    previous = Some(result);
   }
 
   Ok(())
 }
 
-#[allow(dependency_on_unit_never_type_fallback)]
-#[instrument(skip(update))]
-async fn execute_stage(
-  _executions: Vec<Execution>,
-  parent_id: &str,
-  parent_name: &str,
+/// Failures in the cleanup stages are only logged,
+/// the procedure fails with the error which triggered the cleanup.
+async fn execute_cleanup_stages(
+  procedure: &Procedure,
+  args: &[Argument],
   update: &Mutex<Update>,
-) -> anyhow::Result<()> {
-  let mut executions = Vec::with_capacity(_executions.capacity());
-  for execution in _executions {
-    match execution {
-      Execution::BatchRunAction(exec) => {
-        extend_batch_exection::<BatchRunAction>(
-          &exec.pattern,
-          &mut executions,
-        )
-        .await?;
+  run: &mut ProcedureRun,
+) {
+  let mut previous = Some(StageResult::Failed);
+  for stage in &procedure.config.stages {
+    if !stage.enabled || !stage.cleanup {
+      continue;
+    }
+    match check_stage_condition(stage, previous, update).await {
+      Ok(true) => {}
+      Ok(false) => {
+        previous = Some(StageResult::Skipped);
+        record_skipped_stage(run, stage).await;
+        continue;
       }
-      Execution::BatchRunProcedure(exec) => {
-        extend_batch_exection::<BatchRunProcedure>(
-          &exec.pattern,
-          &mut executions,
+      Err(e) => {
+        add_line_to_update(
+          update,
+          &format!("{}: {e:#}", colored("ERROR", Color::Red)),
         )
-        .await?;
+        .await;
+        previous = Some(StageResult::Skipped);
+        record_skipped_stage(run, stage).await;
+        continue;
       }
-      Execution::BatchRunBuild(exec) => {
-        extend_batch_exection::<BatchRunBuild>(
-          &exec.pattern,
-          &mut executions,
+    }
+    add_line_to_update(
+      update,
+      &format!(
+        "{}: Executing cleanup stage: '{}'",
+        muted("INFO"),
+        bold(&stage.name)
+      ),
+    )
+    .await;
+    let timer = Instant::now();
+    let start_ts = komodo_timestamp();
+    let mut executions = Vec::new();
+    let res = execute_stage(
+      stage,
+      &procedure.id,
+      &procedure.name,
+      args,
+      update,
+      &mut executions,
+    )
+    .await;
+    record_stage(run, stage, &res, start_ts, executions).await;
+    let line = match res {
+      Ok(result) => {
+        previous = Some(result);
+        format!(
+          "{}: {} cleanup stage '{}' execution in {:?}",
+          muted("INFO"),
+          colored("Finished", Color::Green),
+          bold(&stage.name),
+          timer.elapsed()
         )
-        .await?;
       }
-      Execution::BatchCloneRepo(exec) => {
-        extend_batch_exection::<BatchCloneRepo>(
-          &exec.pattern,
-          &mut executions,
-        )
-        .await?;
+      Err(StageFailure { error, .. }) => {
+        previous = Some(StageResult::Failed);
+        format!(
+            "{}: Failed cleanup stage '{}' execution after {:?} | {error:#}",
+            colored("ERROR", Color::Red),
+            bold(&stage.name),
+            timer.elapsed()
+          )
       }
-      Execution::BatchPullRepo(exec) => {
-        extend_batch_exection::<BatchPullRepo>(
-          &exec.pattern,
-          &mut executions,
+    };
+    add_line_to_update(update, &line).await;
+  }
+}
+
+/// Checks the stage condition, logging the outcome.
+/// Returns false if the stage should be skipped.
+async fn check_stage_condition(
+  stage: &ProcedureStage,
+  previous: Option<StageResult>,
+  update: &Mutex<Update>,
+) -> anyhow::Result<bool> {
+  let Some(condition) = &stage.condition else {
+    return Ok(true);
+  };
+  let (passed, reason) =
+    evaluate_stage_condition(condition, previous)
+      .await
+      .with_context(|| {
+        format!(
+          "Failed to check condition for stage '{}'",
+          bold(&stage.name)
         )
-        .await?;
-      }
-      Execution::BatchBuildRepo(exec) => {
-        extend_batch_exection::<BatchBuildRepo>(
-          &exec.pattern,
-          &mut executions,
+      })?;
+  let line = if passed {
+    format!(
+      "{}: Condition passed for stage '{}': {reason}",
+      muted("INFO"),
+      bold(&stage.name)
+    )
+  } else {
+    format!(
+      "{}: Skipping stage '{}', condition not met: {reason}",
+      colored("SKIPPED", Color::Blue),
+      bold(&stage.name)
+    )
+  };
+  add_line_to_update(update, &line).await;
+  Ok(passed)
+}
+
+/// Returns whether the condition passed, and a description of why.
+async fn evaluate_stage_condition(
+  condition: &StageCondition,
+  previous: Option<StageResult>,
+) -> anyhow::Result<(bool, String)> {
+  match condition {
+    StageCondition::PreviousStage { result } => {
+      let Some(previous) = previous else {
+        return Ok((
+          false,
+          format!("expected previous stage {result}, but there is no previous stage"),
+        ));
+      };
+      Ok((
+        previous == *result,
+        format!("previous stage {previous}, expected {result}"),
+      ))
+    }
+    StageCondition::StackState { stack, state } => {
+      let stack = resource::get::<Stack>(stack).await?;
+      let curr = get_stack_state(&stack).await?;
+      Ok((
+        curr == *state,
+        format!("Stack '{}' is {curr}, expected {state}", stack.name),
+      ))
+    }
+    StageCondition::StackChanged { stack } => {
+      let stack = resource::get::<Stack>(stack).await?;
+      State
+        .resolve(
+          RefreshStackCache {
+            stack: stack.id.clone(),
+          },
+          procedure_user().to_owned(),
         )
         .await?;
-      }
-      Execution::BatchDeploy(exec) => {
-        extend_batch_exection::<BatchDeploy>(
-          &exec.pattern,
-          &mut executions,
+      let stack = resource::get::<Stack>(&stack.id).await?;
+      let changed = compose_contents_changed(&stack);
+      let reason = if changed {
+        format!("Stack '{}' has changes", stack.name)
+      } else {
+        format!("Stack '{}' has no changes", stack.name)
+      };
+      Ok((changed, reason))
+    }
+    StageCondition::DeploymentState { deployment, state } => {
+      let deployment =
+        resource::get::<Deployment>(deployment).await?;
+      let curr = get_deployment_state(&deployment).await?;
+      Ok((
+        curr == *state,
+        format!(
+          "Deployment '{}' is {curr}, expected {state}",
+          deployment.name
+        ),
+      ))
+    }
+    StageCondition::BuildState { build, state } => {
+      let build = resource::get::<Build>(build).await?;
+      let name = build.name.clone();
+      let curr = Build::to_list_item(build).await.info.state;
+      Ok((
+        curr == *state,
+        format!("Build '{name}' is {curr}, expected {state}"),
+      ))
+    }
+    StageCondition::Variable { name, value } => {
+      let variable = get_variable(name).await?;
+      let passed = variable.value == *value;
+      let reason = if variable.is_secret {
+        format!(
+          "Secret Variable '{name}' {} the expected value",
+          if passed { "matches" } else { "doesn't match" }
         )
-        .await?;
-      }
-      Execution::BatchDestroyDeployment(exec) => {
-        extend_batch_exection::<BatchDestroyDeployment>(
-          &exec.pattern,
-          &mut executions,
+      } else {
+        format!(
+          "Variable '{name}' is '{}', expected '{value}'",
+          variable.value
         )
-        .await?;
+      };
+      Ok((passed, reason))
+    }
+  }
+}
+
+struct StageFailure {
+  error: anyhow::Error,
+  /// Whether the failed execution has `on_failure: Cleanup`.
+  run_cleanup: bool,
+}
+
+/// The failure handling of an execution,
+/// combining the stage settings with the execution overrides.
+#[derive(Debug, Clone, Copy)]
+struct ExecutionPolicy {
+  on_failure: ProcedureFailurePolicy,
+  retries: u32,
+  timeout: Option<Duration>,
+}
+
+/// The most retries an execution can have.
+const MAX_EXECUTION_RETRIES: u32 = 10;
+/// The longest timeout an execution can have (1 day).
+const MAX_EXECUTION_TIMEOUT_SECONDS: u64 = 86_400;
+
+impl ExecutionPolicy {
+  fn new(
+    stage: &ProcedureStage,
+    execution: &EnabledExecution,
+  ) -> Self {
+    let retries = execution.retries.unwrap_or(stage.retries);
+    let timeout_seconds =
+      execution.timeout_seconds.unwrap_or(stage.timeout_seconds);
+    Self {
+      on_failure: execution.on_failure.unwrap_or(stage.on_failure),
+      retries: u32::try_from(retries)
+        .unwrap_or(if retries < 0 { 0 } else { u32::MAX })
+        .min(MAX_EXECUTION_RETRIES),
+      timeout: u64::try_from(timeout_seconds)
+        .ok()
+        .filter(|secs| *secs > 0)
+        .map(|secs| {
+          Duration::from_secs(secs.min(MAX_EXECUTION_TIMEOUT_SECONDS))
+        }),
+    }
+  }
+}
+
+/// Rejects stages and executions with `retries`
+/// or `timeout_seconds` out of range.
+pub fn check_execution_policies(
+  stages: &[ProcedureStage],
+) -> anyhow::Result<()> {
+  let check = |name: &str, retries: I64, timeout_seconds: I64| {
+    if !(0..=MAX_EXECUTION_RETRIES as I64).contains(&retries) {
+      return Err(anyhow!(
+        "Stage '{name}' has retries {retries}. Must be between 0 and {MAX_EXECUTION_RETRIES}."
+      ));
+    }
+    if !(0..=MAX_EXECUTION_TIMEOUT_SECONDS as I64)
+      .contains(&timeout_seconds)
+    {
+      return Err(anyhow!(
+        "Stage '{name}' has timeout_seconds {timeout_seconds}. Must be between 0 and {MAX_EXECUTION_TIMEOUT_SECONDS}."
+      ));
+    }
+    Ok(())
+  };
+  for stage in stages {
+    check(&stage.name, stage.retries, stage.timeout_seconds)?;
+    for execution in &stage.executions {
+      check(
+        &stage.name,
+        execution.retries.unwrap_or_default(),
+        execution.timeout_seconds.unwrap_or_default(),
+      )?;
+    }
+  }
+  Ok(())
+}
+
+#[instrument(skip(args, update))]
+async fn execute_stage(
+  stage: &ProcedureStage,
+  parent_id: &str,
+  parent_name: &str,
+  args: &[Argument],
+  update: &Mutex<Update>,
+  executions_run: &mut Vec<ProcedureExecutionRun>,
+) -> Result<StageResult, StageFailure> {
+  let mut executions = Vec::with_capacity(stage.executions.len());
+  for item in stage.executions.iter().filter(|item| item.enabled) {
+    let policy = ExecutionPolicy::new(stage, item);
+    let mut expanded = Vec::new();
+    expand_execution(item.execution.clone(), &mut expanded)
+      .await
+      .map_err(|error| StageFailure {
+        error,
+        run_cleanup: policy.on_failure
+          == ProcedureFailurePolicy::Cleanup,
+      })?;
+    executions.extend(
+      expanded.into_iter().map(|execution| (execution, policy)),
+    );
+  }
+  let limits = StageLimits::new(stage, &executions).await;
+  if let Some(line) = limits.describe(executions.len()) {
+    add_line_to_update(update, &line).await;
+  }
+  let total = executions.len();
+  let finished = AtomicUsize::new(0);
+  let failed = AtomicUsize::new(0);
+  let (limits, finished, failed) = (&limits, &finished, &failed);
+  let futures = executions.into_iter().enumerate().map(
+    |(index, (execution, policy))| async move {
+      // Hold the permits until the execution is finished.
+      let _permits = limits.acquire(index).await;
+      let now = Instant::now();
+      let mut record = ProcedureExecutionRun {
+        execution: execution.clone(),
+        update_id: None,
+        attempts: 0,
+        start_ts: komodo_timestamp(),
+        end_ts: 0,
+        success: false,
+        error: None,
+      };
+      add_line_to_update(
+        update,
+        &format!("{}: Executing: {execution:?}", muted("INFO")),
+      )
+      .await;
+      let fail_log = format!(
+        "{}: Failed on {execution:?}",
+        colored("ERROR", Color::Red)
+      );
+      let res = execute_execution_with_policy(
+        &execution,
+        policy,
+        parent_id,
+        parent_name,
+        args,
+        update,
+        &mut record,
+      )
+      .await
+      .context(fail_log);
+      record.end_ts = komodo_timestamp();
+      record.success = res.is_ok();
+      record.error = res.as_ref().err().map(|e| format!("{e:#}"));
+      let line = match (&res, policy.on_failure) {
+        (Ok(_), _) => format!(
+          "{}: {} execution in {:?}: {execution:?}",
+          muted("INFO"),
+          colored("Finished", Color::Green),
+          now.elapsed()
+        ),
+        (Err(e), ProcedureFailurePolicy::Continue) => format!(
+          "{}: Continuing after failed execution in {:?} | {e:#}",
+          colored("WARN", Color::Red),
+          now.elapsed()
+        ),
+        (Err(_), on_failure) => format!(
+          "{}: Failed execution in {:?} ({on_failure}): {execution:?}",
+          colored("ERROR", Color::Red),
+          now.elapsed()
+        ),
+      };
+      add_line_to_update(update, &line).await;
+      if total > 1 {
+        let finished = finished.fetch_add(1, Ordering::Relaxed) + 1;
+        let failed = if res.is_err() {
+          failed.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+          failed.load(Ordering::Relaxed)
+        };
+        let line = format!(
+          "{}: Stage progress: {finished} / {total} finished, {failed} failed",
+          muted("INFO"),
+        );
+        add_line_to_update(update, &line).await;
       }
-      Execution::BatchDeployStack(exec) => {
-        extend_batch_exection::<BatchDeployStack>(
-          &exec.pattern,
-          &mut executions,
-        )
-        .await?;
+      (res, policy, record)
+    },
+  );
+  let mut result = StageResult::Succeeded;
+  let mut failure = None::<StageFailure>;
+  for (res, policy, record) in join_all(futures).await {
+    executions_run.push(record);
+    let Err(error) = res else {
+      continue;
+    };
+    let run_cleanup = match policy.on_failure {
+      ProcedureFailurePolicy::Continue => {
+        result = StageResult::Failed;
+        continue;
       }
-      Execution::BatchDeployStackIfChanged(exec) => {
-        extend_batch_exection::<BatchDeployStackIfChanged>(
-          &exec.pattern,
-          &mut executions,
+      ProcedureFailurePolicy::Abort => false,
+      ProcedureFailurePolicy::Cleanup => true,
+    };
+    match &mut failure {
+      // Keep the first error, but run cleanup if any execution asks for it.
+      Some(failure) => failure.run_cleanup |= run_cleanup,
+      None => failure = Some(StageFailure { error, run_cleanup }),
+    }
+  }
+  match failure {
+    Some(failure) => Err(failure),
+    None => Ok(result),
+  }
+}
+
+/// The concurrency limits of a stage. Each execution waits on
+/// its Server permit before the stage permit, so executions waiting
+/// on a busy Server don't hold up executions on other Servers.
+struct StageLimits {
+  stage: Option<Semaphore>,
+  servers: HashMap<String, Semaphore>,
+  /// The Server of each execution, by index.
+  execution_servers: Vec<Option<String>>,
+  max_concurrency: usize,
+  max_concurrency_per_server: usize,
+}
+
+impl StageLimits {
+  async fn new(
+    stage: &ProcedureStage,
+    executions: &[(Execution, ExecutionPolicy)],
+  ) -> StageLimits {
+    // Semaphore panics above MAX_PERMITS
+    let max_concurrency = stage
+      .max_concurrency
+      .clamp(0, Semaphore::MAX_PERMITS as I64)
+      as usize;
+    let max_concurrency_per_server = stage
+      .max_concurrency_per_server
+      .clamp(0, Semaphore::MAX_PERMITS as I64)
+      as usize;
+    let execution_servers = if max_concurrency_per_server > 0 {
+      join_all(
+        executions
+          .iter()
+          .map(|(execution, _)| execution_server(execution)),
+      )
+      .await
+    } else {
+      Vec::new()
+    };
+    let servers = execution_servers
+      .iter()
+      .flatten()
+      .map(|server| {
+        (server.clone(), Semaphore::new(max_concurrency_per_server))
+      })
+      .collect();
+    StageLimits {
+      stage: (max_concurrency > 0)
+        .then(|| Semaphore::new(max_concurrency)),
+      servers,
+      execution_servers,
+      max_concurrency,
+      max_concurrency_per_server,
+    }
+  }
+
+  fn describe(&self, total: usize) -> Option<String> {
+    let stage_limited =
+      self.max_concurrency > 0 && self.max_concurrency < total;
+    let server_limited = !self.servers.is_empty();
+    let limits = match (stage_limited, server_limited) {
+      (false, false) => return None,
+      (true, false) => format!("{} at once", self.max_concurrency),
+      (false, true) => {
+        format!(
+          "{} at once per Server",
+          self.max_concurrency_per_server
         )
+      }
+      (true, true) => format!(
+        "{} at once, {} per Server",
+        self.max_concurrency, self.max_concurrency_per_server
+      ),
+    };
+    Some(format!(
+      "{}: Running {total} executions, {}",
+      muted("INFO"),
+      bold(limits)
+    ))
+  }
+
+  async fn acquire(
+    &self,
+    index: usize,
+  ) -> (Option<SemaphorePermit<'_>>, Option<SemaphorePermit<'_>>) {
+    let server = match self
+      .execution_servers
+      .get(index)
+      .and_then(Option::as_ref)
+      .and_then(|server| self.servers.get(server))
+    {
+      Some(semaphore) => semaphore.acquire().await.ok(),
+      None => None,
+    };
+    let stage = match &self.stage {
+      Some(semaphore) => semaphore.acquire().await.ok(),
+      None => None,
+    };
+    (server, stage)
+  }
+}
+
+/// The id of the Server the execution runs on,
+/// for the per Server concurrency limit.
+async fn execution_server(execution: &Execution) -> Option<String> {
+  let server_id = match execution {
+    Execution::Deploy(Deploy { deployment, .. })
+    | Execution::PullDeployment(PullDeployment { deployment })
+    | Execution::StartDeployment(StartDeployment { deployment })
+    | Execution::RestartDeployment(RestartDeployment {
+      deployment,
+    })
+    | Execution::PauseDeployment(PauseDeployment { deployment })
+    | Execution::UnpauseDeployment(UnpauseDeployment {
+      deployment,
+    })
+    | Execution::StopDeployment(StopDeployment {
+      deployment, ..
+    })
+    | Execution::DestroyDeployment(DestroyDeployment {
+      deployment,
+      ..
+    }) => {
+      resource::get::<Deployment>(deployment)
+        .await
+        .ok()?
+        .config
+        .server_id
+    }
+    Execution::DeployStack(DeployStack { stack, .. })
+    | Execution::DeployStackIfChanged(DeployStackIfChanged {
+      stack,
+      ..
+    })
+    | Execution::PullStack(PullStack { stack, .. })
+    | Execution::StartStack(StartStack { stack, .. })
+    | Execution::RestartStack(RestartStack { stack, .. })
+    | Execution::PauseStack(PauseStack { stack, .. })
+    | Execution::UnpauseStack(UnpauseStack { stack, .. })
+    | Execution::StopStack(StopStack { stack, .. })
+    | Execution::DestroyStack(DestroyStack { stack, .. }) => {
+      resource::get::<Stack>(stack).await.ok()?.config.server_id
+    }
+    Execution::CloneRepo(CloneRepo { repo })
+    | Execution::PullRepo(PullRepo { repo }) => {
+      resource::get::<Repo>(repo).await.ok()?.config.server_id
+    }
+    Execution::StartContainer(StartContainer { server, .. })
+    | Execution::RestartContainer(RestartContainer {
+      server, ..
+    })
+    | Execution::PauseContainer(PauseContainer { server, .. })
+    | Execution::UnpauseContainer(UnpauseContainer {
+      server, ..
+    })
+    | Execution::StopContainer(StopContainer { server, .. })
+    | Execution::DestroyContainer(DestroyContainer {
+      server, ..
+    })
+    | Execution::StartAllContainers(StartAllContainers { server })
+    | Execution::RestartAllContainers(RestartAllContainers {
+      server,
+    })
+    | Execution::PauseAllContainers(PauseAllContainers { server })
+    | Execution::UnpauseAllContainers(UnpauseAllContainers {
+      server,
+    })
+    | Execution::StopAllContainers(StopAllContainers { server })
+    | Execution::PruneContainers(PruneContainers { server })
+    | Execution::DeleteNetwork(DeleteNetwork { server, .. })
+    | Execution::PruneNetworks(PruneNetworks { server })
+    | Execution::DeleteImage(DeleteImage { server, .. })
+    | Execution::PruneImages(PruneImages { server })
+    | Execution::DeleteVolume(DeleteVolume { server, .. })
+    | Execution::PruneVolumes(PruneVolumes { server })
+    | Execution::PruneDockerBuilders(PruneDockerBuilders {
+      server,
+    })
+    | Execution::PruneBuildx(PruneBuildx { server })
+    | Execution::PruneSystem(PruneSystem { server }) => {
+      // Could be id or name
+      resource::get::<Server>(server).await.ok()?.id
+    }
+    _ => return None,
+  };
+  (!server_id.is_empty()).then_some(server_id)
+}
+
+/// Expands batch executions into the single executions
+/// on the matching resources.
+async fn expand_execution(
+  execution: Execution,
+  executions: &mut Vec<Execution>,
+) -> anyhow::Result<()> {
+  match execution {
+    Execution::BatchRunAction(exec) => {
+      extend_batch_exection::<BatchRunAction>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchRunProcedure(exec) => {
+      extend_batch_exection::<BatchRunProcedure>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchRunBuild(exec) => {
+      extend_batch_exection::<BatchRunBuild>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchCloneRepo(exec) => {
+      extend_batch_exection::<BatchCloneRepo>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchPullRepo(exec) => {
+      extend_batch_exection::<BatchPullRepo>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchBuildRepo(exec) => {
+      extend_batch_exection::<BatchBuildRepo>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchDeploy(exec) => {
+      extend_batch_exection::<BatchDeploy>(&exec.pattern, executions)
         .await?;
+    }
+    Execution::BatchDestroyDeployment(exec) => {
+      extend_batch_exection::<BatchDestroyDeployment>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchDeployStack(exec) => {
+      extend_batch_exection::<BatchDeployStack>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchDeployStackIfChanged(exec) => {
+      extend_batch_exection::<BatchDeployStackIfChanged>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    Execution::BatchDestroyStack(exec) => {
+      extend_batch_exection::<BatchDestroyStack>(
+        &exec.pattern,
+        executions,
+      )
+      .await?;
+    }
+    execution => executions.push(execution),
+  }
+  Ok(())
+}
+
+/// Runs the execution, retrying failed attempts and
+/// cancelling attempts which exceed the timeout.
+/// Timed out attempts are not retried, as the underlying
+/// operation (eg. on Periphery) may still be running.
+async fn execute_execution_with_policy(
+  execution: &Execution,
+  policy: ExecutionPolicy,
+  parent_id: &str,
+  parent_name: &str,
+  args: &[Argument],
+  update: &Mutex<Update>,
+  record: &mut ProcedureExecutionRun,
+) -> anyhow::Result<()> {
+  let attempts = policy.retries.saturating_add(1);
+  let mut attempt = 1;
+  loop {
+    record.attempts = attempt as I64;
+    let child_update = OnceLock::new();
+    let res = match policy.timeout {
+      Some(timeout) => match tokio::time::timeout(
+        timeout,
+        execute_execution(
+          execution.clone(),
+          parent_id,
+          parent_name,
+          args,
+          &child_update,
+        ),
+      )
+      .await
+      {
+        Ok(res) => res,
+        Err(_) => {
+          let error =
+            anyhow!("Execution timed out after {timeout:?}");
+          if let Some(id) = child_update.get() {
+            record.update_id = Some(id.clone());
+            if let Err(e) = fail_timed_out_update(id, &error).await {
+              warn!("Failed to finalize timed out update | {e:#}");
+            }
+          }
+          return Err(error);
+        }
+      },
+      None => {
+        execute_execution(
+          execution.clone(),
+          parent_id,
+          parent_name,
+          args,
+          &child_update,
+        )
+        .await
       }
-      Execution::BatchDestroyStack(exec) => {
-        extend_batch_exection::<BatchDestroyStack>(
-          &exec.pattern,
-          &mut executions,
+    };
+    let res = res.and_then(|update| {
+      if !update.id.is_empty() {
+        record.update_id = Some(update.id.clone());
+      }
+      check_update_success(&update)
+    });
+    match res {
+      Ok(_) => return Ok(()),
+      Err(e) if attempt < attempts => {
+        let delay = retry_backoff(attempt);
+        add_line_to_update(
+          update,
+          &format!(
+            "{}: Retrying in {delay:?} (attempt {}/{attempts}): {execution:?} | {e:#}",
+            colored("WARN", Color::Red),
+            attempt + 1,
+          ),
         )
-        .await?;
+        .await;
+        tokio::time::sleep(delay).await;
+        attempt += 1;
       }
-      execution => executions.push(execution),
+      Err(e) => return Err(e),
     }
   }
-  let futures = executions.into_iter().map(|execution| async move {
-    let now = Instant::now();
-    add_line_to_update(
-      update,
-      &format!("{}: Executing: {execution:?}", muted("INFO")),
-    )
-    .await;
-    let fail_log = format!(
-      "{}: Failed on {execution:?}",
-      colored("ERROR", Color::Red)
-    );
-    let res =
-      execute_execution(execution.clone(), parent_id, parent_name)
-        .await
-        .context(fail_log);
-    add_line_to_update(
-      update,
-      &format!(
-        "{}: {} execution in {:?}: {execution:?}",
-        muted("INFO"),
-        colored("Finished", Color::Green),
-        now.elapsed()
-      ),
-    )
-    .await;
-    res
-  });
-  join_all(futures)
+}
+
+/// The execution future is dropped on timeout, which would leave
+/// its Update in progress. Finalize it as failed with the timeout.
+async fn fail_timed_out_update(
+  update_id: &str,
+  error: &anyhow::Error,
+) -> anyhow::Result<()> {
+  let mut update = find_one_by_id(&db_client().updates, update_id)
     .await
-    .into_iter()
-    .collect::<anyhow::Result<_>>()?;
-  Ok(())
+    .context("Failed to query to db")?
+    .context("no update exists with given id")?;
+  update.push_error_log(
+    "Timed out",
+    format!(
+      "{error}. The operation may still be running, and will not be retried."
+    ),
+  );
+  update.finalize();
+  update_update(update).await
+}
+
+/// Waits 5s, 10s, 20s, ... capped at 5 minutes.
+fn retry_backoff(attempt: u32) -> Duration {
+  let secs = 5u64 << (attempt - 1).min(6);
+  Duration::from_secs(secs.min(300))
+}
+
+/// Inits the Update for the execution,
+/// storing the id so it can be finalized if the execution times out.
+async fn init_child_update(
+  request: &ExecuteRequest,
+  user: &User,
+  child_update: &OnceLock<String>,
+) -> anyhow::Result<Update> {
+  let update = init_execution_update(request, user).await?;
+  let _ = child_update.set(update.id.clone());
+  Ok(update)
 }
 
 async fn execute_execution(
@@ -212,16 +962,25 @@ async fn execute_execution(
   // used to prevent recursive procedure
   parent_id: &str,
   parent_name: &str,
-) -> anyhow::Result<()> {
+  // interpolated into the config of deploys
+  args: &[Argument],
+  child_update: &OnceLock<String>,
+) -> anyhow::Result<Update> {
   let user = procedure_user().to_owned();
   let update = match execution {
-    Execution::None(_) => return Ok(()),
+    Execution::None(_) => {
+      return Ok(Update {
+        success: true,
+        ..Default::default()
+      })
+    }
     Execution::RunProcedure(req) => {
       if req.procedure == parent_id || req.procedure == parent_name {
         return Err(anyhow!("Self referential procedure detected"));
       }
       let req = ExecuteRequest::RunProcedure(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RunProcedure(req) = req else {
         unreachable!()
       };
@@ -243,7 +1002,8 @@ async fn execute_execution(
     }
     Execution::RunAction(req) => {
       let req = ExecuteRequest::RunAction(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RunAction(req) = req else {
         unreachable!()
       };
@@ -265,7 +1025,8 @@ async fn execute_execution(
     }
     Execution::RunBuild(req) => {
       let req = ExecuteRequest::RunBuild(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RunBuild(req) = req else {
         unreachable!()
       };
@@ -287,7 +1048,8 @@ async fn execute_execution(
     }
     Execution::CancelBuild(req) => {
       let req = ExecuteRequest::CancelBuild(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::CancelBuild(req) = req else {
         unreachable!()
       };
@@ -303,14 +1065,14 @@ async fn execute_execution(
     }
     Execution::Deploy(req) => {
       let req = ExecuteRequest::Deploy(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::Deploy(req) = req else {
         unreachable!()
       };
       let update_id = update.id.clone();
       handle_resolve_result(
-        State
-          .resolve(req, (user, update))
+        deploy_inner(req, user, update, args)
           .await
           .context("Failed at Deploy"),
         &update_id,
@@ -325,7 +1087,8 @@ async fn execute_execution(
     }
     Execution::PullDeployment(req) => {
       let req = ExecuteRequest::PullDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PullDeployment(req) = req else {
         unreachable!()
       };
@@ -341,7 +1104,8 @@ async fn execute_execution(
     }
     Execution::StartDeployment(req) => {
       let req = ExecuteRequest::StartDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StartDeployment(req) = req else {
         unreachable!()
       };
@@ -357,7 +1121,8 @@ async fn execute_execution(
     }
     Execution::RestartDeployment(req) => {
       let req = ExecuteRequest::RestartDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RestartDeployment(req) = req else {
         unreachable!()
       };
@@ -373,7 +1138,8 @@ async fn execute_execution(
     }
     Execution::PauseDeployment(req) => {
       let req = ExecuteRequest::PauseDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PauseDeployment(req) = req else {
         unreachable!()
       };
@@ -389,7 +1155,8 @@ async fn execute_execution(
     }
     Execution::UnpauseDeployment(req) => {
       let req = ExecuteRequest::UnpauseDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::UnpauseDeployment(req) = req else {
         unreachable!()
       };
@@ -405,7 +1172,8 @@ async fn execute_execution(
     }
     Execution::StopDeployment(req) => {
       let req = ExecuteRequest::StopDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StopDeployment(req) = req else {
         unreachable!()
       };
@@ -421,7 +1189,8 @@ async fn execute_execution(
     }
     Execution::DestroyDeployment(req) => {
       let req = ExecuteRequest::DestroyDeployment(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DestroyDeployment(req) = req else {
         unreachable!()
       };
@@ -443,7 +1212,8 @@ async fn execute_execution(
     }
     Execution::CloneRepo(req) => {
       let req = ExecuteRequest::CloneRepo(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::CloneRepo(req) = req else {
         unreachable!()
       };
@@ -465,7 +1235,8 @@ async fn execute_execution(
     }
     Execution::PullRepo(req) => {
       let req = ExecuteRequest::PullRepo(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PullRepo(req) = req else {
         unreachable!()
       };
@@ -487,7 +1258,8 @@ async fn execute_execution(
     }
     Execution::BuildRepo(req) => {
       let req = ExecuteRequest::BuildRepo(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::BuildRepo(req) = req else {
         unreachable!()
       };
@@ -509,7 +1281,8 @@ async fn execute_execution(
     }
     Execution::CancelRepoBuild(req) => {
       let req = ExecuteRequest::CancelRepoBuild(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::CancelRepoBuild(req) = req else {
         unreachable!()
       };
@@ -525,7 +1298,8 @@ async fn execute_execution(
     }
     Execution::StartContainer(req) => {
       let req = ExecuteRequest::StartContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StartContainer(req) = req else {
         unreachable!()
       };
@@ -541,7 +1315,8 @@ async fn execute_execution(
     }
     Execution::RestartContainer(req) => {
       let req = ExecuteRequest::RestartContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RestartContainer(req) = req else {
         unreachable!()
       };
@@ -557,7 +1332,8 @@ async fn execute_execution(
     }
     Execution::PauseContainer(req) => {
       let req = ExecuteRequest::PauseContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PauseContainer(req) = req else {
         unreachable!()
       };
@@ -573,7 +1349,8 @@ async fn execute_execution(
     }
     Execution::UnpauseContainer(req) => {
       let req = ExecuteRequest::UnpauseContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::UnpauseContainer(req) = req else {
         unreachable!()
       };
@@ -589,7 +1366,8 @@ async fn execute_execution(
     }
     Execution::StopContainer(req) => {
       let req = ExecuteRequest::StopContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StopContainer(req) = req else {
         unreachable!()
       };
@@ -605,7 +1383,8 @@ async fn execute_execution(
     }
     Execution::DestroyContainer(req) => {
       let req = ExecuteRequest::DestroyContainer(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DestroyContainer(req) = req else {
         unreachable!()
       };
@@ -621,7 +1400,8 @@ async fn execute_execution(
     }
     Execution::StartAllContainers(req) => {
       let req = ExecuteRequest::StartAllContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StartAllContainers(req) = req else {
         unreachable!()
       };
@@ -637,7 +1417,8 @@ async fn execute_execution(
     }
     Execution::RestartAllContainers(req) => {
       let req = ExecuteRequest::RestartAllContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RestartAllContainers(req) = req else {
         unreachable!()
       };
@@ -653,7 +1434,8 @@ async fn execute_execution(
     }
     Execution::PauseAllContainers(req) => {
       let req = ExecuteRequest::PauseAllContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PauseAllContainers(req) = req else {
         unreachable!()
       };
@@ -669,7 +1451,8 @@ async fn execute_execution(
     }
     Execution::UnpauseAllContainers(req) => {
       let req = ExecuteRequest::UnpauseAllContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::UnpauseAllContainers(req) = req else {
         unreachable!()
       };
@@ -685,7 +1468,8 @@ async fn execute_execution(
     }
     Execution::StopAllContainers(req) => {
       let req = ExecuteRequest::StopAllContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StopAllContainers(req) = req else {
         unreachable!()
       };
@@ -701,7 +1485,8 @@ async fn execute_execution(
     }
     Execution::PruneContainers(req) => {
       let req = ExecuteRequest::PruneContainers(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneContainers(req) = req else {
         unreachable!()
       };
@@ -717,7 +1502,8 @@ async fn execute_execution(
     }
     Execution::DeleteNetwork(req) => {
       let req = ExecuteRequest::DeleteNetwork(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DeleteNetwork(req) = req else {
         unreachable!()
       };
@@ -733,7 +1519,8 @@ async fn execute_execution(
     }
     Execution::PruneNetworks(req) => {
       let req = ExecuteRequest::PruneNetworks(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneNetworks(req) = req else {
         unreachable!()
       };
@@ -749,7 +1536,8 @@ async fn execute_execution(
     }
     Execution::DeleteImage(req) => {
       let req = ExecuteRequest::DeleteImage(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DeleteImage(req) = req else {
         unreachable!()
       };
@@ -765,7 +1553,8 @@ async fn execute_execution(
     }
     Execution::PruneImages(req) => {
       let req = ExecuteRequest::PruneImages(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneImages(req) = req else {
         unreachable!()
       };
@@ -781,7 +1570,8 @@ async fn execute_execution(
     }
     Execution::DeleteVolume(req) => {
       let req = ExecuteRequest::DeleteVolume(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DeleteVolume(req) = req else {
         unreachable!()
       };
@@ -797,7 +1587,8 @@ async fn execute_execution(
     }
     Execution::PruneVolumes(req) => {
       let req = ExecuteRequest::PruneVolumes(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneVolumes(req) = req else {
         unreachable!()
       };
@@ -813,7 +1604,8 @@ async fn execute_execution(
     }
     Execution::PruneDockerBuilders(req) => {
       let req = ExecuteRequest::PruneDockerBuilders(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneDockerBuilders(req) = req else {
         unreachable!()
       };
@@ -829,7 +1621,8 @@ async fn execute_execution(
     }
     Execution::PruneBuildx(req) => {
       let req = ExecuteRequest::PruneBuildx(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneBuildx(req) = req else {
         unreachable!()
       };
@@ -845,7 +1638,8 @@ async fn execute_execution(
     }
     Execution::PruneSystem(req) => {
       let req = ExecuteRequest::PruneSystem(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PruneSystem(req) = req else {
         unreachable!()
       };
@@ -861,7 +1655,8 @@ async fn execute_execution(
     }
     Execution::RunSync(req) => {
       let req = ExecuteRequest::RunSync(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RunSync(req) = req else {
         unreachable!()
       };
@@ -882,14 +1677,14 @@ async fn execute_execution(
       .context("Failed at CommitSync")?,
     Execution::DeployStack(req) => {
       let req = ExecuteRequest::DeployStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DeployStack(req) = req else {
         unreachable!()
       };
       let update_id = update.id.clone();
       handle_resolve_result(
-        State
-          .resolve(req, (user, update))
+        deploy_stack_inner(req, user, update, args)
           .await
           .context("Failed at DeployStack"),
         &update_id,
@@ -904,14 +1699,14 @@ async fn execute_execution(
     }
     Execution::DeployStackIfChanged(req) => {
       let req = ExecuteRequest::DeployStackIfChanged(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DeployStackIfChanged(req) = req else {
         unreachable!()
       };
       let update_id = update.id.clone();
       handle_resolve_result(
-        State
-          .resolve(req, (user, update))
+        deploy_stack_if_changed_inner(req, user, update, args)
           .await
           .context("Failed at DeployStackIfChanged"),
         &update_id,
@@ -926,7 +1721,8 @@ async fn execute_execution(
     }
     Execution::PullStack(req) => {
       let req = ExecuteRequest::PullStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PullStack(req) = req else {
         unreachable!()
       };
@@ -942,7 +1738,8 @@ async fn execute_execution(
     }
     Execution::StartStack(req) => {
       let req = ExecuteRequest::StartStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StartStack(req) = req else {
         unreachable!()
       };
@@ -958,7 +1755,8 @@ async fn execute_execution(
     }
     Execution::RestartStack(req) => {
       let req = ExecuteRequest::RestartStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::RestartStack(req) = req else {
         unreachable!()
       };
@@ -974,7 +1772,8 @@ async fn execute_execution(
     }
     Execution::PauseStack(req) => {
       let req = ExecuteRequest::PauseStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::PauseStack(req) = req else {
         unreachable!()
       };
@@ -990,7 +1789,8 @@ async fn execute_execution(
     }
     Execution::UnpauseStack(req) => {
       let req = ExecuteRequest::UnpauseStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::UnpauseStack(req) = req else {
         unreachable!()
       };
@@ -1006,7 +1806,8 @@ async fn execute_execution(
     }
     Execution::StopStack(req) => {
       let req = ExecuteRequest::StopStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::StopStack(req) = req else {
         unreachable!()
       };
@@ -1022,7 +1823,8 @@ async fn execute_execution(
     }
     Execution::DestroyStack(req) => {
       let req = ExecuteRequest::DestroyStack(req);
-      let update = init_execution_update(&req, &user).await?;
+      let update =
+        init_child_update(&req, &user, child_update).await?;
       let ExecuteRequest::DestroyStack(req) = req else {
         unreachable!()
       };
@@ -1051,6 +1853,10 @@ async fn execute_execution(
       }
     }
   };
+  Ok(update)
+}
+
+fn check_update_success(update: &Update) -> anyhow::Result<()> {
   if update.success {
     Ok(())
   } else {
@@ -1086,6 +1892,82 @@ async fn handle_resolve_result(
   }
 }
 
+pub async fn add_procedure_run(
+  run: &ProcedureRun,
+) -> anyhow::Result<String> {
+  let id = db_client()
+    .procedure_runs
+    .insert_one(run)
+    .await
+    .context("Failed to insert procedure run into db")?
+    .inserted_id
+    .as_object_id()
+    .context("inserted_id is not object id")?
+    .to_string();
+  Ok(id)
+}
+
+pub async fn update_procedure_run(
+  run: &ProcedureRun,
+) -> anyhow::Result<()> {
+  update_one_by_id(
+    &db_client().procedure_runs,
+    &run.id,
+    mungos::update::Update::Set(to_document(run)?),
+    None,
+  )
+  .await
+  .context("Failed to update procedure run on db")?;
+  Ok(())
+}
+
+async fn record_skipped_stage(
+  run: &mut ProcedureRun,
+  stage: &ProcedureStage,
+) {
+  let ts = komodo_timestamp();
+  run.stages.push(ProcedureStageRun {
+    name: stage.name.clone(),
+    cleanup: stage.cleanup,
+    result: StageResult::Skipped,
+    start_ts: ts,
+    end_ts: ts,
+    executions: Vec::new(),
+  });
+  save_procedure_run(run).await;
+}
+
+async fn record_stage(
+  run: &mut ProcedureRun,
+  stage: &ProcedureStage,
+  res: &Result<StageResult, StageFailure>,
+  start_ts: I64,
+  executions: Vec<ProcedureExecutionRun>,
+) {
+  run.stages.push(ProcedureStageRun {
+    name: stage.name.clone(),
+    cleanup: stage.cleanup,
+    result: match res {
+      Ok(result) => *result,
+      Err(_) => StageResult::Failed,
+    },
+    start_ts,
+    end_ts: komodo_timestamp(),
+    executions,
+  });
+  save_procedure_run(run).await;
+}
+
+/// Failing to record the run should not fail the procedure.
+async fn save_procedure_run(run: &ProcedureRun) {
+  if run.id.is_empty() {
+    return;
+  }
+  if let Err(e) = update_procedure_run(run).await {
+    warn!("Failed to save procedure run | {e:#}");
+  }
+}
+
 /// ASSUMES FIRST LOG IS ALREADY CREATED
 #[instrument(level = "debug")]
 async fn add_line_to_update(update: &Mutex<Update>, line: &str) {
@@ -1125,14 +2007,17 @@ trait ExtendBatch {
 impl ExtendBatch for BatchRunProcedure {
   type Resource = Procedure;
   fn single_execution(procedure: String) -> Execution {
-    Execution::RunProcedure(RunProcedure { procedure })
+    Execution::RunProcedure(RunProcedure {
+      procedure,
+      args: None,
+    })
   }
 }
 
 impl ExtendBatch for BatchRunAction {
   type Resource = Action;
   fn single_execution(action: String) -> Execution {
-    Execution::RunAction(RunAction { action })
+    Execution::RunAction(RunAction { action, args: None })
   }
 }
 
@@ -14,17 +14,34 @@ pub fn spawn_prune_loop() {
   tokio::spawn(async move {
     loop {
       wait_until_timelength(Timelength::OneDay, 5000).await;
-      let (images_res, stats_res, alerts_res) =
-        tokio::join!(prune_images(), prune_stats(), prune_alerts());
+      let (
+        images_res,
+        stats_res,
+        rollups_res,
+        alerts_res,
+        procedure_runs_res,
+      ) = tokio::join!(
+        prune_images(),
+        prune_stats(),
+        prune_stats_rollups(),
+        prune_alerts(),
+        prune_procedure_runs()
+      );
       if let Err(e) = images_res {
         error!("error in pruning images | {e:#}");
       }
       if let Err(e) = stats_res {
         error!("error in pruning stats | {e:#}");
       }
+      if let Err(e) = rollups_res {
+        error!("error in pruning stats rollups | {e:#}");
+      }
       if let Err(e) = alerts_res {
         error!("error in pruning alerts | {e:#}");
       }
+      if let Err(e) = procedure_runs_res {
+        error!("error in pruning procedure runs | {e:#}");
+      }
     }
   });
 }
@@ -74,6 +91,43 @@ async fn prune_stats() -> anyhow::Result<()> {
   if res.deleted_count > 0 {
     info!("deleted {} stats from db", res.deleted_count);
   }
+  let res = db_client()
+    .container_stats
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} container stats from db", res.deleted_count);
+  }
+  let res = db_client()
+    .probes
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} probe records from db", res.deleted_count);
+  }
+  Ok(())
+}
+
+async fn prune_stats_rollups() -> anyhow::Result<()> {
+  if core_config().keep_stats_rollups_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_stats_rollups_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .stats_rollups
+    .delete_many(doc! {
+      "ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} stats rollups from db", res.deleted_count);
+  }
   Ok(())
 }
 
@@ -95,3 +149,22 @@ async fn prune_alerts() -> anyhow::Result<()> {
   }
   Ok(())
 }
+
+async fn prune_procedure_runs() -> anyhow::Result<()> {
+  if core_config().keep_procedure_runs_for_days == 0 {
+    return Ok(());
+  }
+  let delete_before_ts = (unix_timestamp_ms()
+    - core_config().keep_procedure_runs_for_days as u128 * ONE_DAY_MS)
+    as i64;
+  let res = db_client()
+    .procedure_runs
+    .delete_many(doc! {
+      "start_ts": { "$lt": delete_before_ts }
+    })
+    .await?;
+  if res.deleted_count > 0 {
+    info!("deleted {} procedure runs from db", res.deleted_count);
+  }
+  Ok(())
+}
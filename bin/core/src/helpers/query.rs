@@ -8,6 +8,7 @@ use komodo_client::entities::{
   builder::Builder,
   deployment::{Deployment, DeploymentState},
   docker::container::{ContainerListItem, ContainerStateStatusEnum},
+  maintenance::MaintenanceWindow,
   permission::PermissionLevel,
   procedure::Procedure,
   repo::Repo,
@@ -333,6 +334,19 @@ pub async fn get_variable(name: &str) -> anyhow::Result<Variable> {
     })
 }
 
+pub async fn get_maintenance_window(
+  name: &str,
+) -> anyhow::Result<MaintenanceWindow> {
+  db_client()
+    .maintenance_windows
+    .find_one(doc! { "name": &name })
+    .await
+    .context("failed at call to db")?
+    .with_context(|| {
+      format!("no maintenance window found with given name: {name}")
+    })
+}
+
 pub async fn get_latest_update(
   resource_type: ResourceTargetVariant,
   id: &str,
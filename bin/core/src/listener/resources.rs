@@ -16,7 +16,10 @@ use serde::Deserialize;
 
 use crate::{
   api::execute::ExecuteRequest,
-  helpers::update::init_execution_update, state::State,
+  helpers::{
+    arguments::webhook_arguments, update::init_execution_update,
+  },
+  state::State,
 };
 
 use super::{ListenerLockCache, ANY_BRANCH};
@@ -415,8 +418,11 @@ pub async fn handle_procedure_webhook<B: super::VerifyBranch>(
   }
 
   let user = git_webhook_user().to_owned();
+  let args =
+    webhook_arguments(&procedure.config.webhook_args, &body)?;
   let req = ExecuteRequest::RunProcedure(RunProcedure {
     procedure: procedure.id,
+    args,
   });
   let update = init_execution_update(&req, &user).await?;
   let ExecuteRequest::RunProcedure(req) = req else {
@@ -461,8 +467,11 @@ pub async fn handle_action_webhook<B: super::VerifyBranch>(
   }
 
   let user = git_webhook_user().to_owned();
-  let req =
-    ExecuteRequest::RunAction(RunAction { action: action.id });
+  let args = webhook_arguments(&action.config.webhook_args, &body)?;
+  let req = ExecuteRequest::RunAction(RunAction {
+    action: action.id,
+    args,
+  });
   let update = init_execution_update(&req, &user).await?;
   let ExecuteRequest::RunAction(req) = req else {
     unreachable!()
@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 #[macro_use]
 extern crate tracing;
 
@@ -23,6 +25,7 @@ mod helpers;
 mod listener;
 mod monitor;
 mod resource;
+mod schedule;
 mod stack;
 mod state;
 mod sync;
@@ -54,12 +57,17 @@ async fn app() -> anyhow::Result<()> {
 
   // Spawn tasks
   monitor::spawn_monitor_loop();
+  monitor::spawn_stats_rollup_loop();
+  monitor::spawn_certificate_check_loop();
+  monitor::spawn_endpoint_probe_loop();
   resource::spawn_resource_refresh_loop();
   resource::spawn_build_state_refresh_loop();
   resource::spawn_repo_state_refresh_loop();
   resource::spawn_procedure_state_refresh_loop();
   resource::spawn_action_state_refresh_loop();
   resource::spawn_resource_sync_state_refresh_loop();
+  schedule::spawn_schedule_executor();
+  alert::spawn_alert_escalation_loop();
   helpers::prune::spawn_prune_loop();
 
   // Setup static frontend services
@@ -75,6 +83,7 @@ async fn app() -> anyhow::Result<()> {
     .nest("/read", api::read::router())
     .nest("/write", api::write::router())
     .nest("/execute", api::execute::router())
+    .nest("/metrics", api::metrics::router())
     .nest("/listener", listener::router())
     .nest("/ws", ws::router())
     .nest("/client", ts_client::router())
@@ -0,0 +1,443 @@
+use std::{
+  collections::{HashMap, HashSet},
+  net::IpAddr,
+  sync::{Arc, OnceLock},
+  time::Duration,
+};
+
+use anyhow::Context;
+use async_timing_util::{
+  wait_until_timelength, Timelength, ONE_DAY_MS,
+};
+use futures::future::join_all;
+use komodo_client::entities::{
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  komodo_timestamp, ResourceTarget,
+};
+use mungos::find::find_collect;
+use reqwest::Url;
+use rustls::{
+  client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+  },
+  crypto::{
+    verify_tls12_signature, verify_tls13_signature, CryptoProvider,
+  },
+  pki_types::{CertificateDer, ServerName, UnixTime},
+  ClientConfig, DigitallySignedStruct, SignatureScheme,
+};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use x509_cert::{der::Decode, Certificate};
+
+use crate::{
+  alert::{record_alerts, send_alerts},
+  config::core_config,
+  state::db_client,
+};
+
+use super::{get_open_alerts, resolve_alerts, update_alerts};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// (target, url) -> open alert
+type OpenCertificateAlertMap =
+  HashMap<(ResourceTarget, String), Alert>;
+
+/// A https link to probe, and the resource it belongs to.
+struct Endpoint {
+  target: ResourceTarget,
+  id: String,
+  name: String,
+  url: String,
+}
+
+/// Probes the https links on Servers, Deployments, and Stacks
+/// once an hour, alerting on certificates which expire soon.
+pub fn spawn_certificate_check_loop() {
+  tokio::spawn(async move {
+    loop {
+      check_certificates().await;
+      wait_until_timelength(Timelength::OneHour, 0).await;
+    }
+  });
+}
+
+#[instrument(level = "debug")]
+async fn check_certificates() {
+  let open_alerts = match get_open_alerts(
+    AlertDataVariant::CertificateExpiring,
+  )
+  .await
+  {
+    Ok(alerts) => alerts,
+    Err(e) => {
+      error!("{e:#}");
+      return;
+    }
+  };
+  let mut open_alerts = open_alerts
+    .into_iter()
+    .filter_map(|alert| {
+      let AlertData::CertificateExpiring { url, .. } = &alert.data
+      else {
+        return None;
+      };
+      Some(((alert.target.clone(), url.clone()), alert))
+    })
+    .collect::<OpenCertificateAlertMap>();
+
+  let config = core_config();
+  let warning_ms =
+    config.certificate_expiry_warning_days as i64 * ONE_DAY_MS as i64;
+  let critical_ms = config.certificate_expiry_critical_days as i64
+    * ONE_DAY_MS as i64;
+
+  let endpoints = if warning_ms > 0 || critical_ms > 0 {
+    match get_endpoints().await {
+      Ok(endpoints) => endpoints,
+      Err(e) => {
+        error!("failed to get certificate endpoints | {e:#}");
+        return;
+      }
+    }
+  } else {
+    Vec::new()
+  };
+
+  let expiries = join_all(
+    endpoints
+      .iter()
+      .map(|endpoint| certificate_expiry(&endpoint.url)),
+  )
+  .await;
+
+  let ts = komodo_timestamp();
+
+  // The expiry is not known when the probe fails,
+  // leave any open alerts on these as is.
+  let mut unknown = HashSet::<(ResourceTarget, String)>::new();
+  let mut to_open = Vec::new();
+  let mut to_update = Vec::new();
+  let mut to_resolve = Vec::new();
+
+  for (endpoint, expires_ts) in endpoints.into_iter().zip(expiries) {
+    let key = (endpoint.target.clone(), endpoint.url.clone());
+    let expires_ts = match expires_ts {
+      Ok(expires_ts) => expires_ts,
+      Err(e) => {
+        warn!(
+          "failed to probe certificate at {} | {e:#}",
+          endpoint.url
+        );
+        unknown.insert(key);
+        continue;
+      }
+    };
+
+    let remaining_ms = expires_ts - ts;
+    let level = if critical_ms > 0 && remaining_ms < critical_ms {
+      SeverityLevel::Critical
+    } else if warning_ms > 0 && remaining_ms < warning_ms {
+      SeverityLevel::Warning
+    } else {
+      SeverityLevel::Ok
+    };
+    let data = AlertData::CertificateExpiring {
+      id: endpoint.id,
+      name: endpoint.name,
+      url: endpoint.url,
+      expires_ts,
+      days_remaining: remaining_ms.div_euclid(ONE_DAY_MS as i64),
+    };
+
+    match (level, open_alerts.remove(&key)) {
+      (SeverityLevel::Warning | SeverityLevel::Critical, None) => {
+        to_open.push(Alert {
+          id: Default::default(),
+          ts,
+          resolved: false,
+          resolved_ts: None,
+          level,
+          target: endpoint.target,
+          data,
+          ..Default::default()
+        });
+      }
+      (
+        SeverityLevel::Warning | SeverityLevel::Critical,
+        Some(mut alert),
+      ) => {
+        if alert.level != level {
+          alert.level = level;
+          alert.data = data;
+          to_update.push(alert);
+        }
+      }
+      (SeverityLevel::Ok, Some(mut alert)) => {
+        // The certificate was renewed
+        alert.data = data;
+        to_resolve.push(alert);
+      }
+      (SeverityLevel::Ok, None) => {}
+    }
+  }
+
+  // The remaining open alerts are on links which were removed,
+  // or resources which no longer send alerts.
+  to_resolve.extend(
+    open_alerts
+      .into_iter()
+      .filter(|(key, _)| !unknown.contains(key))
+      .map(|(_, alert)| alert),
+  );
+
+  tokio::join!(resolve_alerts(to_resolve), update_alerts(to_update));
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!("failed to record certificate alerts to db | {e:#}");
+  }
+  send_alerts(&to_open).await;
+}
+
+/// Collects the https links on the Servers, Deployments,
+/// and Stacks which send alerts.
+async fn get_endpoints() -> anyhow::Result<Vec<Endpoint>> {
+  let db = db_client();
+  let (servers, deployments, stacks) = tokio::try_join!(
+    find_collect(&db.servers, None, None),
+    find_collect(&db.deployments, None, None),
+    find_collect(&db.stacks, None, None),
+  )
+  .context("failed to get resources from db")?;
+
+  let servers = servers
+    .into_iter()
+    .filter(|server| server.config.enabled)
+    .map(|server| {
+      (
+        ResourceTarget::Server(server.id),
+        server.name,
+        server.config.links,
+      )
+    });
+  let deployments = deployments
+    .into_iter()
+    .filter(|deployment| deployment.config.send_alerts)
+    .map(|deployment| {
+      (
+        ResourceTarget::Deployment(deployment.id),
+        deployment.name,
+        deployment.config.links,
+      )
+    });
+  let stacks = stacks
+    .into_iter()
+    .filter(|stack| stack.config.send_alerts)
+    .map(|stack| {
+      (
+        ResourceTarget::Stack(stack.id),
+        stack.name,
+        stack.config.links,
+      )
+    });
+
+  let endpoints = servers
+    .chain(deployments)
+    .chain(stacks)
+    .flat_map(|(target, name, links)| {
+      let id = target.extract_variant_id().1.clone();
+      links
+        .into_iter()
+        .filter(|link| link.starts_with("https://"))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .map(move |url| Endpoint {
+          target: target.clone(),
+          id: id.clone(),
+          name: name.clone(),
+          url,
+        })
+    })
+    .collect();
+
+  Ok(endpoints)
+}
+
+/// Connects to the https url, and returns the
+/// expiry timestamp (in ms) of the certificate it serves.
+async fn certificate_expiry(url: &str) -> anyhow::Result<i64> {
+  let url = Url::parse(url).context("invalid url")?;
+  let host = url
+    .host_str()
+    .context("url has no host")?
+    .trim_start_matches('[')
+    .trim_end_matches(']');
+  let port = url.port_or_known_default().unwrap_or(443);
+  let server_name = match host.parse::<IpAddr>() {
+    Ok(ip) => ServerName::from(ip),
+    Err(_) => ServerName::try_from(host.to_string())
+      .context("invalid server name")?,
+  };
+
+  let connect = async {
+    let stream = TcpStream::connect((host, port))
+      .await
+      .context("failed to connect")?;
+    TlsConnector::from(tls_config())
+      .connect(server_name, stream)
+      .await
+      .context("tls handshake failed")
+  };
+  let stream = tokio::time::timeout(PROBE_TIMEOUT, connect)
+    .await
+    .context("timed out probing certificate")??;
+
+  let certificate = stream
+    .get_ref()
+    .1
+    .peer_certificates()
+    .and_then(|certificates| certificates.first())
+    .context("no certificate was served")?;
+  let certificate = Certificate::from_der(certificate)
+    .context("failed to parse certificate")?;
+  let not_after = certificate
+    .tbs_certificate
+    .validity
+    .not_after
+    .to_unix_duration();
+
+  Ok(not_after.as_millis() as i64)
+}
+
+fn tls_config() -> Arc<ClientConfig> {
+  static TLS_CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+  TLS_CONFIG
+    .get_or_init(|| {
+      let provider =
+        Arc::new(rustls::crypto::ring::default_provider());
+      let config =
+        ClientConfig::builder_with_provider(provider.clone())
+          .with_safe_default_protocol_versions()
+          .expect("failed to configure tls protocol versions")
+          .dangerous()
+          .with_custom_certificate_verifier(Arc::new(
+            AcceptAnyCertificate(provider),
+          ))
+          .with_no_client_auth();
+      Arc::new(config)
+    })
+    .clone()
+}
+
+/// The certificate is only inspected for its expiry,
+/// so it is accepted even if expired or untrusted (eg. self signed).
+/// The handshake signatures are still checked.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+  fn verify_server_cert(
+    &self,
+    _end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    Ok(ServerCertVerified::assertion())
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    verify_tls12_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    verify_tls13_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    self.0.signature_verification_algorithms.supported_schemes()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Datelike;
+  use rcgen::{date_time_ymd, CertificateParams, KeyPair};
+  use rustls::{
+    pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+    ServerConfig,
+  };
+  use tokio::net::TcpListener;
+  use tokio_rustls::TlsAcceptor;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn reads_expiry_of_short_lived_certificate() {
+    let expires = chrono::Utc::now() + chrono::Duration::days(3);
+    let mut params =
+      CertificateParams::new(vec![String::from("127.0.0.1")])
+        .unwrap();
+    let not_after = date_time_ymd(
+      expires.year(),
+      expires.month() as u8,
+      expires.day() as u8,
+    );
+    params.not_after = not_after;
+    let key = KeyPair::generate().unwrap();
+    let certificate = params.self_signed(&key).unwrap();
+
+    let config = ServerConfig::builder_with_provider(Arc::new(
+      rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(
+      vec![certificate.der().clone()],
+      PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+        key.serialize_der(),
+      )),
+    )
+    .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let _ = acceptor.accept(stream).await;
+    });
+
+    let expiry =
+      certificate_expiry(&format!("https://127.0.0.1:{port}"))
+        .await
+        .unwrap();
+
+    assert_eq!(expiry, not_after.unix_timestamp() * 1000);
+  }
+}
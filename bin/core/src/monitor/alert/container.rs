@@ -0,0 +1,406 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::Arc,
+};
+
+use derive_variants::ExtractVariant;
+use komodo_client::entities::{
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  deployment::DeploymentState,
+  docker::container::ContainerStats,
+  server::ServerState,
+  stack::StackState,
+  ResourceTarget,
+};
+use mungos::find::find_collect;
+
+use crate::{
+  alert::{record_alerts, send_alerts},
+  monitor::CachedServerStatus,
+  state::{
+    db_client, deployment_status_cache, server_status_cache,
+    stack_status_cache,
+  },
+};
+
+use super::{get_open_alerts, resolve_alerts, update_alerts};
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// Like the server alerts, open alerts are only resolved once usage
+/// drops this percentage below the lowest threshold.
+const RESOLVE_MARGIN_PERCENTAGE: f64 = 5.0;
+
+/// (target, container name, alert type) -> open alert
+type OpenContainerAlertMap =
+  HashMap<(ResourceTarget, String, AlertDataVariant), Alert>;
+
+#[derive(Default)]
+struct ContainerAlerts {
+  to_open: Vec<Alert>,
+  to_update: Vec<Alert>,
+  to_resolve: Vec<Alert>,
+}
+
+/// The container and the thresholds to check its usage against.
+struct ContainerUsageCheck<'a> {
+  target: ResourceTarget,
+  id: &'a str,
+  name: &'a str,
+  server_id: &'a str,
+  server_name: String,
+  container: &'a str,
+  service: Option<&'a str>,
+  cpu_warning: f64,
+  cpu_critical: f64,
+  mem_warning_mb: f64,
+  mem_critical_mb: f64,
+  send_alerts: bool,
+}
+
+#[instrument(level = "debug")]
+pub async fn alert_container_usage(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  let (cpu_alerts, mem_alerts, deployments, stacks) = tokio::join!(
+    get_open_alerts(AlertDataVariant::ContainerCpu),
+    get_open_alerts(AlertDataVariant::ContainerMem),
+    find_collect(&db_client().deployments, None, None),
+    find_collect(&db_client().stacks, None, None),
+  );
+  let (cpu_alerts, mem_alerts) = match (cpu_alerts, mem_alerts) {
+    (Ok(cpu_alerts), Ok(mem_alerts)) => (cpu_alerts, mem_alerts),
+    (Err(e), _) | (_, Err(e)) => {
+      error!("{e:#}");
+      return;
+    }
+  };
+  let (deployments, stacks) = match (deployments, stacks) {
+    (Ok(deployments), Ok(stacks)) => (deployments, stacks),
+    (Err(e), _) | (_, Err(e)) => {
+      error!("failed to get resources from db | {e:#}");
+      return;
+    }
+  };
+
+  let mut open_alerts = cpu_alerts
+    .into_iter()
+    .chain(mem_alerts)
+    .filter_map(|alert| {
+      let container = match &alert.data {
+        AlertData::ContainerCpu { container, .. }
+        | AlertData::ContainerMem { container, .. } => {
+          container.clone()
+        }
+        _ => return None,
+      };
+      let variant = alert.data.extract_variant();
+      Some(((alert.target.clone(), container, variant), alert))
+    })
+    .collect::<OpenContainerAlertMap>();
+
+  let servers = server_status_cache()
+    .get_list()
+    .await
+    .into_iter()
+    .map(|status| (status.id.clone(), status))
+    .collect::<HashMap<_, _>>();
+
+  // Usage is not known while the server is unreachable,
+  // leave any open alerts on these as is. Containers which
+  // aren't running have no stats, and their alerts are resolved.
+  let mut unknown = HashSet::<ResourceTarget>::new();
+  let mut alerts = ContainerAlerts::default();
+
+  let deployments = deployments
+    .into_iter()
+    .map(|deployment| (deployment.id.clone(), deployment))
+    .collect::<HashMap<_, _>>();
+
+  for status in deployment_status_cache().get_list().await {
+    let target = ResourceTarget::Deployment(status.curr.id.clone());
+    let (Some(deployment), Some(container)) =
+      (deployments.get(&status.curr.id), &status.curr.container)
+    else {
+      continue;
+    };
+    let config = &deployment.config;
+    if config.cpu_warning <= 0.0
+      && config.cpu_critical <= 0.0
+      && config.mem_warning_mb <= 0.0
+      && config.mem_critical_mb <= 0.0
+    {
+      continue;
+    }
+    let (true, Some(server_stats)) = (
+      status.curr.state != DeploymentState::Unknown,
+      server_container_stats(&servers, &config.server_id),
+    ) else {
+      unknown.insert(target);
+      continue;
+    };
+    let Some(stats) = container_stats(server_stats, &container.name)
+    else {
+      continue;
+    };
+    let check = ContainerUsageCheck {
+      target,
+      id: &deployment.id,
+      name: &deployment.name,
+      server_id: &config.server_id,
+      server_name: server_name(server_names, &config.server_id),
+      container: &container.name,
+      service: None,
+      cpu_warning: config.cpu_warning,
+      cpu_critical: config.cpu_critical,
+      mem_warning_mb: config.mem_warning_mb,
+      mem_critical_mb: config.mem_critical_mb,
+      send_alerts: config.send_alerts,
+    };
+    check_container_usage(
+      ts,
+      &check,
+      stats,
+      &mut open_alerts,
+      &mut alerts,
+    );
+  }
+
+  let stacks = stacks
+    .into_iter()
+    .map(|stack| (stack.id.clone(), stack))
+    .collect::<HashMap<_, _>>();
+
+  for status in stack_status_cache().get_list().await {
+    let target = ResourceTarget::Stack(status.curr.id.clone());
+    let Some(stack) = stacks.get(&status.curr.id) else {
+      continue;
+    };
+    let config = &stack.config;
+    let (true, Some(server_stats)) = (
+      status.curr.state != StackState::Unknown,
+      server_container_stats(&servers, &config.server_id),
+    ) else {
+      unknown.insert(target);
+      continue;
+    };
+    for service in &status.curr.services {
+      let Some(container) = &service.container else {
+        continue;
+      };
+      let Some(thresholds) = config
+        .service_thresholds
+        .iter()
+        .find(|thresholds| thresholds.service == service.service)
+      else {
+        continue;
+      };
+      let Some(stats) =
+        container_stats(server_stats, &container.name)
+      else {
+        continue;
+      };
+      let check = ContainerUsageCheck {
+        target: target.clone(),
+        id: &stack.id,
+        name: &stack.name,
+        server_id: &config.server_id,
+        server_name: server_name(server_names, &config.server_id),
+        container: &container.name,
+        service: Some(&service.service),
+        cpu_warning: thresholds.cpu_warning,
+        cpu_critical: thresholds.cpu_critical,
+        mem_warning_mb: thresholds.mem_warning_mb,
+        mem_critical_mb: thresholds.mem_critical_mb,
+        send_alerts: config.send_alerts,
+      };
+      check_container_usage(
+        ts,
+        &check,
+        stats,
+        &mut open_alerts,
+        &mut alerts,
+      );
+    }
+  }
+
+  // The remaining open alerts are on containers which no longer exist,
+  // or no longer have thresholds configured.
+  alerts.to_resolve.extend(
+    open_alerts
+      .into_iter()
+      .filter(|((target, _, _), _)| !unknown.contains(target))
+      .map(|(_, alert)| alert),
+  );
+
+  let ContainerAlerts {
+    mut to_open,
+    to_update,
+    to_resolve,
+  } = alerts;
+
+  tokio::join!(resolve_alerts(to_resolve), update_alerts(to_update));
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!("failed to record container usage alerts to db | {e:#}");
+  }
+  send_alerts(&to_open).await;
+}
+
+fn check_container_usage(
+  ts: i64,
+  check: &ContainerUsageCheck,
+  stats: &ContainerStats,
+  open_alerts: &mut OpenContainerAlertMap,
+  alerts: &mut ContainerAlerts,
+) {
+  // CPU
+  let percentage = stats.cpu_perc().unwrap_or_default();
+  let cpu_data = AlertData::ContainerCpu {
+    id: check.id.to_string(),
+    name: check.name.to_string(),
+    server_id: check.server_id.to_string(),
+    server_name: check.server_name.clone(),
+    container: check.container.to_string(),
+    service: check.service.map(str::to_string),
+    percentage,
+  };
+  let (level, should_close) =
+    usage_level(percentage, check.cpu_warning, check.cpu_critical);
+  handle_container_alert(
+    ts,
+    check,
+    level,
+    should_close,
+    cpu_data,
+    open_alerts,
+    alerts,
+  );
+
+  // MEM
+  let (used, limit) = stats.mem_usage_bytes().unwrap_or_default();
+  let used_mb = used / BYTES_PER_MB;
+  let mem_data = AlertData::ContainerMem {
+    id: check.id.to_string(),
+    name: check.name.to_string(),
+    server_id: check.server_id.to_string(),
+    server_name: check.server_name.clone(),
+    container: check.container.to_string(),
+    service: check.service.map(str::to_string),
+    used_mb,
+    limit_mb: limit / BYTES_PER_MB,
+  };
+  let (level, should_close) =
+    usage_level(used_mb, check.mem_warning_mb, check.mem_critical_mb);
+  handle_container_alert(
+    ts,
+    check,
+    level,
+    should_close,
+    mem_data,
+    open_alerts,
+    alerts,
+  );
+}
+
+fn handle_container_alert(
+  ts: i64,
+  check: &ContainerUsageCheck,
+  level: SeverityLevel,
+  should_close: bool,
+  data: AlertData,
+  open_alerts: &mut OpenContainerAlertMap,
+  alerts: &mut ContainerAlerts,
+) {
+  let open_alert = open_alerts.remove(&(
+    check.target.clone(),
+    check.container.to_string(),
+    data.extract_variant(),
+  ));
+  match (level, open_alert, should_close) {
+    (SeverityLevel::Warning | SeverityLevel::Critical, None, _) => {
+      if !check.send_alerts {
+        return;
+      }
+      alerts.to_open.push(Alert {
+        id: Default::default(),
+        ts,
+        resolved: false,
+        resolved_ts: None,
+        level,
+        target: check.target.clone(),
+        data,
+        ..Default::default()
+      });
+    }
+    // modify alert level only if it has increased
+    (
+      SeverityLevel::Warning | SeverityLevel::Critical,
+      Some(mut alert),
+      _,
+    ) if alert.level < level => {
+      alert.level = level;
+      alert.data = data;
+      alerts.to_update.push(alert);
+    }
+    (SeverityLevel::Ok, Some(mut alert), true) => {
+      alert.data = data;
+      alerts.to_resolve.push(alert);
+    }
+    _ => {}
+  }
+}
+
+/// Returns the level of the usage, and whether it
+/// is low enough to resolve an open alert.
+fn usage_level(
+  value: f64,
+  warning: f64,
+  critical: f64,
+) -> (SeverityLevel, bool) {
+  if critical > 0.0 && value >= critical {
+    return (SeverityLevel::Critical, false);
+  }
+  if warning > 0.0 && value >= warning {
+    return (SeverityLevel::Warning, false);
+  }
+  let lowest = if warning > 0.0 { warning } else { critical };
+  // With no thresholds configured, any open alert should be resolved.
+  let should_close = lowest <= 0.0
+    || value < lowest * (1.0 - RESOLVE_MARGIN_PERCENTAGE / 100.0);
+  (SeverityLevel::Ok, should_close)
+}
+
+/// The container stats on the server, if it is reachable
+/// and they were fetched. Only running containers are included.
+fn server_container_stats<'a>(
+  servers: &'a HashMap<String, Arc<CachedServerStatus>>,
+  server_id: &str,
+) -> Option<&'a [ContainerStats]> {
+  let server = servers.get(server_id)?;
+  if server.state != ServerState::Ok {
+    return None;
+  }
+  server.container_stats.as_deref()
+}
+
+fn container_stats<'a>(
+  stats: &'a [ContainerStats],
+  container: &str,
+) -> Option<&'a ContainerStats> {
+  stats.iter().find(|stats| stats.name == container)
+}
+
+fn server_name(
+  server_names: &HashMap<String, String>,
+  server_id: &str,
+) -> String {
+  server_names
+    .get(server_id)
+    .cloned()
+    .unwrap_or(String::from("unknown"))
+}
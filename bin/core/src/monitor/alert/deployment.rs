@@ -1,16 +1,25 @@
 use std::collections::HashMap;
 
 use komodo_client::entities::{
-  alert::{Alert, AlertData, SeverityLevel},
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
   deployment::{Deployment, DeploymentState},
+  docker::container::HealthStatusEnum,
   ResourceTarget,
 };
 
+use mungos::find::find_collect;
+
 use crate::{
-  alert::send_alerts, monitor::deployment_status_cache, resource,
+  alert::{record_alerts, send_alerts},
+  monitor::deployment_status_cache,
+  resource,
   state::db_client,
 };
 
+use super::{
+  get_open_alerts, resolve_alerts, restarts::restarts_in_window,
+};
+
 #[instrument(level = "debug")]
 pub async fn alert_deployments(
   ts: i64,
@@ -65,6 +74,7 @@ pub async fn alert_deployments(
         target,
         data,
         ts,
+        ..Default::default()
       };
       alerts.push(alert);
     }
@@ -72,9 +82,231 @@ pub async fn alert_deployments(
   if alerts.is_empty() {
     return;
   }
-  send_alerts(&alerts).await;
-  let res = db_client().alerts.insert_many(alerts).await;
-  if let Err(e) = res {
+  if let Err(e) = record_alerts(&mut alerts).await {
     error!("failed to record deployment status alerts to db | {e:#}");
   }
+  send_alerts(&alerts).await;
+}
+
+#[instrument(level = "debug")]
+pub async fn alert_deployments_health(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  let mut open_alerts =
+    match get_open_alerts(AlertDataVariant::ContainerUnhealthy).await
+    {
+      Ok(alerts) => alerts
+        .into_iter()
+        .filter_map(|alert| match &alert.target {
+          ResourceTarget::Deployment(id) => Some((id.clone(), alert)),
+          _ => None,
+        })
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("{e:#}");
+        return;
+      }
+    };
+
+  let mut to_open = Vec::<Alert>::new();
+  let mut to_resolve = Vec::<Alert>::new();
+
+  for status in deployment_status_cache().get_list().await {
+    let open_alert = open_alerts.remove(&status.curr.id);
+
+    // Health is not known while the server is unreachable,
+    // leave any open alert as is.
+    if status.curr.state == DeploymentState::Unknown {
+      continue;
+    }
+
+    let unhealthy =
+      status.curr.container.as_ref().is_some_and(|container| {
+        container.health == HealthStatusEnum::Unhealthy
+      });
+
+    match (unhealthy, open_alert) {
+      (true, None) => {
+        let Ok(deployment) =
+          resource::get::<Deployment>(&status.curr.id)
+            .await
+            .inspect_err(|e| {
+              error!("failed to get deployment from db | {e:#?}")
+            })
+        else {
+          continue;
+        };
+        if !deployment.config.send_alerts {
+          continue;
+        }
+        let target: ResourceTarget = (&deployment).into();
+        let data = AlertData::ContainerUnhealthy {
+          id: status.curr.id.clone(),
+          container: status
+            .curr
+            .container
+            .as_ref()
+            .map(|container| container.name.clone())
+            .unwrap_or_else(|| deployment.name.clone()),
+          name: deployment.name,
+          server_name: server_names
+            .get(&deployment.config.server_id)
+            .cloned()
+            .unwrap_or(String::from("unknown")),
+          server_id: deployment.config.server_id,
+          service: None,
+        };
+        to_open.push(Alert {
+          id: Default::default(),
+          level: SeverityLevel::Critical,
+          resolved: false,
+          resolved_ts: None,
+          target,
+          data,
+          ts,
+          ..Default::default()
+        });
+      }
+      (false, Some(alert)) => to_resolve.push(alert),
+      _ => {}
+    }
+  }
+
+  // The remaining open alerts are on deployments which no longer exist.
+  to_resolve.extend(open_alerts.into_values());
+
+  resolve_alerts(to_resolve).await;
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!("failed to record deployment health alerts to db | {e:#}");
+  }
+  send_alerts(&to_open).await;
+}
+
+#[instrument(level = "debug")]
+pub async fn alert_deployments_restarts(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  let mut open_alerts =
+    match get_open_alerts(AlertDataVariant::ContainerRestartLoop)
+      .await
+    {
+      Ok(alerts) => alerts
+        .into_iter()
+        .filter_map(|alert| match &alert.target {
+          ResourceTarget::Deployment(id) => Some((id.clone(), alert)),
+          _ => None,
+        })
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("{e:#}");
+        return;
+      }
+    };
+
+  let mut to_open = Vec::<Alert>::new();
+  let mut to_resolve = Vec::<Alert>::new();
+
+  let deployments =
+    match find_collect(&db_client().deployments, None, None).await {
+      Ok(deployments) => deployments
+        .into_iter()
+        .map(|deployment| (deployment.id.clone(), deployment))
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("failed to get deployments from db | {e:#}");
+        return;
+      }
+    };
+
+  for status in deployment_status_cache().get_list().await {
+    let open_alert = open_alerts.remove(&status.curr.id);
+
+    // Restarts are not known while the server is unreachable,
+    // leave any open alert as is.
+    if status.curr.state == DeploymentState::Unknown {
+      continue;
+    }
+
+    let Some(container) = &status.curr.container else {
+      if let Some(alert) = open_alert {
+        to_resolve.push(alert);
+      }
+      continue;
+    };
+    let Some(restart_count) = container.restart_count else {
+      continue;
+    };
+
+    let Some(deployment) = deployments.get(&status.curr.id) else {
+      continue;
+    };
+
+    let config = &deployment.config;
+    let restarts = restarts_in_window(
+      &deployment.id,
+      &container.name,
+      ts,
+      restart_count,
+      config.restart_loop_window_minutes * 60_000,
+    );
+
+    match open_alert {
+      None => {
+        if !config.send_alerts
+          || !config.send_restart_loop_alerts
+          || config.restart_loop_threshold < 1
+          || restarts <= config.restart_loop_threshold
+        {
+          continue;
+        }
+        let data = AlertData::ContainerRestartLoop {
+          id: deployment.id.clone(),
+          name: deployment.name.clone(),
+          server_id: config.server_id.clone(),
+          server_name: server_names
+            .get(&config.server_id)
+            .cloned()
+            .unwrap_or(String::from("unknown")),
+          container: container.name.clone(),
+          service: None,
+          restarts,
+          window_minutes: config.restart_loop_window_minutes,
+        };
+        to_open.push(Alert {
+          id: Default::default(),
+          level: SeverityLevel::Critical,
+          resolved: false,
+          resolved_ts: None,
+          target: deployment.into(),
+          data,
+          ts,
+          ..Default::default()
+        });
+      }
+      // Resolve once the container has not restarted for a full window.
+      Some(alert) if restarts == 0 => to_resolve.push(alert),
+      Some(_) => {}
+    }
+  }
+
+  // The remaining open alerts are on deployments which no longer exist.
+  to_resolve.extend(open_alerts.into_values());
+
+  resolve_alerts(to_resolve).await;
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!(
+      "failed to record deployment restart alerts to db | {e:#}"
+    );
+  }
+  send_alerts(&to_open).await;
 }
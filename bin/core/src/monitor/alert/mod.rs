@@ -1,13 +1,30 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use anyhow::Context;
 use komodo_client::entities::{
-  resource::ResourceQuery, server::Server, user::User,
+  alert::{Alert, AlertDataVariant, SeverityLevel},
+  komodo_timestamp,
+  resource::ResourceQuery,
+  server::Server,
+  user::User,
 };
+use mungos::{
+  by_id::update_one_by_id,
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId, to_bson},
+};
+
+use crate::{alert::send_alerts, resource, state::db_client};
 
-use crate::resource;
+pub use certificate::spawn_certificate_check_loop;
+pub use probe::spawn_endpoint_probe_loop;
 
+mod certificate;
+mod container;
 mod deployment;
+mod network;
+mod probe;
+mod restarts;
 mod server;
 mod stack;
 
@@ -25,8 +42,16 @@ pub async fn check_alerts(ts: i64) {
   tokio::join!(
     server::alert_servers(ts, servers),
     deployment::alert_deployments(ts, &server_names),
-    stack::alert_stacks(ts, &server_names)
+    deployment::alert_deployments_health(ts, &server_names),
+    stack::alert_stacks(ts, &server_names),
+    stack::alert_stacks_health(ts, &server_names),
+    deployment::alert_deployments_restarts(ts, &server_names),
+    stack::alert_stacks_restarts(ts, &server_names),
+    container::alert_container_usage(ts, &server_names)
   );
+
+  restarts::prune_restart_samples(ts);
+  network::prune_network_entries(ts);
 }
 
 #[instrument(level = "debug")]
@@ -56,3 +81,76 @@ async fn get_all_servers_map(
 
   Ok((servers, server_names))
 }
+
+async fn get_open_alerts(
+  variant: AlertDataVariant,
+) -> anyhow::Result<Vec<Alert>> {
+  find_collect(
+    &db_client().alerts,
+    doc! { "resolved": false, "data.type": format!("{variant:?}") },
+    None,
+  )
+  .await
+  .with_context(|| {
+    format!("failed to get open {variant:?} alerts from db")
+  })
+}
+
+/// Updates the level and data of the open alerts on the db,
+/// and sends the updated alerts.
+async fn update_alerts(alerts: Vec<Alert>) {
+  if alerts.is_empty() {
+    return;
+  }
+
+  for alert in &alerts {
+    let update = || async {
+      let update = doc! { "$set": {
+        "level": to_bson(&alert.level)?,
+        "data": to_bson(&alert.data)?,
+      } };
+      update_one_by_id(&db_client().alerts, &alert.id, update, None)
+        .await?;
+      anyhow::Ok(())
+    };
+    if let Err(e) = update().await {
+      warn!("failed to update alert on db | {e:#}");
+    }
+  }
+
+  send_alerts(&alerts).await;
+}
+
+/// Resolves the open alerts on the db, and sends the resolved alerts.
+async fn resolve_alerts(alerts: Vec<Alert>) {
+  if alerts.is_empty() {
+    return;
+  }
+
+  let ids = alerts
+    .iter()
+    .filter_map(|alert| ObjectId::from_str(&alert.id).ok())
+    .collect::<Vec<_>>();
+  let ts = komodo_timestamp();
+
+  let _ = db_client()
+    .alerts
+    .update_many(
+      doc! { "_id": { "$in": &ids } },
+      doc! { "$set": { "resolved": true, "resolved_ts": ts } },
+    )
+    .await
+    .inspect_err(|e| warn!("failed to resolve alerts on db | {e:#}"));
+
+  let resolved = alerts
+    .into_iter()
+    .map(|mut alert| {
+      alert.resolved = true;
+      alert.resolved_ts = Some(ts);
+      alert.level = SeverityLevel::Ok;
+      alert
+    })
+    .collect::<Vec<_>>();
+
+  send_alerts(&resolved).await;
+}
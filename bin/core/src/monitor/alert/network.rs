@@ -0,0 +1,43 @@
+use std::{
+  collections::HashMap,
+  sync::{Mutex, OnceLock},
+};
+
+/// Entries not updated for this long are dropped,
+/// eg. for servers or interfaces which are no longer reported.
+const MAX_ENTRY_AGE_MS: i64 = 60 * 60 * 1000;
+
+/// (server id, interface) -> (above threshold since ts, last seen ts)
+type AboveThreshold = HashMap<(String, String), (i64, i64)>;
+
+fn above_threshold() -> &'static Mutex<AboveThreshold> {
+  static ABOVE_THRESHOLD: OnceLock<Mutex<AboveThreshold>> =
+    OnceLock::new();
+  ABOVE_THRESHOLD.get_or_init(Default::default)
+}
+
+/// Records whether the interface throughput is above the threshold at `ts`,
+/// and returns how long it has been continuously above the threshold.
+pub fn sustained_ms(
+  server_id: &str,
+  interface: &str,
+  ts: i64,
+  above: bool,
+) -> i64 {
+  let mut entries = above_threshold().lock().unwrap();
+  let key = (server_id.to_string(), interface.to_string());
+  if !above {
+    entries.remove(&key);
+    return 0;
+  }
+  let (since, last_seen) = entries.entry(key).or_insert((ts, ts));
+  *last_seen = ts;
+  ts - *since
+}
+
+pub fn prune_network_entries(ts: i64) {
+  above_threshold()
+    .lock()
+    .unwrap()
+    .retain(|_, (_, last_seen)| ts - *last_seen < MAX_ENTRY_AGE_MS);
+}
@@ -0,0 +1,375 @@
+use std::{
+  collections::{HashMap, HashSet},
+  str::FromStr,
+  sync::{Mutex, OnceLock},
+  time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context};
+use async_timing_util::{
+  wait_until_timelength, Timelength, FIVE_SECONDS_MS,
+};
+use komodo_client::entities::{
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  komodo_timestamp,
+  probe::{EndpointProbe, ProbeRecord},
+  ResourceTarget,
+};
+use mungos::{
+  find::find_collect,
+  mongodb::bson::{doc, oid::ObjectId},
+};
+use tokio::net::TcpStream;
+
+use crate::{
+  alert::{record_alerts, send_alerts},
+  state::db_client,
+};
+
+use super::{get_open_alerts, resolve_alerts};
+
+/// (target, endpoint)
+type ProbeKey = (ResourceTarget, String);
+
+/// A configured probe, and the resource it belongs to.
+struct ProbeTarget {
+  target: ResourceTarget,
+  id: String,
+  name: String,
+  send_alerts: bool,
+  probe: EndpointProbe,
+}
+
+impl ProbeTarget {
+  fn key(&self) -> ProbeKey {
+    (self.target.clone(), self.probe.endpoint.clone())
+  }
+}
+
+/// ProbeKey -> last probed ts
+fn last_probed() -> &'static Mutex<HashMap<ProbeKey, i64>> {
+  static LAST_PROBED: OnceLock<Mutex<HashMap<ProbeKey, i64>>> =
+    OnceLock::new();
+  LAST_PROBED.get_or_init(Default::default)
+}
+
+/// The probes which are still running.
+fn in_flight() -> &'static Mutex<HashSet<ProbeKey>> {
+  static IN_FLIGHT: OnceLock<Mutex<HashSet<ProbeKey>>> =
+    OnceLock::new();
+  IN_FLIGHT.get_or_init(Default::default)
+}
+
+/// Removes the probe from [in_flight] when it finishes.
+struct InFlightGuard(ProbeKey);
+
+impl Drop for InFlightGuard {
+  fn drop(&mut self) {
+    in_flight().lock().unwrap().remove(&self.0);
+  }
+}
+
+fn http_client() -> &'static reqwest::Client {
+  static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+  HTTP_CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Checks every 5 seconds for the Deployment / Stack
+/// endpoint probes which are due, and runs them.
+pub fn spawn_endpoint_probe_loop() {
+  tokio::spawn(async move {
+    loop {
+      wait_until_timelength(Timelength::FiveSeconds, 0).await;
+      run_endpoint_probes().await;
+    }
+  });
+}
+
+#[instrument(level = "debug")]
+async fn run_endpoint_probes() {
+  // Taken before the open alerts are read, so any probe which
+  // finishes after this has its alert recorded, or is skipped.
+  let running = in_flight().lock().unwrap().clone();
+  let (probes, open_alerts) = tokio::join!(
+    get_probe_targets(),
+    get_open_alerts(AlertDataVariant::EndpointDown)
+  );
+  let (probes, open_alerts) = match (probes, open_alerts) {
+    (Ok(probes), Ok(open_alerts)) => (probes, open_alerts),
+    (Err(e), _) | (_, Err(e)) => {
+      error!("{e:#}");
+      return;
+    }
+  };
+  let mut open_alerts = open_alerts
+    .into_iter()
+    .filter_map(|alert| {
+      let AlertData::EndpointDown { endpoint, .. } = &alert.data
+      else {
+        return None;
+      };
+      Some(((alert.target.clone(), endpoint.clone()), alert))
+    })
+    .collect::<HashMap<_, _>>();
+
+  let ts = komodo_timestamp();
+
+  let due = {
+    let mut last_probed = last_probed().lock().unwrap();
+    let configured =
+      probes.iter().map(ProbeTarget::key).collect::<HashSet<_>>();
+    last_probed.retain(|key, _| configured.contains(key));
+    probes
+      .into_iter()
+      .filter(|probe| {
+        let interval_ms = probe.probe.interval_secs as i64 * 1000;
+        // Skip probes still running, which may not have
+        // recorded their alert yet.
+        let due = !running.contains(&probe.key())
+          && last_probed.get(&probe.key()).is_none_or(|last| {
+            // Allow for the loop waking up slightly early / late.
+            ts - last + FIVE_SECONDS_MS as i64 / 2 >= interval_ms
+          });
+        if due {
+          last_probed.insert(probe.key(), ts);
+        } else {
+          // The probe is still configured, leave any open alert as is
+          open_alerts.remove(&probe.key());
+        }
+        due
+      })
+      .collect::<Vec<_>>()
+  };
+
+  // Each probe runs in its own task, so a slow probe
+  // doesn't hold up the others or the next pass.
+  for probe in due {
+    let open_alert = open_alerts.remove(&probe.key());
+    in_flight().lock().unwrap().insert(probe.key());
+    let guard = InFlightGuard(probe.key());
+    tokio::spawn(async move {
+      probe_and_alert(ts, probe, open_alert).await;
+      drop(guard);
+    });
+  }
+
+  // The remaining open alerts are on probes which were removed.
+  resolve_alerts(open_alerts.into_values().collect()).await;
+}
+
+/// Runs the probe and records it,
+/// then opens or recovers its EndpointDown alert.
+async fn probe_and_alert(
+  ts: i64,
+  probe: ProbeTarget,
+  open_alert: Option<Alert>,
+) {
+  let record = run_probe(ts, &probe.target, &probe.probe).await;
+
+  if let Err(e) = db_client().probes.insert_one(&record).await {
+    warn!("failed to record endpoint probe to db | {e:#}");
+  }
+
+  match (record.success, open_alert) {
+    (false, None) if probe.send_alerts => {
+      let mut to_open = [Alert {
+        id: Default::default(),
+        ts,
+        resolved: false,
+        resolved_ts: None,
+        level: SeverityLevel::Critical,
+        target: probe.target,
+        data: AlertData::EndpointDown {
+          id: probe.id,
+          name: probe.name,
+          endpoint: probe.probe.endpoint,
+          error: record.error.unwrap_or_default(),
+        },
+        ..Default::default()
+      }];
+      if let Err(e) = record_alerts(&mut to_open).await {
+        error!("failed to record endpoint alerts to db | {e:#}");
+      }
+      send_alerts(&to_open).await;
+    }
+    (true, Some(alert)) => recover_alerts(ts, vec![alert]).await,
+    _ => {}
+  }
+}
+
+/// Resolves the EndpointDown alerts on the db,
+/// and sends EndpointRecovered alerts in their place.
+async fn recover_alerts(ts: i64, alerts: Vec<Alert>) {
+  if alerts.is_empty() {
+    return;
+  }
+
+  let ids = alerts
+    .iter()
+    .filter_map(|alert| ObjectId::from_str(&alert.id).ok())
+    .collect::<Vec<_>>();
+  let _ = db_client()
+    .alerts
+    .update_many(
+      doc! { "_id": { "$in": &ids } },
+      doc! { "$set": { "resolved": true, "resolved_ts": ts } },
+    )
+    .await
+    .inspect_err(|e| warn!("failed to resolve alerts on db | {e:#}"));
+
+  let (mut recovered, notified): (Vec<_>, Vec<_>) = alerts
+    .into_iter()
+    .filter_map(|alert| {
+      let AlertData::EndpointDown {
+        id, name, endpoint, ..
+      } = alert.data
+      else {
+        return None;
+      };
+      let recovered = Alert {
+        id: Default::default(),
+        ts,
+        resolved: true,
+        resolved_ts: Some(ts),
+        level: SeverityLevel::Ok,
+        // The recovery of a held back EndpointDown is held back too.
+        suppressed: alert.suppressed,
+        target: alert.target,
+        data: AlertData::EndpointRecovered {
+          id,
+          name,
+          endpoint,
+          down_ms: ts - alert.ts,
+        },
+        ..Default::default()
+      };
+      Some((recovered, alert.last_notified))
+    })
+    .unzip();
+
+  if let Err(e) = record_alerts(&mut recovered).await {
+    error!("failed to record endpoint alerts to db | {e:#}");
+  }
+  // Not recorded on the recovered alert, only used so the alerters
+  // notified about the EndpointDown also get the recovery.
+  for (alert, last_notified) in recovered.iter_mut().zip(notified) {
+    alert.last_notified = last_notified;
+  }
+  send_alerts(&recovered).await;
+}
+
+/// Collects the probes configured on Deployments and Stacks.
+async fn get_probe_targets() -> anyhow::Result<Vec<ProbeTarget>> {
+  let db = db_client();
+  let filter = doc! { "config.probes.0": { "$exists": true } };
+  let (deployments, stacks) = tokio::try_join!(
+    find_collect(&db.deployments, filter.clone(), None),
+    find_collect(&db.stacks, filter, None),
+  )
+  .context("failed to get resources with probes from db")?;
+
+  let deployments = deployments.into_iter().map(|deployment| {
+    (
+      ResourceTarget::Deployment(deployment.id.clone()),
+      deployment.id,
+      deployment.name,
+      deployment.config.send_alerts,
+      deployment.config.probes,
+    )
+  });
+  let stacks = stacks.into_iter().map(|stack| {
+    (
+      ResourceTarget::Stack(stack.id.clone()),
+      stack.id,
+      stack.name,
+      stack.config.send_alerts,
+      stack.config.probes,
+    )
+  });
+
+  let mut seen = HashSet::new();
+  let probes = deployments
+    .chain(stacks)
+    .flat_map(|(target, id, name, send_alerts, probes)| {
+      probes.into_iter().map(move |probe| ProbeTarget {
+        target: target.clone(),
+        id: id.clone(),
+        name: name.clone(),
+        send_alerts,
+        probe,
+      })
+    })
+    .filter(|probe| !probe.probe.endpoint.is_empty())
+    // Only the first probe for an endpoint is used
+    .filter(|probe| seen.insert(probe.key()))
+    .collect();
+
+  Ok(probes)
+}
+
+async fn run_probe(
+  ts: i64,
+  target: &ResourceTarget,
+  probe: &EndpointProbe,
+) -> ProbeRecord {
+  // Time out before the next probe is due.
+  let timeout = Duration::from_secs(
+    probe.timeout_secs.clamp(1, probe.interval_secs.max(1)),
+  );
+  let start = Instant::now();
+  let (status, res) = if probe.is_http() {
+    probe_http(probe, timeout).await
+  } else {
+    (None, probe_tcp(&probe.endpoint, timeout).await)
+  };
+  ProbeRecord {
+    ts,
+    target: target.clone(),
+    endpoint: probe.endpoint.clone(),
+    success: res.is_ok(),
+    status,
+    response_time_ms: start.elapsed().as_millis() as i64,
+    error: res.err().map(|e| format!("{e:#}")),
+  }
+}
+
+async fn probe_http(
+  probe: &EndpointProbe,
+  timeout: Duration,
+) -> (Option<u16>, anyhow::Result<()>) {
+  let res = http_client()
+    .get(&probe.endpoint)
+    .timeout(timeout)
+    .send()
+    .await;
+  let status = match res {
+    Ok(res) => res.status().as_u16(),
+    Err(e) => {
+      return (
+        None,
+        Err(anyhow::Error::from(e).context("request failed")),
+      )
+    }
+  };
+  let res = match probe.expected_status {
+    0 if status < 400 => Ok(()),
+    0 => Err(anyhow!("got error status {status}")),
+    expected if status == expected => Ok(()),
+    expected => {
+      Err(anyhow!("expected status {expected}, got {status}"))
+    }
+  };
+  (Some(status), res)
+}
+
+async fn probe_tcp(
+  endpoint: &str,
+  timeout: Duration,
+) -> anyhow::Result<()> {
+  let address = endpoint.trim_start_matches("tcp://");
+  tokio::time::timeout(timeout, TcpStream::connect(address))
+    .await
+    .context("timed out connecting")?
+    .context("failed to connect")?;
+  Ok(())
+}
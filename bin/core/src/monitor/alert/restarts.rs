@@ -0,0 +1,62 @@
+use std::{
+  collections::{HashMap, VecDeque},
+  sync::{Mutex, OnceLock},
+};
+
+use komodo_client::entities::I64;
+
+/// Samples older than this are dropped along with
+/// containers which are no longer reported.
+const MAX_SAMPLE_AGE_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// (resource id, container name) -> (ts, restart count) samples
+type RestartSamples = HashMap<(String, String), VecDeque<(i64, I64)>>;
+
+fn restart_samples() -> &'static Mutex<RestartSamples> {
+  static RESTART_SAMPLES: OnceLock<Mutex<RestartSamples>> =
+    OnceLock::new();
+  RESTART_SAMPLES.get_or_init(Default::default)
+}
+
+/// Records the container restart count observed at `ts`,
+/// and returns the number of restarts within the window.
+pub fn restarts_in_window(
+  resource_id: &str,
+  container: &str,
+  ts: i64,
+  restart_count: I64,
+  window_ms: i64,
+) -> I64 {
+  let mut samples = restart_samples().lock().unwrap();
+  let samples = samples
+    .entry((resource_id.to_string(), container.to_string()))
+    .or_default();
+
+  // The count starts over when the container is recreated.
+  if samples
+    .back()
+    .is_some_and(|(_, count)| *count > restart_count)
+  {
+    samples.clear();
+  }
+  samples.push_back((ts, restart_count));
+
+  // Keep the last sample before the window start as the baseline.
+  let window_start = ts - window_ms;
+  while samples.len() > 1 && samples[1].0 <= window_start {
+    samples.pop_front();
+  }
+
+  samples
+    .front()
+    .map(|(_, baseline)| restart_count - baseline)
+    .unwrap_or_default()
+}
+
+pub fn prune_restart_samples(ts: i64) {
+  restart_samples().lock().unwrap().retain(|_, samples| {
+    samples.back().is_some_and(|(sample_ts, _)| {
+      ts - sample_ts < MAX_SAMPLE_AGE_MS
+    })
+  });
+}
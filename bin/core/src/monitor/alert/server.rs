@@ -6,24 +6,31 @@ use komodo_client::entities::{
   alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
   komodo_timestamp, optional_string,
   server::{Server, ServerState},
+  stats::SystemStats,
   ResourceTarget,
 };
 use mongo_indexed::Indexed;
 use mungos::{
   bulk_update::{self, BulkUpdate},
   find::find_collect,
-  mongodb::bson::{doc, oid::ObjectId, to_bson},
+  mongodb::bson::{doc, oid::ObjectId, to_document},
 };
 
 use crate::{
   alert::send_alerts,
+  monitor::helpers::interface_throughput,
   state::{db_client, server_status_cache},
 };
 
+use super::network;
+
 type SendAlerts = bool;
 type OpenAlertMap<T = AlertDataVariant> =
   HashMap<ResourceTarget, HashMap<T, Alert>>;
 type OpenDiskAlertMap = OpenAlertMap<PathBuf>;
+/// For alerts on a named part of the server,
+/// ie the network interface or process name.
+type OpenNamedAlertMap = OpenAlertMap<(AlertDataVariant, String)>;
 
 #[instrument(level = "debug")]
 pub async fn alert_servers(
@@ -32,13 +39,14 @@ pub async fn alert_servers(
 ) {
   let server_statuses = server_status_cache().get_list().await;
 
-  let (alerts, disk_alerts) = match get_open_alerts().await {
-    Ok(alerts) => alerts,
-    Err(e) => {
-      error!("{e:#}");
-      return;
-    }
-  };
+  let (alerts, disk_alerts, named_alerts) =
+    match get_open_alerts().await {
+      Ok(alerts) => alerts,
+      Err(e) => {
+        error!("{e:#}");
+        return;
+      }
+    };
 
   let mut alerts_to_open = Vec::<(Alert, SendAlerts)>::new();
   let mut alerts_to_update = Vec::<(Alert, SendAlerts)>::new();
@@ -73,6 +81,7 @@ pub async fn alert_servers(
             region: optional_string(&server.config.region),
             err: server_status.err.clone(),
           },
+          ..Default::default()
         };
         alerts_to_open
           .push((alert, server.config.send_unreachable_alerts))
@@ -142,6 +151,7 @@ pub async fn alert_servers(
               .map(|s| s.cpu_perc as f64)
               .unwrap_or(0.0),
           },
+          ..Default::default()
         };
         alerts_to_open.push((alert, server.config.send_cpu_alerts));
       }
@@ -218,6 +228,7 @@ pub async fn alert_servers(
               .map(|s| s.mem_used_gb)
               .unwrap_or(0.0),
           },
+          ..Default::default()
         };
         alerts_to_open.push((alert, server.config.send_mem_alerts));
       }
@@ -307,6 +318,7 @@ pub async fn alert_servers(
               total_gb: disk.map(|d| d.total_gb).unwrap_or_default(),
               used_gb: disk.map(|d| d.used_gb).unwrap_or_default(),
             },
+            ..Default::default()
           };
           alerts_to_open
             .push((alert, server.config.send_disk_alerts));
@@ -367,6 +379,154 @@ pub async fn alert_servers(
         }
       }
     }
+
+    // ===================
+    // SERVER NETWORK
+    // ===================
+
+    let server_named_alerts = named_alerts
+      .get(&ResourceTarget::Server(server_status.id.clone()));
+    let sustained_ms =
+      server.config.network_sustained_minutes * 60_000;
+
+    for (interface, health) in &health.network {
+      let network_alert = server_named_alerts
+        .as_ref()
+        .and_then(|alerts| {
+          alerts.get(&(
+            AlertDataVariant::ServerNetwork,
+            interface.clone(),
+          ))
+        })
+        .cloned();
+      let above = health.level != SeverityLevel::Ok;
+      let sustained = network::sustained_ms(
+        &server_status.id,
+        interface,
+        ts,
+        above,
+      ) >= sustained_ms;
+      let data = || {
+        let (ingress_mb_per_sec, egress_mb_per_sec) =
+          network_throughput(&server_status.stats, interface);
+        AlertData::ServerNetwork {
+          id: server_status.id.clone(),
+          name: server.name.clone(),
+          region: optional_string(&server.config.region),
+          interface: interface.clone(),
+          ingress_mb_per_sec,
+          egress_mb_per_sec,
+        }
+      };
+      match (health.level, network_alert, health.should_close_alert) {
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          None,
+          _,
+        ) => {
+          if !sustained {
+            continue;
+          }
+          let alert = Alert {
+            id: Default::default(),
+            ts,
+            resolved: false,
+            resolved_ts: None,
+            level: health.level,
+            target: ResourceTarget::Server(server_status.id.clone()),
+            data: data(),
+            ..Default::default()
+          };
+          alerts_to_open
+            .push((alert, server.config.send_network_alerts));
+        }
+        // modify alert level only if it has increased
+        (
+          SeverityLevel::Warning | SeverityLevel::Critical,
+          Some(mut alert),
+          _,
+        ) if alert.level < health.level => {
+          alert.level = health.level;
+          alert.data = data();
+          alerts_to_update
+            .push((alert, server.config.send_network_alerts));
+        }
+        (SeverityLevel::Ok, Some(mut alert), true) => {
+          alert.level = health.level;
+          alert.data = data();
+          alert_ids_to_close
+            .push((alert, server.config.send_network_alerts))
+        }
+        _ => {}
+      }
+    }
+
+    // ===================
+    // SERVER PROCESSES
+    // ===================
+
+    for (process, health) in &health.processes {
+      let process_alert = server_named_alerts
+        .as_ref()
+        .and_then(|alerts| {
+          alerts.get(&(
+            AlertDataVariant::ServerProcessMissing,
+            process.clone(),
+          ))
+        })
+        .cloned();
+      let data = || AlertData::ServerProcessMissing {
+        id: server_status.id.clone(),
+        name: server.name.clone(),
+        region: optional_string(&server.config.region),
+        process: process.clone(),
+      };
+      match (health.level, process_alert) {
+        (SeverityLevel::Critical, None) => {
+          let alert = Alert {
+            id: Default::default(),
+            ts,
+            resolved: false,
+            resolved_ts: None,
+            level: health.level,
+            target: ResourceTarget::Server(server_status.id.clone()),
+            data: data(),
+            ..Default::default()
+          };
+          alerts_to_open
+            .push((alert, server.config.send_process_alerts));
+        }
+        (SeverityLevel::Ok, Some(mut alert)) => {
+          alert.level = health.level;
+          alert_ids_to_close
+            .push((alert, server.config.send_process_alerts))
+        }
+        _ => {}
+      }
+    }
+
+    // Need to close any open ones on interfaces no longer reported
+    // (or with network alerts disabled), and processes no longer required.
+    if let Some(named_alerts) = server_named_alerts {
+      for ((variant, name), alert) in named_alerts {
+        let (close, send) = match variant {
+          AlertDataVariant::ServerNetwork => (
+            !health.network.contains_key(name),
+            server.config.send_network_alerts,
+          ),
+          AlertDataVariant::ServerProcessMissing => (
+            !server.config.required_processes.contains(name),
+            server.config.send_process_alerts,
+          ),
+          _ => continue,
+        };
+        if close {
+          let mut alert = alert.clone();
+          alert.level = SeverityLevel::Ok;
+          alert_ids_to_close.push((alert, send));
+        }
+      }
+    }
   }
 
   tokio::join!(
@@ -432,9 +592,16 @@ async fn update_alerts(alerts: &[(Alert, SendAlerts)]) {
 
   let open = || async {
     let updates = alerts.iter().map(|(alert, _)| {
+        let mut set = to_document(alert).context("failed to convert alert to bson")?;
+        // Suppression, notification times, and acknowledgement are
+        // marked on the db directly, don't overwrite them with the in memory alert.
+        set.remove("suppressed");
+        set.remove("last_notified");
+        set.remove("acknowledged");
+        set.remove("snoozed_until");
         let update = BulkUpdate {
           query: doc! { "_id": ObjectId::from_str(&alert.id).context("failed to convert alert id to ObjectId")? },
-          update: doc! { "$set": to_bson(alert).context("failed to convert alert to bson")? }
+          update: doc! { "$set": set }
         };
         anyhow::Ok(update)
       })
@@ -531,7 +698,8 @@ async fn resolve_alerts(alerts: &[(Alert, SendAlerts)]) {
 
 #[instrument(level = "debug")]
 async fn get_open_alerts(
-) -> anyhow::Result<(OpenAlertMap, OpenDiskAlertMap)> {
+) -> anyhow::Result<(OpenAlertMap, OpenDiskAlertMap, OpenNamedAlertMap)>
+{
   let alerts = find_collect(
     &db_client().alerts,
     doc! { "resolved": false },
@@ -542,6 +710,7 @@ async fn get_open_alerts(
 
   let mut map = OpenAlertMap::new();
   let mut disk_map = OpenDiskAlertMap::new();
+  let mut named_map = OpenNamedAlertMap::new();
 
   for alert in alerts {
     match &alert.data {
@@ -549,6 +718,22 @@ async fn get_open_alerts(
         let inner = disk_map.entry(alert.target.clone()).or_default();
         inner.insert(path.to_owned(), alert);
       }
+      AlertData::ServerNetwork { interface, .. } => {
+        let inner =
+          named_map.entry(alert.target.clone()).or_default();
+        inner.insert(
+          (AlertDataVariant::ServerNetwork, interface.clone()),
+          alert,
+        );
+      }
+      AlertData::ServerProcessMissing { process, .. } => {
+        let inner =
+          named_map.entry(alert.target.clone()).or_default();
+        inner.insert(
+          (AlertDataVariant::ServerProcessMissing, process.clone()),
+          alert,
+        );
+      }
       _ => {
         let inner = map.entry(alert.target.clone()).or_default();
         inner.insert(alert.data.extract_variant(), alert);
@@ -556,5 +741,22 @@ async fn get_open_alerts(
     }
   }
 
-  Ok((map, disk_map))
+  Ok((map, disk_map, named_map))
+}
+
+/// Get the (ingress, egress) throughput in MB/s on the interface.
+fn network_throughput(
+  stats: &Option<SystemStats>,
+  interface: &str,
+) -> (f64, f64) {
+  stats
+    .as_ref()
+    .and_then(|stats| {
+      let usage = stats
+        .network_usage_interface
+        .iter()
+        .find(|usage| usage.name == interface)?;
+      Some(interface_throughput(usage, stats.polling_rate))
+    })
+    .unwrap_or_default()
 }
@@ -1,17 +1,24 @@
 use std::collections::HashMap;
 
 use komodo_client::entities::{
-  alert::{Alert, AlertData, SeverityLevel},
+  alert::{Alert, AlertData, AlertDataVariant, SeverityLevel},
+  docker::container::HealthStatusEnum,
   stack::{Stack, StackState},
   ResourceTarget,
 };
 
+use mungos::find::find_collect;
+
 use crate::{
-  alert::send_alerts,
+  alert::{record_alerts, send_alerts},
   resource,
   state::{db_client, stack_status_cache},
 };
 
+use super::{
+  get_open_alerts, resolve_alerts, restarts::restarts_in_window,
+};
+
 #[instrument(level = "debug")]
 pub async fn alert_stacks(
   ts: i64,
@@ -64,6 +71,7 @@ pub async fn alert_stacks(
         target,
         data,
         ts,
+        ..Default::default()
       };
       alerts.push(alert);
     }
@@ -71,9 +79,250 @@ pub async fn alert_stacks(
   if alerts.is_empty() {
     return;
   }
-  send_alerts(&alerts).await;
-  let res = db_client().alerts.insert_many(alerts).await;
-  if let Err(e) = res {
+  if let Err(e) = record_alerts(&mut alerts).await {
     error!("failed to record stack status alerts to db | {e:#}");
   }
+  send_alerts(&alerts).await;
+}
+
+#[instrument(level = "debug")]
+pub async fn alert_stacks_health(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  // (stack id, container name) -> open alert
+  let mut open_alerts =
+    match get_open_alerts(AlertDataVariant::ContainerUnhealthy).await
+    {
+      Ok(alerts) => alerts
+        .into_iter()
+        .filter_map(|alert| {
+          let (
+            ResourceTarget::Stack(id),
+            AlertData::ContainerUnhealthy { container, .. },
+          ) = (&alert.target, &alert.data)
+          else {
+            return None;
+          };
+          Some(((id.clone(), container.clone()), alert))
+        })
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("{e:#}");
+        return;
+      }
+    };
+
+  let mut to_open = Vec::<Alert>::new();
+  let mut to_resolve = Vec::<Alert>::new();
+
+  for status in stack_status_cache().get_list().await {
+    // Health is not known while the server is unreachable,
+    // leave any open alerts as is.
+    if status.curr.state == StackState::Unknown {
+      open_alerts.retain(|(id, _), _| id != &status.curr.id);
+      continue;
+    }
+
+    let mut stack = None::<Stack>;
+
+    for service in &status.curr.services {
+      let Some(container) = &service.container else {
+        continue;
+      };
+      let open_alert = open_alerts
+        .remove(&(status.curr.id.clone(), container.name.clone()));
+      let unhealthy = container.health == HealthStatusEnum::Unhealthy;
+
+      match (unhealthy, open_alert) {
+        (true, None) => {
+          if stack.is_none() {
+            stack = resource::get::<Stack>(&status.curr.id)
+              .await
+              .inspect_err(|e| {
+                error!("failed to get stack from db | {e:#?}")
+              })
+              .ok();
+          }
+          let Some(stack) = &stack else {
+            continue;
+          };
+          if !stack.config.send_alerts {
+            continue;
+          }
+          let data = AlertData::ContainerUnhealthy {
+            id: status.curr.id.clone(),
+            name: stack.name.clone(),
+            server_id: stack.config.server_id.clone(),
+            server_name: server_names
+              .get(&stack.config.server_id)
+              .cloned()
+              .unwrap_or(String::from("unknown")),
+            container: container.name.clone(),
+            service: Some(service.service.clone()),
+          };
+          to_open.push(Alert {
+            id: Default::default(),
+            level: SeverityLevel::Critical,
+            resolved: false,
+            resolved_ts: None,
+            target: stack.into(),
+            data,
+            ts,
+            ..Default::default()
+          });
+        }
+        (false, Some(alert)) => to_resolve.push(alert),
+        _ => {}
+      }
+    }
+  }
+
+  // The remaining open alerts are on containers which no longer exist.
+  to_resolve.extend(open_alerts.into_values());
+
+  resolve_alerts(to_resolve).await;
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!("failed to record stack health alerts to db | {e:#}");
+  }
+  send_alerts(&to_open).await;
+}
+
+#[instrument(level = "debug")]
+pub async fn alert_stacks_restarts(
+  ts: i64,
+  server_names: &HashMap<String, String>,
+) {
+  // (stack id, container name) -> open alert
+  let mut open_alerts =
+    match get_open_alerts(AlertDataVariant::ContainerRestartLoop)
+      .await
+    {
+      Ok(alerts) => alerts
+        .into_iter()
+        .filter_map(|alert| {
+          let (
+            ResourceTarget::Stack(id),
+            AlertData::ContainerRestartLoop { container, .. },
+          ) = (&alert.target, &alert.data)
+          else {
+            return None;
+          };
+          Some(((id.clone(), container.clone()), alert))
+        })
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("{e:#}");
+        return;
+      }
+    };
+
+  let stacks =
+    match find_collect(&db_client().stacks, None, None).await {
+      Ok(stacks) => stacks
+        .into_iter()
+        .map(|stack| (stack.id.clone(), stack))
+        .collect::<HashMap<_, _>>(),
+      Err(e) => {
+        error!("failed to get stacks from db | {e:#}");
+        return;
+      }
+    };
+
+  let mut to_open = Vec::<Alert>::new();
+  let mut to_resolve = Vec::<Alert>::new();
+
+  for status in stack_status_cache().get_list().await {
+    // Restarts are not known while the server is unreachable,
+    // leave any open alerts as is.
+    if status.curr.state == StackState::Unknown {
+      open_alerts.retain(|(id, _), _| id != &status.curr.id);
+      continue;
+    }
+
+    let services = status
+      .curr
+      .services
+      .iter()
+      .filter_map(|service| {
+        let container = service.container.as_ref()?;
+        Some((service, container, container.restart_count?))
+      })
+      .collect::<Vec<_>>();
+    if services.is_empty() {
+      continue;
+    }
+
+    let Some(stack) = stacks.get(&status.curr.id) else {
+      continue;
+    };
+    let config = &stack.config;
+
+    for (service, container, restart_count) in services {
+      let open_alert = open_alerts
+        .remove(&(stack.id.clone(), container.name.clone()));
+      let restarts = restarts_in_window(
+        &stack.id,
+        &container.name,
+        ts,
+        restart_count,
+        config.restart_loop_window_minutes * 60_000,
+      );
+
+      match open_alert {
+        None => {
+          if !config.send_alerts
+            || !config.send_restart_loop_alerts
+            || config.restart_loop_threshold < 1
+            || restarts <= config.restart_loop_threshold
+          {
+            continue;
+          }
+          let data = AlertData::ContainerRestartLoop {
+            id: stack.id.clone(),
+            name: stack.name.clone(),
+            server_id: config.server_id.clone(),
+            server_name: server_names
+              .get(&config.server_id)
+              .cloned()
+              .unwrap_or(String::from("unknown")),
+            container: container.name.clone(),
+            service: Some(service.service.clone()),
+            restarts,
+            window_minutes: config.restart_loop_window_minutes,
+          };
+          to_open.push(Alert {
+            id: Default::default(),
+            level: SeverityLevel::Critical,
+            resolved: false,
+            resolved_ts: None,
+            target: stack.into(),
+            data,
+            ts,
+            ..Default::default()
+          });
+        }
+        // Resolve once the container has not restarted for a full window.
+        Some(alert) if restarts == 0 => to_resolve.push(alert),
+        Some(_) => {}
+      }
+    }
+  }
+
+  // The remaining open alerts are on containers which no longer exist.
+  to_resolve.extend(open_alerts.into_values());
+
+  resolve_alerts(to_resolve).await;
+
+  if to_open.is_empty() {
+    return;
+  }
+  if let Err(e) = record_alerts(&mut to_open).await {
+    error!("failed to record stack restart alerts to db | {e:#}");
+  }
+  send_alerts(&to_open).await;
 }
@@ -1,9 +1,12 @@
+use async_timing_util::get_timelength_in_ms;
 use komodo_client::entities::{
   alert::SeverityLevel,
   deployment::{Deployment, DeploymentState},
   docker::{
-    container::ContainerListItem, image::ImageListItem,
-    network::NetworkListItem, volume::VolumeListItem,
+    container::{ContainerListItem, ContainerStats},
+    image::ImageListItem,
+    network::NetworkListItem,
+    volume::VolumeListItem,
   },
   repo::Repo,
   server::{
@@ -11,7 +14,11 @@ use komodo_client::entities::{
     ServerState,
   },
   stack::{ComposeProject, Stack, StackState},
-  stats::{SingleDiskUsage, SystemStats},
+  stats::{
+    SingleDiskUsage, SingleNetworkInterfaceUsage, SystemProcess,
+    SystemStats,
+  },
+  Timelength,
 };
 use serror::Serror;
 
@@ -97,6 +104,7 @@ type DockerLists = (
   Option<Vec<ImageListItem>>,
   Option<Vec<VolumeListItem>>,
   Option<Vec<ComposeProject>>,
+  Option<Vec<ContainerStats>>,
 );
 
 #[instrument(level = "debug", skip_all)]
@@ -105,10 +113,13 @@ pub async fn insert_server_status(
   state: ServerState,
   version: String,
   stats: Option<SystemStats>,
-  (containers, networks, images, volumes, projects): DockerLists,
+  processes: Option<Vec<SystemProcess>>,
+  (containers, networks, images, volumes, projects, container_stats): DockerLists,
   err: impl Into<Option<Serror>>,
 ) {
-  let health = stats.as_ref().map(|s| get_server_health(server, s));
+  let health = stats
+    .as_ref()
+    .map(|s| get_server_health(server, s, processes.as_deref()));
   server_status_cache()
     .insert(
       server.id.clone(),
@@ -123,6 +134,7 @@ pub async fn insert_server_status(
         images,
         volumes,
         projects,
+        container_stats,
         err: err.into(),
       }
       .into(),
@@ -131,6 +143,23 @@ pub async fn insert_server_status(
 }
 
 const ALERT_PERCENTAGE_THRESHOLD: f32 = 5.0;
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+/// Get the (ingress, egress) throughput of the interface in MB/s.
+/// The reported usage is in bytes over the stats polling interval.
+pub fn interface_throughput(
+  usage: &SingleNetworkInterfaceUsage,
+  polling_rate: Timelength,
+) -> (f64, f64) {
+  let polling_secs = polling_rate
+    .try_into()
+    .map(|rate| get_timelength_in_ms(rate) as f64 / 1000.0)
+    .unwrap_or(1.0);
+  (
+    usage.ingress_bytes / BYTES_PER_MB / polling_secs,
+    usage.egress_bytes / BYTES_PER_MB / polling_secs,
+  )
+}
 
 fn get_server_health(
   server: &Server,
@@ -139,16 +168,20 @@ fn get_server_health(
     mem_used_gb,
     mem_total_gb,
     disks,
+    network_usage_interface,
+    polling_rate,
     ..
   }: &SystemStats,
+  processes: Option<&[SystemProcess]>,
 ) -> ServerHealth {
   let ServerConfig {
     cpu_warning,
     cpu_critical,
     mem_warning,
     mem_critical,
-    disk_warning,
-    disk_critical,
+    network_warning,
+    network_critical,
+    required_processes,
     ..
   } = &server.config;
   let mut health = ServerHealth::default();
@@ -179,11 +212,13 @@ fn get_server_health(
     ..
   } in disks
   {
+    let (disk_warning, disk_critical) =
+      server.config.disk_thresholds_for(mount);
     let perc = 100.0 * used_gb / total_gb;
     let mut state = ServerHealthState::default();
-    if perc >= *disk_critical {
+    if perc >= disk_critical {
       state.level = SeverityLevel::Critical;
-    } else if perc >= *disk_warning {
+    } else if perc >= disk_warning {
       state.level = SeverityLevel::Warning;
     } else if perc
       < disk_warning - (ALERT_PERCENTAGE_THRESHOLD as f64)
@@ -193,5 +228,42 @@ fn get_server_health(
     health.disks.insert(mount.clone(), state);
   }
 
+  if *network_warning > 0.0 || *network_critical > 0.0 {
+    for usage in network_usage_interface {
+      let (ingress, egress) =
+        interface_throughput(usage, *polling_rate);
+      let mb_per_sec = ingress.max(egress);
+      let mut state = ServerHealthState::default();
+      if *network_critical > 0.0 && mb_per_sec >= *network_critical {
+        state.level = SeverityLevel::Critical;
+      } else if *network_warning > 0.0
+        && mb_per_sec >= *network_warning
+      {
+        state.level = SeverityLevel::Warning;
+      } else {
+        state.should_close_alert = true;
+      }
+      health.network.insert(usage.name.clone(), state);
+    }
+  }
+
+  if let Some(processes) = processes {
+    for required in required_processes {
+      let running =
+        processes.iter().any(|process| process.name == *required);
+      health.processes.insert(
+        required.clone(),
+        ServerHealthState {
+          level: if running {
+            SeverityLevel::Ok
+          } else {
+            SeverityLevel::Critical
+          },
+          should_close_alert: running,
+        },
+      );
+    }
+  }
+
   health
 }
@@ -4,8 +4,10 @@ use helpers::insert_stacks_status_unknown;
 use komodo_client::entities::{
   deployment::DeploymentState,
   docker::{
-    container::ContainerListItem, image::ImageListItem,
-    network::NetworkListItem, volume::VolumeListItem,
+    container::{ContainerListItem, ContainerStats},
+    image::ImageListItem,
+    network::NetworkListItem,
+    volume::VolumeListItem,
   },
   komodo_timestamp,
   server::{Server, ServerHealth, ServerState},
@@ -19,7 +21,10 @@ use serror::Serror;
 use crate::{
   config::core_config,
   helpers::periphery_client,
-  monitor::{alert::check_alerts, record::record_server_stats},
+  monitor::{
+    alert::check_alerts,
+    record::{record_container_stats, record_server_stats},
+  },
   state::{db_client, deployment_status_cache, repo_status_cache},
 };
 
@@ -33,6 +38,12 @@ mod helpers;
 mod lists;
 mod record;
 mod resources;
+mod rollup;
+
+pub use alert::{
+  spawn_certificate_check_loop, spawn_endpoint_probe_loop,
+};
+pub use rollup::spawn_stats_rollup_loop;
 
 #[derive(Default, Debug)]
 pub struct History<Curr: Default, Prev> {
@@ -52,6 +63,9 @@ pub struct CachedServerStatus {
   pub images: Option<Vec<ImageListItem>>,
   pub volumes: Option<Vec<VolumeListItem>>,
   pub projects: Option<Vec<ComposeProject>>,
+  /// Only fetched when the server records container stats,
+  /// or a resource on it has container usage alert thresholds.
+  pub container_stats: Option<Vec<ContainerStats>>,
   /// Store the error in reaching periphery
   pub err: Option<serror::Serror>,
 }
@@ -113,7 +127,11 @@ async fn refresh_server_cache(ts: i64) {
     update_cache_for_server(&server).await;
   });
   join_all(futures).await;
-  tokio::join!(check_alerts(ts), record_server_stats(ts));
+  tokio::join!(
+    check_alerts(ts),
+    record_server_stats(ts),
+    record_container_stats(ts)
+  );
 }
 
 #[instrument(level = "debug")]
@@ -152,7 +170,8 @@ pub async fn update_cache_for_server(server: &Server) {
       ServerState::Disabled,
       String::from("unknown"),
       None,
-      (None, None, None, None, None),
+      None,
+      (None, None, None, None, None, None),
       None,
     )
     .await;
@@ -177,7 +196,8 @@ pub async fn update_cache_for_server(server: &Server) {
         ServerState::NotOk,
         String::from("unknown"),
         None,
-        (None, None, None, None, None),
+        None,
+        (None, None, None, None, None, None),
         Serror::from(&e),
       )
       .await;
@@ -197,7 +217,8 @@ pub async fn update_cache_for_server(server: &Server) {
           ServerState::NotOk,
           String::from("unknown"),
           None,
-          (None, None, None, None, None),
+          None,
+          (None, None, None, None, None, None),
           Serror::from(&e),
         )
         .await;
@@ -208,11 +229,54 @@ pub async fn update_cache_for_server(server: &Server) {
     None
   };
 
+  // Only needed to check for required processes
+  let processes = if stats.is_some()
+    && !server.config.required_processes.is_empty()
+  {
+    periphery
+      .request(api::stats::GetSystemProcesses {})
+      .await
+      .inspect_err(|e| {
+        warn!(
+          "failed to get processes for server {} | {e:#}",
+          server.name
+        )
+      })
+      .ok()
+  } else {
+    None
+  };
+
+  let needs_container_stats = server.config.record_container_stats
+    || deployments.iter().any(|deployment| {
+      deployment.config.cpu_warning > 0.0
+        || deployment.config.cpu_critical > 0.0
+        || deployment.config.mem_warning_mb > 0.0
+        || deployment.config.mem_critical_mb > 0.0
+    })
+    || stacks
+      .iter()
+      .any(|stack| !stack.config.service_thresholds.is_empty());
+
   match lists::get_docker_lists(&periphery).await {
     Ok((mut containers, networks, images, volumes, projects)) => {
       containers.iter_mut().for_each(|container| {
         container.server_id = Some(server.id.clone())
       });
+      let container_stats = if needs_container_stats {
+        periphery
+          .request(api::container::GetContainerStatsList {})
+          .await
+          .inspect_err(|e| {
+            warn!(
+              "failed to get container stats for server {} | {e:#}",
+              server.name
+            )
+          })
+          .ok()
+      } else {
+        None
+      };
       tokio::join!(
         resources::update_deployment_cache(
           server.name.clone(),
@@ -233,12 +297,14 @@ pub async fn update_cache_for_server(server: &Server) {
         ServerState::Ok,
         version,
         stats,
+        processes,
         (
           Some(containers.clone()),
           Some(networks),
           Some(images),
           Some(volumes),
           Some(projects),
+          container_stats,
         ),
         None,
       )
@@ -252,7 +318,8 @@ pub async fn update_cache_for_server(server: &Server) {
         ServerState::Ok,
         version,
         stats,
-        (None, None, None, None, None),
+        processes,
+        (None, None, None, None, None, None),
         Some(e.into()),
       )
       .await;
@@ -1,6 +1,11 @@
-use komodo_client::entities::stats::{
-  sum_disk_usage, SystemStatsRecord, TotalDiskUsage,
+use komodo_client::entities::{
+  docker::container::ContainerStats,
+  stats::{
+    sum_disk_usage, ContainerStatsRecord, SystemStatsRecord,
+    TotalDiskUsage,
+  },
 };
+use mungos::{find::find_collect, mongodb::bson::doc};
 
 use crate::state::{db_client, server_status_cache};
 
@@ -28,7 +33,10 @@ pub async fn record_server_stats(ts: i64) {
         disks: stats.disks.clone(),
         network_ingress_bytes: stats.network_ingress_bytes,
         network_egress_bytes: stats.network_egress_bytes,
-        network_usage_interface: stats.network_usage_interface.clone(),
+        network_usage_interface: stats
+          .network_usage_interface
+          .clone(),
+        max: None,
       })
     })
     .collect::<Vec<_>>();
@@ -39,3 +47,74 @@ pub async fn record_server_stats(ts: i64) {
     }
   }
 }
+
+#[instrument(level = "debug")]
+pub async fn record_container_stats(ts: i64) {
+  let servers = match find_collect(
+    &db_client().servers,
+    doc! {
+      "config.enabled": true,
+      "config.record_container_stats": true,
+    },
+    None,
+  )
+  .await
+  {
+    Ok(servers) => servers,
+    Err(e) => {
+      error!(
+        "failed to get servers to record container stats | {e:#}"
+      );
+      return;
+    }
+  };
+
+  let status_cache = server_status_cache();
+  let mut records = Vec::new();
+  for server in servers {
+    let Some(status) = status_cache.get(&server.id).await else {
+      continue;
+    };
+    let Some(container_stats) = &status.container_stats else {
+      continue;
+    };
+    records.extend(
+      container_stats
+        .iter()
+        .map(|stats| container_stats_record(ts, &server.id, stats)),
+    );
+  }
+
+  if !records.is_empty() {
+    let res = db_client().container_stats.insert_many(records).await;
+    if let Err(e) = res {
+      error!("failed to record container stats | {e:#}");
+    }
+  }
+}
+
+fn container_stats_record(
+  ts: i64,
+  sid: &str,
+  stats: &ContainerStats,
+) -> ContainerStatsRecord {
+  let (mem_used_bytes, mem_limit_bytes) =
+    stats.mem_usage_bytes().unwrap_or_default();
+  let (net_rx_bytes, net_tx_bytes) =
+    stats.net_io_bytes().unwrap_or_default();
+  let (block_read_bytes, block_write_bytes) =
+    stats.block_io_bytes().unwrap_or_default();
+  ContainerStatsRecord {
+    ts,
+    sid: sid.to_string(),
+    name: stats.name.clone(),
+    cpu_perc: stats.cpu_perc().unwrap_or_default(),
+    mem_perc: stats.mem_perc().unwrap_or_default(),
+    mem_used_bytes,
+    mem_limit_bytes,
+    net_rx_bytes,
+    net_tx_bytes,
+    block_read_bytes,
+    block_write_bytes,
+  }
+}
@@ -22,7 +22,7 @@ use komodo_client::{
 };
 
 use crate::{
-  alert::send_alerts,
+  alert::{record_alert, send_alerts},
   api::execute::{self, ExecuteRequest},
   helpers::query::get_stack_state_from_containers,
   stack::{
@@ -30,8 +30,7 @@ use crate::{
     services::extract_services_from_stack,
   },
   state::{
-    action_states, db_client, deployment_status_cache,
-    stack_status_cache,
+    action_states, deployment_status_cache, stack_status_cache,
   },
 };
 
@@ -126,7 +125,7 @@ pub async fn update_deployment_cache(
             {
               Ok(_) => {
                 let ts = komodo_timestamp();
-                let alert = Alert {
+                let mut alert = Alert {
                   id: Default::default(),
                   ts,
                   resolved: true,
@@ -140,8 +139,9 @@ pub async fn update_deployment_cache(
                     server_id: deployment.config.server_id,
                     image,
                   },
+                  ..Default::default()
                 };
-                let res = db_client().alerts.insert_one(&alert).await;
+                let res = record_alert(&mut alert).await;
                 if let Err(e) = res {
                   error!(
                     "Failed to record DeploymentAutoUpdated to db | {e:#}"
@@ -171,7 +171,7 @@ pub async fn update_deployment_cache(
           .unwrap()
           .insert(deployment.id.clone());
         let ts = komodo_timestamp();
-        let alert = Alert {
+        let mut alert = Alert {
           id: Default::default(),
           ts,
           resolved: true,
@@ -185,8 +185,9 @@ pub async fn update_deployment_cache(
             server_id: deployment.config.server_id,
             image,
           },
+          ..Default::default()
         };
-        let res = db_client().alerts.insert_one(&alert).await;
+        let res = record_alert(&mut alert).await;
         if let Err(e) = res {
           error!(
             "Failed to record DeploymentImageUpdateAvailable to db | {e:#}"
@@ -282,7 +283,7 @@ pub async fn update_stack_cache(
             .unwrap()
             .insert((stack.id.clone(), service_name.clone()));
           let ts = komodo_timestamp();
-          let alert = Alert {
+          let mut alert = Alert {
             id: Default::default(),
             ts,
             resolved: true,
@@ -297,9 +298,10 @@ pub async fn update_stack_cache(
               service: service_name.clone(),
               image: image.clone(),
             },
+            ..Default::default()
           };
           tokio::spawn(async move {
-            let res = db_client().alerts.insert_one(&alert).await;
+            let res = record_alert(&mut alert).await;
             if let Err(e) = res {
               error!(
                 "Failed to record StackImageUpdateAvailable to db | {e:#}"
@@ -371,7 +373,7 @@ pub async fn update_stack_cache(
         {
           Ok(_) => {
             let ts = komodo_timestamp();
-            let alert = Alert {
+            let mut alert = Alert {
               id: Default::default(),
               ts,
               resolved: true,
@@ -385,8 +387,9 @@ pub async fn update_stack_cache(
                 server_id: stack.config.server_id,
                 images: images_with_update,
               },
+              ..Default::default()
             };
-            let res = db_client().alerts.insert_one(&alert).await;
+            let res = record_alert(&mut alert).await;
             if let Err(e) = res {
               error!(
                 "Failed to record StackAutoUpdated to db | {e:#}"
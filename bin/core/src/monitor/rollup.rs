@@ -0,0 +1,185 @@
+use anyhow::Context;
+use async_timing_util::{
+  get_timelength_in_ms, wait_until_timelength,
+};
+use futures::TryStreamExt;
+use komodo_client::entities::{
+  komodo_timestamp,
+  stats::{SystemStatsMax, SystemStatsRollup},
+  Timelength, I64,
+};
+use mungos::mongodb::{
+  bson::{doc, from_document, to_document},
+  options::FindOneOptions,
+};
+use serde::Deserialize;
+
+use crate::state::db_client;
+
+/// Give the monitor loop time to record the stats
+/// at the end of the period before rolling it up.
+const ADDITIONAL_MS: u128 = 10_000;
+
+/// The downsampled granularities, from finest to coarsest.
+const ROLLUP_GRANULARITIES: [Timelength; 2] =
+  [Timelength::FiveMinutes, Timelength::OneHour];
+
+/// Periodically downsamples the raw stats into rollups.
+/// Each cycle picks up from the latest stored rollup,
+/// so any periods missed while Core was down are filled in.
+pub fn spawn_stats_rollup_loop() {
+  tokio::spawn(async move {
+    loop {
+      for granularity in ROLLUP_GRANULARITIES {
+        if let Err(e) = rollup_stats(granularity).await {
+          error!("failed to roll up {granularity} stats | {e:#}");
+        }
+      }
+      wait_until_timelength(
+        async_timing_util::Timelength::FiveMinutes,
+        ADDITIONAL_MS,
+      )
+      .await;
+    }
+  });
+}
+
+async fn rollup_stats(granularity: Timelength) -> anyhow::Result<()> {
+  let granularity_ms =
+    get_timelength_in_ms(granularity.try_into()?) as i64;
+  let db = db_client();
+
+  let latest = db
+    .stats_rollups
+    .find_one(doc! { "granularity": granularity.to_string() })
+    .with_options(
+      FindOneOptions::builder().sort(doc! { "ts": -1 }).build(),
+    )
+    .await
+    .context("failed to query db for latest stats rollup")?;
+
+  let start = match latest {
+    Some(latest) => latest.ts + granularity_ms,
+    None => {
+      let Some(earliest) = db
+        .stats
+        .find_one(doc! {})
+        .with_options(
+          FindOneOptions::builder().sort(doc! { "ts": 1 }).build(),
+        )
+        .await
+        .context("failed to query db for earliest stats")?
+      else {
+        return Ok(());
+      };
+      earliest.ts - earliest.ts % granularity_ms
+    }
+  };
+
+  // Only roll up completed periods
+  let now = komodo_timestamp();
+  let end = now - now % granularity_ms;
+  if start >= end {
+    return Ok(());
+  }
+
+  let groups = db
+    .stats
+    .aggregate([
+      doc! { "$match": { "ts": { "$gte": start, "$lt": end } } },
+      doc! { "$group": {
+        "_id": {
+          "sid": "$sid",
+          "ts": { "$subtract": ["$ts", { "$mod": ["$ts", granularity_ms] }] },
+        },
+        "samples": { "$sum": 1 },
+        "cpu_perc": { "$avg": "$cpu_perc" },
+        "mem_used_gb": { "$avg": "$mem_used_gb" },
+        "mem_total_gb": { "$max": "$mem_total_gb" },
+        "disk_used_gb": { "$avg": "$disk_used_gb" },
+        "disk_total_gb": { "$max": "$disk_total_gb" },
+        "network_ingress_bytes": { "$avg": { "$ifNull": ["$network_ingress_bytes", 0] } },
+        "network_egress_bytes": { "$avg": { "$ifNull": ["$network_egress_bytes", 0] } },
+        "max_cpu_perc": { "$max": "$cpu_perc" },
+        "max_mem_used_gb": { "$max": "$mem_used_gb" },
+        "max_disk_used_gb": { "$max": "$disk_used_gb" },
+        "max_network_ingress_bytes": { "$max": { "$ifNull": ["$network_ingress_bytes", 0] } },
+        "max_network_egress_bytes": { "$max": { "$ifNull": ["$network_egress_bytes", 0] } },
+      } },
+    ])
+    .await
+    .context("failed to aggregate stats rollups")?
+    .try_collect::<Vec<_>>()
+    .await
+    .context("failed to collect stats rollups")?;
+
+  for group in groups {
+    let group = from_document::<StatsGroup>(group)
+      .context("failed to parse stats rollup")?;
+    let rollup = group.into_rollup(granularity);
+    db.stats_rollups
+      .update_one(
+        doc! {
+          "sid": &rollup.sid,
+          "granularity": granularity.to_string(),
+          "ts": rollup.ts,
+        },
+        doc! { "$set": to_document(&rollup)? },
+      )
+      .upsert(true)
+      .await
+      .context("failed to write stats rollup to db")?;
+  }
+
+  Ok(())
+}
+
+#[derive(Deserialize)]
+struct StatsGroup {
+  #[serde(rename = "_id")]
+  id: StatsGroupId,
+  samples: I64,
+  cpu_perc: f32,
+  mem_used_gb: f64,
+  mem_total_gb: f64,
+  disk_used_gb: f64,
+  disk_total_gb: f64,
+  network_ingress_bytes: f64,
+  network_egress_bytes: f64,
+  max_cpu_perc: f32,
+  max_mem_used_gb: f64,
+  max_disk_used_gb: f64,
+  max_network_ingress_bytes: f64,
+  max_network_egress_bytes: f64,
+}
+
+#[derive(Deserialize)]
+struct StatsGroupId {
+  sid: String,
+  ts: I64,
+}
+
+impl StatsGroup {
+  fn into_rollup(self, granularity: Timelength) -> SystemStatsRollup {
+    SystemStatsRollup {
+      ts: self.id.ts,
+      sid: self.id.sid,
+      granularity,
+      samples: self.samples,
+      cpu_perc: self.cpu_perc,
+      mem_used_gb: self.mem_used_gb,
+      mem_total_gb: self.mem_total_gb,
+      disk_used_gb: self.disk_used_gb,
+      disk_total_gb: self.disk_total_gb,
+      network_ingress_bytes: self.network_ingress_bytes,
+      network_egress_bytes: self.network_egress_bytes,
+      max: SystemStatsMax {
+        cpu_perc: self.max_cpu_perc,
+        mem_used_gb: self.max_mem_used_gb,
+        disk_used_gb: self.max_disk_used_gb,
+        network_ingress_bytes: self.max_network_ingress_bytes,
+        network_egress_bytes: self.max_network_egress_bytes,
+      },
+    }
+  }
+}
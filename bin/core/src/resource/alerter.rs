@@ -1,8 +1,10 @@
+use anyhow::anyhow;
 use derive_variants::ExtractVariant;
 use komodo_client::entities::{
   alerter::{
-    Alerter, AlerterConfig, AlerterConfigDiff, AlerterListItem,
-    AlerterListItemInfo, AlerterQuerySpecifics, PartialAlerterConfig,
+    Alerter, AlerterConfig, AlerterConfigDiff, AlerterEndpoint,
+    AlerterListItem, AlerterListItemInfo, AlerterQuerySpecifics,
+    PartialAlerterConfig,
   },
   resource::Resource,
   update::Update,
@@ -60,10 +62,10 @@ impl super::KomodoResource for Alerter {
   }
 
   async fn validate_create_config(
-    _config: &mut Self::PartialConfig,
+    config: &mut Self::PartialConfig,
     _user: &User,
   ) -> anyhow::Result<()> {
-    Ok(())
+    validate_config(config)
   }
 
   async fn post_create(
@@ -81,10 +83,10 @@ impl super::KomodoResource for Alerter {
 
   async fn validate_update_config(
     _id: &str,
-    _config: &mut Self::PartialConfig,
+    config: &mut Self::PartialConfig,
     _user: &User,
   ) -> anyhow::Result<()> {
-    Ok(())
+    validate_config(config)
   }
 
   async fn post_update(
@@ -120,3 +122,16 @@ impl super::KomodoResource for Alerter {
     Ok(())
   }
 }
+
+/// Public ntfy topics can be read by anyone who knows them,
+/// so there is no default and the topic url must be set.
+fn validate_config(
+  config: &PartialAlerterConfig,
+) -> anyhow::Result<()> {
+  if let Some(AlerterEndpoint::Ntfy(endpoint)) = &config.endpoint {
+    if endpoint.url.trim().is_empty() {
+      return Err(anyhow!("Ntfy alerter must have a topic url"));
+    }
+  }
+  Ok(())
+}
@@ -98,6 +98,10 @@ impl super::KomodoResource for Deployment {
         status: status.as_ref().and_then(|s| {
           s.curr.container.as_ref().and_then(|c| c.status.to_owned())
         }),
+        health: status
+          .as_ref()
+          .and_then(|s| s.curr.container.as_ref().map(|c| c.health))
+          .unwrap_or_default(),
         image,
         update_available,
         server_id: deployment.config.server_id,
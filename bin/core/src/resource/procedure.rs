@@ -30,6 +30,11 @@ use mungos::{
 
 use crate::{
   config::core_config,
+  helpers::{
+    arguments::check_no_target_arguments,
+    procedure::check_execution_policies,
+  },
+  schedule::{cancel_schedule, schedule_cache, update_schedule},
   state::{action_states, db_client, procedure_state_cache},
 };
 
@@ -53,7 +58,14 @@ impl super::KomodoResource for Procedure {
   async fn to_list_item(
     procedure: Resource<Self::Config, Self::Info>,
   ) -> Self::ListItem {
-    let state = get_procedure_state(&procedure.id).await;
+    let (state, last_run_at, schedule) = tokio::join!(
+      get_procedure_state(&procedure.id),
+      get_procedure_last_run_at(&procedure.id),
+      schedule_cache().get(&procedure.id),
+    );
+    let (next_scheduled_run, schedule_error) = schedule
+      .map(|schedule| (schedule.next_run, schedule.error))
+      .unwrap_or_default();
     ProcedureListItem {
       name: procedure.name,
       id: procedure.id,
@@ -62,6 +74,9 @@ impl super::KomodoResource for Procedure {
       info: ProcedureListItemInfo {
         stages: procedure.config.stages.len() as i64,
         state,
+        last_run_at,
+        next_scheduled_run,
+        schedule_error,
       },
     }
   }
@@ -93,9 +108,10 @@ impl super::KomodoResource for Procedure {
   }
 
   async fn post_create(
-    _created: &Resource<Self::Config, Self::Info>,
+    created: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    update_schedule(created).await;
     refresh_procedure_state_cache().await;
     Ok(())
   }
@@ -141,9 +157,20 @@ impl super::KomodoResource for Procedure {
   }
 
   async fn post_delete(
-    _resource: &Resource<Self::Config, Self::Info>,
+    resource: &Resource<Self::Config, Self::Info>,
     _update: &mut Update,
   ) -> anyhow::Result<()> {
+    cancel_schedule(&resource.id).await;
+    if let Err(e) = db_client()
+      .procedure_runs
+      .delete_many(doc! { "procedure_id": &resource.id })
+      .await
+    {
+      warn!(
+        "failed to delete runs of procedure {} | {e:#}",
+        resource.name
+      );
+    }
     Ok(())
   }
 }
@@ -157,6 +184,8 @@ async fn validate_config(
   let Some(stages) = &mut config.stages else {
     return Ok(());
   };
+  check_no_target_arguments(stages)?;
+  check_execution_policies(stages)?;
   for stage in stages {
     for exec in &mut stage.executions {
       match &mut exec.execution {
@@ -739,6 +768,28 @@ async fn get_procedure_state(id: &String) -> ProcedureState {
   procedure_state_cache().get(id).await.unwrap_or_default()
 }
 
+async fn get_procedure_last_run_at(id: &str) -> Option<i64> {
+  db_client()
+    .updates
+    .find_one(doc! {
+      "target.type": "Procedure",
+      "target.id": id,
+      "operation": "RunProcedure"
+    })
+    .with_options(
+      FindOneOptions::builder()
+        .sort(doc! { "start_ts": -1 })
+        .build(),
+    )
+    .await
+    .inspect_err(|e| {
+      warn!("Failed to get Procedure last run for {id} | {e:#}")
+    })
+    .ok()
+    .flatten()
+    .map(|update| update.start_ts)
+}
+
 async fn get_procedure_state_from_db(id: &str) -> ProcedureState {
   async {
     let state = db_client()
@@ -66,6 +66,11 @@ impl super::KomodoResource for Stack {
             service: service.service.clone(),
             image: service.image.clone(),
             update_available: service.update_available,
+            health: service
+              .container
+              .as_ref()
+              .map(|c| c.health)
+              .unwrap_or_default(),
           })
           .collect::<Vec<_>>()
       })
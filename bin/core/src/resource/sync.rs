@@ -238,6 +238,7 @@ async fn get_resource_sync_state(
   if !data.resource_updates.is_empty()
     || !data.variable_updates.is_empty()
     || !data.user_group_updates.is_empty()
+    || !data.maintenance_window_updates.is_empty()
     || data.pending_deploy.to_deploy > 0
   {
     return ResourceSyncState::Pending;
@@ -0,0 +1,235 @@
+use std::{str::FromStr, sync::OnceLock, time::Duration};
+
+use anyhow::{anyhow, Context};
+use async_timing_util::{get_timelength_in_ms, Timelength};
+use chrono::{DateTime, Local, TimeZone};
+use formatting::{bold, format_serror, muted};
+use komodo_client::{
+  api::execute::RunProcedure,
+  entities::{
+    komodo_timestamp,
+    procedure::{Procedure, ScheduleFormat},
+    user::schedule_user,
+  },
+};
+use mungos::find::find_collect;
+use resolver_api::Resolve;
+
+use crate::{
+  api::execute::ExecuteRequest,
+  helpers::{
+    cache::Cache,
+    update::{init_execution_update, update_update},
+  },
+  state::{db_client, State},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleInfo {
+  /// The procedure id
+  pub id: String,
+  /// Unix ms of the next scheduled run
+  pub next_run: Option<i64>,
+  /// Error computing the next run from the schedule
+  pub error: Option<String>,
+}
+
+pub type ScheduleCache = Cache<String, ScheduleInfo>;
+
+pub fn schedule_cache() -> &'static ScheduleCache {
+  static SCHEDULE_CACHE: OnceLock<ScheduleCache> = OnceLock::new();
+  SCHEDULE_CACHE.get_or_init(Default::default)
+}
+
+/// How long to wait before retrying a scheduled run
+/// which failed before computing its next run.
+const SCHEDULE_RETRY_MS: i64 = 60_000;
+
+pub fn spawn_schedule_executor() {
+  tokio::spawn(async move {
+    if let Err(e) = init_schedules().await {
+      error!("Failed to initialize procedure schedules | {e:#}");
+    }
+    loop {
+      // Wake up at the start of each second
+      let now = komodo_timestamp();
+      let wait = 1000 - now.rem_euclid(1000);
+      tokio::time::sleep(Duration::from_millis(wait as u64)).await;
+      let now = komodo_timestamp();
+      for info in schedule_cache().get_list().await {
+        let Some(next_run) = info.next_run else {
+          continue;
+        };
+        if next_run > now {
+          continue;
+        }
+        // Push back the next run so it isn't triggered again
+        // before the run recomputes it. If the run fails before
+        // recomputing (eg. db error), it is retried after this.
+        schedule_cache()
+          .update_entry(info.id.clone(), |info| {
+            info.next_run = Some(now + SCHEDULE_RETRY_MS)
+          })
+          .await;
+        tokio::spawn(async move {
+          if let Err(e) = run_scheduled_procedure(&info.id).await {
+            warn!(
+              "Failed to run scheduled procedure {} | {e:#}",
+              info.id
+            );
+          }
+        });
+      }
+    }
+  });
+}
+
+async fn init_schedules() -> anyhow::Result<()> {
+  let procedures = find_collect(&db_client().procedures, None, None)
+    .await
+    .context("Failed to get Procedures from db")?;
+  for procedure in &procedures {
+    update_schedule(procedure).await;
+  }
+  Ok(())
+}
+
+/// Recompute the next run for the procedure.
+/// Call after the procedure is created / updated.
+pub async fn update_schedule(procedure: &Procedure) {
+  let config = &procedure.config;
+  if !config.schedule_enabled || config.schedule.trim().is_empty() {
+    schedule_cache().remove(&procedure.id).await;
+    return;
+  }
+  let (next_run, error) = match find_next_run(procedure) {
+    Ok(next_run) => (Some(next_run), None),
+    Err(e) => (None, Some(format!("{e:#}"))),
+  };
+  schedule_cache()
+    .insert(
+      procedure.id.clone(),
+      ScheduleInfo {
+        id: procedure.id.clone(),
+        next_run,
+        error,
+      },
+    )
+    .await;
+}
+
+/// Remove any schedule for the procedure.
+/// Call after the procedure is deleted.
+pub async fn cancel_schedule(id: &String) {
+  schedule_cache().remove(id).await;
+}
+
+/// Finds the next run after the current time in unix ms.
+pub fn find_next_run(procedure: &Procedure) -> anyhow::Result<i64> {
+  let config = &procedure.config;
+  let schedule = config.schedule.trim();
+  match config.schedule_format {
+    ScheduleFormat::Interval => {
+      let timelength = Timelength::from_str(schedule)
+        .with_context(|| {
+          format!("Invalid schedule interval '{schedule}'. Use one of eg. '15-min', '1-hr', '1-day'.")
+        })?;
+      let interval = get_timelength_in_ms(timelength) as i64;
+      let now = komodo_timestamp();
+      Ok((now / interval + 1) * interval)
+    }
+    ScheduleFormat::Cron => find_next_cron_occurrence(
+      schedule,
+      &config.schedule_timezone,
+      komodo_timestamp(),
+      false,
+    ),
+  }
+}
+
+/// Finds the next occurrence of the cron schedule after
+/// the given unix ms timestamp, in unix ms.
+/// The timezone is an IANA timezone, or empty for local time.
+pub fn find_next_cron_occurrence(
+  schedule: &str,
+  timezone: &str,
+  after: i64,
+  inclusive: bool,
+) -> anyhow::Result<i64> {
+  let cron = croner::Cron::new(schedule)
+    .with_seconds_optional()
+    .parse()
+    .with_context(|| format!("Invalid cron schedule '{schedule}'"))?;
+  let after = DateTime::from_timestamp_millis(after)
+    .context("Invalid timestamp")?;
+  let timezone = timezone.trim();
+  if timezone.is_empty() {
+    next_cron_occurrence(
+      &cron,
+      after.with_timezone(&Local),
+      inclusive,
+    )
+  } else {
+    let tz = chrono_tz::Tz::from_str(timezone).map_err(|e| {
+      anyhow!("Invalid schedule timezone '{timezone}' | {e}")
+    })?;
+    next_cron_occurrence(&cron, after.with_timezone(&tz), inclusive)
+  }
+}
+
+fn next_cron_occurrence<Tz: TimeZone>(
+  cron: &croner::Cron,
+  after: DateTime<Tz>,
+  inclusive: bool,
+) -> anyhow::Result<i64> {
+  cron
+    .find_next_occurrence(&after, inclusive)
+    .map(|next| next.timestamp_millis())
+    .context("Failed to find next occurrence for cron schedule")
+}
+
+async fn run_scheduled_procedure(id: &str) -> anyhow::Result<()> {
+  let procedure = crate::resource::get::<Procedure>(id).await?;
+
+  // Compute the next run before execution,
+  // so the schedule continues while this run is in progress.
+  update_schedule(&procedure).await;
+
+  let user = schedule_user().to_owned();
+  let req = ExecuteRequest::RunProcedure(RunProcedure {
+    procedure: procedure.id.clone(),
+    args: None,
+  });
+  let mut update = init_execution_update(&req, &user).await?;
+
+  // Record the schedule trigger on the first log,
+  // RunProcedure will add the procedure logs after this.
+  let timezone = if procedure.config.schedule_timezone.is_empty() {
+    String::new()
+  } else {
+    format!(" ({})", procedure.config.schedule_timezone)
+  };
+  update.push_simple_log(
+    "Execute procedure",
+    format!(
+      "{}: executing procedure '{}'\n{}: triggered by {} schedule '{}'{timezone}",
+      muted("INFO"),
+      bold(&procedure.name),
+      muted("INFO"),
+      procedure.config.schedule_format,
+      bold(&procedure.config.schedule),
+    ),
+  );
+
+  let ExecuteRequest::RunProcedure(req) = req else {
+    unreachable!()
+  };
+  // If the call to .resolve returns Err, the update may not be closed.
+  if let Err(e) = State.resolve(req, (user, update.clone())).await {
+    update
+      .push_error_log("execution error", format_serror(&e.into()));
+    update.finalize();
+    update_update(update).await?;
+  }
+  Ok(())
+}
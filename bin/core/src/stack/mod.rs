@@ -49,3 +49,25 @@ pub fn compose_container_match_regex(
     format!("failed to construct valid regex from {regex}")
   })
 }
+
+/// Whether the latest compose file contents differ from
+/// the deployed contents. Also true if the stack was never deployed.
+pub fn compose_contents_changed(stack: &Stack) -> bool {
+  match (&stack.info.deployed_contents, &stack.info.remote_contents) {
+    (Some(deployed_contents), Some(latest_contents)) => {
+      for latest in latest_contents {
+        let Some(deployed) =
+          deployed_contents.iter().find(|c| c.path == latest.path)
+        else {
+          return true;
+        };
+        if latest.contents != deployed.contents {
+          return true;
+        }
+      }
+      false
+    }
+    (None, _) => true,
+    _ => false,
+  }
+}
@@ -279,6 +279,9 @@ pub fn extend_resources(
     .extend(filter_by_tag(more.resource_syncs, match_tags));
   resources.user_groups.extend(more.user_groups);
   resources.variables.extend(more.variables);
+  resources
+    .maintenance_windows
+    .extend(more.maintenance_windows);
 }
 
 fn filter_by_tag<T: Default>(
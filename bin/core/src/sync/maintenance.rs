@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use formatting::{bold, colored, muted, Color};
+use komodo_client::{
+  api::write::{
+    CreateMaintenanceWindow, DeleteMaintenanceWindow,
+    UpdateMaintenanceWindow,
+  },
+  entities::{
+    maintenance::MaintenanceWindow, sync::DiffData, tag::Tag,
+    update::Log, user::sync_user,
+  },
+};
+use mungos::find::find_collect;
+use resolver_api::Resolve;
+
+use crate::state::{db_client, State};
+
+use super::{toml::TOML_PRETTY_OPTIONS, AllResourcesById};
+
+pub async fn get_updates_for_view(
+  windows: &[MaintenanceWindow],
+  delete: bool,
+  all_resources: &AllResourcesById,
+  id_to_tags: &HashMap<String, Tag>,
+) -> anyhow::Result<Vec<DiffData>> {
+  let map =
+    get_current_maintenance_windows(all_resources, id_to_tags)
+      .await?;
+
+  let mut diffs = Vec::<DiffData>::new();
+
+  if delete {
+    for window in map.values() {
+      if !windows.iter().any(|w| w.name == window.name) {
+        diffs.push(DiffData::Delete {
+          current: window_toml(window)?,
+        });
+      }
+    }
+  }
+
+  for window in windows {
+    match map.get(&window.name) {
+      Some(original) => {
+        if original == window {
+          continue;
+        }
+        diffs.push(DiffData::Update {
+          proposed: window_toml(window)?,
+          current: window_toml(original)?,
+        });
+      }
+      None => {
+        diffs.push(DiffData::Create {
+          name: window.name.clone(),
+          proposed: window_toml(window)?,
+        });
+      }
+    }
+  }
+
+  Ok(diffs)
+}
+
+pub async fn get_updates_for_execution(
+  windows: Vec<MaintenanceWindow>,
+  delete: bool,
+  all_resources: &AllResourcesById,
+  id_to_tags: &HashMap<String, Tag>,
+) -> anyhow::Result<(
+  Vec<MaintenanceWindow>,
+  Vec<MaintenanceWindow>,
+  Vec<String>,
+)> {
+  let map =
+    get_current_maintenance_windows(all_resources, id_to_tags)
+      .await?;
+
+  let mut to_create = Vec::<MaintenanceWindow>::new();
+  let mut to_update = Vec::<MaintenanceWindow>::new();
+  let mut to_delete = Vec::<String>::new();
+
+  if delete {
+    for window in map.values() {
+      if !windows.iter().any(|w| w.name == window.name) {
+        to_delete.push(window.name.clone());
+      }
+    }
+  }
+
+  for window in windows {
+    match map.get(&window.name) {
+      Some(original) => {
+        if original == &window {
+          continue;
+        }
+        to_update.push(window);
+      }
+      None => to_create.push(window),
+    }
+  }
+
+  Ok((to_create, to_update, to_delete))
+}
+
+pub async fn run_updates(
+  to_create: Vec<MaintenanceWindow>,
+  to_update: Vec<MaintenanceWindow>,
+  to_delete: Vec<String>,
+) -> Option<Log> {
+  if to_create.is_empty()
+    && to_update.is_empty()
+    && to_delete.is_empty()
+  {
+    return None;
+  }
+
+  let mut has_error = false;
+  let mut log =
+    String::from("running updates on Maintenance Windows");
+
+  for window in to_create {
+    let name = window.name.clone();
+    if let Err(e) = State
+      .resolve(
+        CreateMaintenanceWindow { window },
+        sync_user().to_owned(),
+      )
+      .await
+    {
+      has_error = true;
+      log.push_str(&format!(
+        "\n{}: failed to create maintenance window '{}' | {e:#}",
+        colored("ERROR", Color::Red),
+        bold(&name)
+      ));
+    } else {
+      log.push_str(&format!(
+        "\n{}: {} maintenance window '{}'",
+        muted("INFO"),
+        colored("created", Color::Green),
+        bold(&name)
+      ))
+    };
+  }
+
+  for window in to_update {
+    let name = window.name.clone();
+    if let Err(e) = State
+      .resolve(
+        UpdateMaintenanceWindow { window },
+        sync_user().to_owned(),
+      )
+      .await
+    {
+      has_error = true;
+      log.push_str(&format!(
+        "\n{}: failed to update maintenance window '{}' | {e:#}",
+        colored("ERROR", Color::Red),
+        bold(&name)
+      ));
+    } else {
+      log.push_str(&format!(
+        "\n{}: {} maintenance window '{}'",
+        muted("INFO"),
+        colored("updated", Color::Blue),
+        bold(&name)
+      ))
+    };
+  }
+
+  for name in to_delete {
+    if let Err(e) = State
+      .resolve(
+        DeleteMaintenanceWindow { name: name.clone() },
+        sync_user().to_owned(),
+      )
+      .await
+    {
+      has_error = true;
+      log.push_str(&format!(
+        "\n{}: failed to delete maintenance window '{}' | {e:#}",
+        colored("ERROR", Color::Red),
+        bold(&name)
+      ))
+    } else {
+      log.push_str(&format!(
+        "\n{}: {} maintenance window '{}'",
+        muted("INFO"),
+        colored("deleted", Color::Red),
+        bold(&name)
+      ))
+    }
+  }
+
+  let stage = "Update Maintenance Windows";
+  Some(if has_error {
+    Log::error(stage, log)
+  } else {
+    Log::simple(stage, log)
+  })
+}
+
+/// Gets the maintenance windows on the db by name,
+/// with the targets and tags referenced by name.
+async fn get_current_maintenance_windows(
+  all_resources: &AllResourcesById,
+  id_to_tags: &HashMap<String, Tag>,
+) -> anyhow::Result<HashMap<String, MaintenanceWindow>> {
+  let windows =
+    find_collect(&db_client().maintenance_windows, None, None)
+      .await
+      .context("failed to query db for maintenance windows")?
+      .into_iter()
+      .map(|window| {
+        let window = convert_maintenance_window(
+          window,
+          all_resources,
+          id_to_tags,
+        );
+        (window.name.clone(), window)
+      })
+      .collect();
+  Ok(windows)
+}
+
+/// Replaces the target and tag ids with their names,
+/// for a stable toml representation.
+pub fn convert_maintenance_window(
+  mut window: MaintenanceWindow,
+  all_resources: &AllResourcesById,
+  id_to_tags: &HashMap<String, Tag>,
+) -> MaintenanceWindow {
+  for target in &mut window.targets {
+    all_resources.replace_id_with_name(target);
+  }
+  for tag in &mut window.tags {
+    if let Some(t) = id_to_tags.get(tag) {
+      *tag = t.name.clone();
+    }
+  }
+  window
+}
+
+fn window_toml(window: &MaintenanceWindow) -> anyhow::Result<String> {
+  Ok(format!(
+    "[[maintenance_window]]\n{}",
+    toml_pretty::to_string(window, TOML_PRETTY_OPTIONS)
+      .context("failed to serialize maintenance window to toml")?
+  ))
+}
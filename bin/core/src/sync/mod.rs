@@ -15,6 +15,7 @@ use crate::resource::KomodoResource;
 pub mod deploy;
 pub mod execute;
 pub mod file;
+pub mod maintenance;
 pub mod remote;
 pub mod resources;
 pub mod toml;
@@ -208,4 +209,59 @@ impl AllResourcesById {
       .await?,
     })
   }
+
+  /// Replaces the target id with the resource name,
+  /// if the resource exists. Targets referenced by name are left as is.
+  pub fn replace_id_with_name(&self, target: &mut ResourceTarget) {
+    let (id, name) = match target {
+      ResourceTarget::System(_) => return,
+      ResourceTarget::Server(id) => {
+        let name = self.servers.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Stack(id) => {
+        let name = self.stacks.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Deployment(id) => {
+        let name = self.deployments.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Build(id) => {
+        let name = self.builds.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Repo(id) => {
+        let name = self.repos.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Procedure(id) => {
+        let name = self.procedures.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Action(id) => {
+        let name = self.actions.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Builder(id) => {
+        let name = self.builders.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::Alerter(id) => {
+        let name = self.alerters.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::ServerTemplate(id) => {
+        let name = self.templates.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+      ResourceTarget::ResourceSync(id) => {
+        let name = self.syncs.get(id.as_str()).map(|r| &r.name);
+        (id, name)
+      }
+    };
+    if let Some(name) = name {
+      *id = name.clone();
+    }
+  }
 }
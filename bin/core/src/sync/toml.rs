@@ -811,6 +811,7 @@ impl ToToml for Procedure {
       .as_object_mut()
       .context("config is not object?")?;
 
+    let parameters = config.remove("parameters");
     let stages = config.remove("stages");
 
     toml.push_str(
@@ -818,6 +819,18 @@ impl ToToml for Procedure {
         .context("failed to serialize procedures to toml")?,
     );
 
+    if let Some(parameters) = parameters {
+      let parameters =
+        parameters.as_array().context("parameters is not array")?;
+      for parameter in parameters {
+        toml.push_str("\n\n[[procedure.config.parameter]]\n");
+        toml.push_str(
+          &toml_pretty::to_string(parameter, TOML_PRETTY_OPTIONS)
+            .context("failed to serialize procedures to toml")?,
+        );
+      }
+    }
+
     if let Some(stages) = stages {
       let stages =
         stages.as_array().context("stages is not array")?;
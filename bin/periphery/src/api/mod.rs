@@ -221,8 +221,11 @@ impl Resolve<GetDockerLists> for State {
     _: (),
   ) -> anyhow::Result<GetDockerListsResponse> {
     let docker = docker_client();
-    let containers =
+    let mut containers =
       docker.list_containers().await.map_err(Into::into);
+    if let Ok(containers) = &mut containers {
+      docker.add_restart_counts(containers).await;
+    }
     // Should still try to retrieve other docker lists, but "in_use" will be false for images, networks, volumes
     let _containers = match &containers {
       Ok(containers) => containers.as_slice(),
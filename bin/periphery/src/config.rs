@@ -73,6 +73,12 @@ pub fn periphery_config() -> &'static PeripheryConfig {
       exclude_disk_mounts: env
         .periphery_exclude_disk_mounts
         .unwrap_or(config.exclude_disk_mounts),
+      metrics_enabled: env
+        .periphery_metrics_enabled
+        .unwrap_or(config.metrics_enabled),
+      metrics_port: env
+        .periphery_metrics_port
+        .or(config.metrics_port),
       ssl_enabled: env
         .periphery_ssl_enabled
         .unwrap_or(config.ssl_enabled),
@@ -7,6 +7,7 @@ use bollard::{
   Docker,
 };
 use command::run_komodo_command;
+use futures::future::join_all;
 use komodo_client::entities::{
   docker::{
     container::*, image::*, network::*, volume::*, ContainerConfig,
@@ -68,6 +69,8 @@ impl DockerClient {
             .context("no container state")?
             .parse()
             .context("failed to parse container state")?,
+          health: container_health(container.status.as_deref()),
+          restart_count: None,
           status: container.status,
           network_mode: container
             .host_config
@@ -117,6 +120,29 @@ impl DockerClient {
     Ok(containers)
   }
 
+  /// The restart count is only available on container inspect,
+  /// so it is only added for the monitoring lists (GetDockerLists).
+  pub async fn add_restart_counts(
+    &self,
+    containers: &mut [ContainerListItem],
+  ) {
+    let restart_counts =
+      join_all(containers.iter().map(|container| async {
+        self
+          .docker
+          .inspect_container(&container.name, None)
+          .await
+          .ok()
+          .and_then(|container| container.restart_count)
+      }))
+      .await;
+    containers.iter_mut().zip(restart_counts).for_each(
+      |(container, restart_count)| {
+        container.restart_count = restart_count;
+      },
+    );
+  }
+
   pub async fn inspect_container(
     &self,
     container_name: &str,
@@ -972,6 +998,23 @@ pub async fn pull_image(image: &str) -> Log {
   run_komodo_command("docker pull", None, command, false).await
 }
 
+/// The container list doesn't include health, but docker appends it
+/// to the human readable status, eg. `Up 5 minutes (unhealthy)`.
+fn container_health(status: Option<&str>) -> HealthStatusEnum {
+  let Some(status) = status else {
+    return HealthStatusEnum::Empty;
+  };
+  if status.ends_with("(unhealthy)") {
+    HealthStatusEnum::Unhealthy
+  } else if status.ends_with("(healthy)") {
+    HealthStatusEnum::Healthy
+  } else if status.ends_with("(health: starting)") {
+    HealthStatusEnum::Starting
+  } else {
+    HealthStatusEnum::None
+  }
+}
+
 pub fn stop_container_command(
   container_name: &str,
   signal: Option<TerminationSignal>,
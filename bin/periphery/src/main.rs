@@ -12,6 +12,7 @@ mod compose;
 mod config;
 mod docker;
 mod helpers;
+mod metrics;
 mod router;
 mod ssl;
 mod stats;
@@ -28,20 +29,48 @@ async fn app() -> anyhow::Result<()> {
 
   stats::spawn_system_stats_polling_threads();
 
-  let socket_addr =
-    SocketAddr::from_str(&format!("0.0.0.0:{}", config.port))
-      .context("failed to parse socket addr")?;
-
-  let app = router::router()
-    .into_make_service_with_connect_info::<SocketAddr>();
-
   if config.ssl_enabled {
     info!("🔒 Periphery SSL Enabled");
     rustls::crypto::ring::default_provider()
       .install_default()
       .expect("failed to install default rustls CryptoProvider");
     ssl::ensure_certs().await;
-    info!("Komodo Periphery starting on https://{}", socket_addr);
+  } else {
+    info!("🔓 Periphery SSL Disabled");
+  }
+
+  if config.metrics_enabled {
+    if let Some(port) = config.metrics_port {
+      tokio::spawn(async move {
+        if let Err(e) =
+          serve(router::metrics_router(), port, "metrics").await
+        {
+          error!("Komodo Periphery metrics server failed | {e:#}");
+        }
+      });
+    }
+  }
+
+  serve(router::router(), config.port, "api").await
+}
+
+async fn serve(
+  router: axum::Router,
+  port: u16,
+  name: &str,
+) -> anyhow::Result<()> {
+  let config = config::periphery_config();
+
+  let socket_addr = SocketAddr::from_str(&format!("0.0.0.0:{port}"))
+    .context("failed to parse socket addr")?;
+
+  let app =
+    router.into_make_service_with_connect_info::<SocketAddr>();
+
+  if config.ssl_enabled {
+    info!(
+      "Komodo Periphery {name} starting on https://{socket_addr}"
+    );
     let ssl_config = RustlsConfig::from_pem_file(
       &config.ssl_cert_file,
       &config.ssl_key_file,
@@ -52,8 +81,7 @@ async fn app() -> anyhow::Result<()> {
       .serve(app)
       .await?
   } else {
-    info!("🔓 Periphery SSL Disabled");
-    info!("Komodo Periphery starting on http://{}", socket_addr);
+    info!("Komodo Periphery {name} starting on http://{socket_addr}");
     axum_server::bind(socket_addr).serve(app).await?
   }
 
@@ -0,0 +1,168 @@
+use axum::{
+  http::header::CONTENT_TYPE, response::IntoResponse, routing::get,
+  Router,
+};
+use komodo_client::entities::docker::container::ContainerStats;
+use metrics::Metrics;
+
+use crate::{docker::container_stats, stats::stats_client};
+
+pub fn router() -> Router {
+  Router::new().route("/", get(handler))
+}
+
+async fn handler() -> impl IntoResponse {
+  (
+    [(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+    collect_metrics().await,
+  )
+}
+
+async fn collect_metrics() -> String {
+  let mut metrics = Metrics::default();
+
+  // SYSTEM
+
+  {
+    let stats = &stats_client().read().await.stats;
+
+    metrics.header(
+      "periphery_cpu_percent",
+      "System cpu usage percentage.",
+      "gauge",
+    );
+    metrics.sample(
+      "periphery_cpu_percent",
+      &[],
+      stats.cpu_perc as f64,
+    );
+
+    metrics.header(
+      "periphery_mem_used_gb",
+      "System used memory in GB.",
+      "gauge",
+    );
+    metrics.sample("periphery_mem_used_gb", &[], stats.mem_used_gb);
+
+    metrics.header(
+      "periphery_mem_free_gb",
+      "System free memory in GB.",
+      "gauge",
+    );
+    metrics.sample("periphery_mem_free_gb", &[], stats.mem_free_gb);
+
+    metrics.header(
+      "periphery_mem_total_gb",
+      "System total memory in GB.",
+      "gauge",
+    );
+    metrics.sample("periphery_mem_total_gb", &[], stats.mem_total_gb);
+
+    metrics.header(
+      "periphery_disk_used_gb",
+      "Disk used in GB, by mount point.",
+      "gauge",
+    );
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      metrics.sample(
+        "periphery_disk_used_gb",
+        &[("mount", &mount), ("file_system", &disk.file_system)],
+        disk.used_gb,
+      );
+    }
+
+    metrics.header(
+      "periphery_disk_total_gb",
+      "Disk size in GB, by mount point.",
+      "gauge",
+    );
+    for disk in &stats.disks {
+      let mount = disk.mount.display().to_string();
+      metrics.sample(
+        "periphery_disk_total_gb",
+        &[("mount", &mount), ("file_system", &disk.file_system)],
+        disk.total_gb,
+      );
+    }
+
+    metrics.header(
+      "periphery_network_ingress_bytes",
+      "Network ingress in bytes over the polling interval, by interface.",
+      "gauge",
+    );
+    for interface in &stats.network_usage_interface {
+      metrics.sample(
+        "periphery_network_ingress_bytes",
+        &[("interface", &interface.name)],
+        interface.ingress_bytes,
+      );
+    }
+
+    metrics.header(
+      "periphery_network_egress_bytes",
+      "Network egress in bytes over the polling interval, by interface.",
+      "gauge",
+    );
+    for interface in &stats.network_usage_interface {
+      metrics.sample(
+        "periphery_network_egress_bytes",
+        &[("interface", &interface.name)],
+        interface.egress_bytes,
+      );
+    }
+  }
+
+  // CONTAINERS
+
+  let containers = container_stats(None)
+    .await
+    .inspect_err(|e| warn!("failed to get container stats | {e:#}"))
+    .unwrap_or_default();
+
+  container_gauge(
+    &mut metrics,
+    &containers,
+    "periphery_container_cpu_percent",
+    "Container cpu usage percentage.",
+    ContainerStats::cpu_perc,
+  );
+  container_gauge(
+    &mut metrics,
+    &containers,
+    "periphery_container_mem_percent",
+    "Container memory usage percentage of its limit.",
+    ContainerStats::mem_perc,
+  );
+  container_gauge(
+    &mut metrics,
+    &containers,
+    "periphery_container_mem_used_bytes",
+    "Container memory usage in bytes.",
+    |stats| stats.mem_usage_bytes().map(|(used, _)| used),
+  );
+  container_gauge(
+    &mut metrics,
+    &containers,
+    "periphery_container_mem_limit_bytes",
+    "Container memory limit in bytes.",
+    |stats| stats.mem_usage_bytes().map(|(_, limit)| limit),
+  );
+
+  metrics.into_string()
+}
+
+fn container_gauge(
+  metrics: &mut Metrics,
+  containers: &[ContainerStats],
+  metric: &str,
+  help: &str,
+  value: impl Fn(&ContainerStats) -> Option<f64>,
+) {
+  metrics.header(metric, help, "gauge");
+  for stats in containers {
+    if let Some(value) = value(stats) {
+      metrics.sample(metric, &[("container", &stats.name)], value);
+    }
+  }
+}
@@ -16,13 +16,28 @@ use resolver_api::Resolver;
 use serror::{AddStatusCode, AddStatusCodeError, Json};
 use uuid::Uuid;
 
-use crate::{config::periphery_config, State};
+use crate::{config::periphery_config, metrics, State};
 
 pub fn router() -> Router {
-  Router::new()
+  let config = periphery_config();
+  let mut router = Router::new()
     .route("/", post(handler))
-    .layer(middleware::from_fn(guard_request_by_ip))
-    .layer(middleware::from_fn(guard_request_by_passkey))
+    .layer(middleware::from_fn(guard_request_by_passkey));
+  if config.metrics_enabled && config.metrics_port.is_none() {
+    router = router.merge(metrics_routes());
+  }
+  router.layer(middleware::from_fn(guard_request_by_ip))
+}
+
+/// Serves `/metrics` alone, when configured with a separate port.
+pub fn metrics_router() -> Router {
+  metrics_routes().layer(middleware::from_fn(guard_request_by_ip))
+}
+
+fn metrics_routes() -> Router {
+  Router::new()
+    .nest("/metrics", metrics::router())
+    .layer(middleware::from_fn(guard_metrics_by_passkey))
 }
 
 async fn handler(
@@ -69,8 +84,25 @@ async fn guard_request_by_passkey(
   req: Request<Body>,
   next: Next,
 ) -> serror::Result<Response> {
+  check_passkey(&req, false)?;
+  Ok(next.run(req).await)
+}
+
+/// Metrics scrapers send the passkey as a bearer token
+async fn guard_metrics_by_passkey(
+  req: Request<Body>,
+  next: Next,
+) -> serror::Result<Response> {
+  check_passkey(&req, true)?;
+  Ok(next.run(req).await)
+}
+
+fn check_passkey(
+  req: &Request<Body>,
+  allow_bearer: bool,
+) -> serror::Result<()> {
   if periphery_config().passkeys.is_empty() {
-    return Ok(next.run(req).await);
+    return Ok(());
   }
   let Some(req_passkey) = req.headers().get("authorization") else {
     return Err(
@@ -78,16 +110,20 @@ async fn guard_request_by_passkey(
         .status_code(StatusCode::UNAUTHORIZED),
     );
   };
-  let req_passkey = req_passkey
+  let mut req_passkey = req_passkey
     .to_str()
     .context("failed to convert passkey to str")
     .status_code(StatusCode::UNAUTHORIZED)?;
+  if allow_bearer {
+    req_passkey =
+      req_passkey.strip_prefix("Bearer ").unwrap_or(req_passkey);
+  }
   if periphery_config()
     .passkeys
     .iter()
     .any(|passkey| passkey == req_passkey)
   {
-    Ok(next.run(req).await)
+    Ok(())
   } else {
     Err(
       anyhow!("request passkey invalid")
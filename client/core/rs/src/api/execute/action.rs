@@ -1,10 +1,12 @@
+use std::collections::HashMap;
+
 use clap::Parser;
 use derive_empty_traits::EmptyTraits;
 use resolver_api::derive::Request;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::update::Update;
+use crate::{entities::update::Update, parsers::parse_arguments};
 
 use super::{BatchExecutionResponse, KomodoExecuteRequest};
 
@@ -25,6 +27,11 @@ use super::{BatchExecutionResponse, KomodoExecuteRequest};
 pub struct RunAction {
   /// Id or name
   pub action: String,
+  /// Arguments for the Action parameters, by parameter name.
+  /// Parameters not given here use their default.
+  #[arg(long, value_parser = parse_arguments)]
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub args: Option<HashMap<String, String>>,
 }
 
 /// Runs multiple Actions in parallel that match pattern. Response: [BatchExecutionResponse]
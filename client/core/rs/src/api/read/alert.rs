@@ -3,7 +3,10 @@ use resolver_api::derive::Request;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::{alert::Alert, MongoDocument, I64, U64};
+use crate::entities::{
+  alert::{Alert, AlertDataVariant, SeverityLevel},
+  MongoDocument, ResourceTarget, I64, U64,
+};
 
 use super::KomodoReadRequest;
 
@@ -41,6 +44,10 @@ pub struct ListAlerts {
   /// ```
   /// This will filter to only include open alerts that have CRITICAL level on those two servers.
   pub query: Option<MongoDocument>,
+  /// Only include acknowledged (`true`) or
+  /// unacknowledged (`false`) alerts.
+  #[serde(default)]
+  pub acknowledged: Option<bool>,
   /// Retrieve older results by incrementing the page.
   /// `page: 0` is default, and returns the most recent results.
   #[serde(default)]
@@ -72,3 +79,72 @@ pub struct GetAlert {
 
 #[typeshare]
 pub type GetAlertResponse = Alert;
+
+//
+
+/// Get alert analytics over a time range, grouped by
+/// target, alert type and severity level.
+/// Only alerts opened within the range are included.
+/// Response: [GetAlertStatsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetAlertStatsResponse)]
+pub struct GetAlertStats {
+  /// Unix timestamp in milliseconds of the start of the range.
+  /// Default: 7 days before `to`.
+  #[serde(default)]
+  pub from: Option<I64>,
+  /// Unix timestamp in milliseconds of the end of the range.
+  /// Default: now.
+  #[serde(default)]
+  pub to: Option<I64>,
+  /// Only include alerts on these targets.
+  /// If empty, includes all targets.
+  #[serde(default)]
+  pub targets: Vec<ResourceTarget>,
+  /// Only include these alert types.
+  /// If empty, includes all alert types.
+  #[serde(default)]
+  pub alert_types: Vec<AlertDataVariant>,
+}
+
+/// Response for [GetAlertStats].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GetAlertStatsResponse {
+  /// The start of the range used.
+  pub from: I64,
+  /// The end of the range used.
+  pub to: I64,
+  /// The alert stats, sorted by count descending.
+  pub stats: Vec<AlertStats>,
+}
+
+/// Aggregated alerts on a target for an alert type and severity level.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AlertStats {
+  /// The target of the alerts.
+  pub target: ResourceTarget,
+  /// The name of the target on the latest alert.
+  pub name: String,
+  /// The type of the alerts.
+  pub alert_type: AlertDataVariant,
+  /// The severity level of the group.
+  pub level: SeverityLevel,
+  /// The number of alerts opened in the range.
+  pub count: I64,
+  /// The number of these alerts which have been resolved.
+  pub resolved: I64,
+  /// The mean time from open to resolve in milliseconds,
+  /// over the resolved alerts. Null if none are resolved.
+  pub mean_time_to_resolve_ms: Option<f64>,
+  /// The number of these alerts which are still open.
+  pub open: I64,
+  /// How long the oldest open alert has been open in milliseconds.
+  /// Null if none are open.
+  pub open_duration_ms: Option<I64>,
+}
@@ -0,0 +1,43 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::maintenance::MaintenanceWindow;
+
+use super::KomodoReadRequest;
+
+/// Get a specific maintenance window by name.
+/// Response: [MaintenanceWindow]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetMaintenanceWindowResponse)]
+pub struct GetMaintenanceWindow {
+  /// The name of the maintenance window to get.
+  pub name: String,
+}
+
+#[typeshare]
+pub type GetMaintenanceWindowResponse = MaintenanceWindow;
+
+//
+
+/// List the maintenance windows.
+/// Response: [ListMaintenanceWindowsResponse]
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Default, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListMaintenanceWindowsResponse)]
+pub struct ListMaintenanceWindows {
+  /// Only include the windows which are currently active.
+  #[serde(default)]
+  pub active: bool,
+}
+
+#[typeshare]
+pub type ListMaintenanceWindowsResponse = Vec<MaintenanceWindow>;
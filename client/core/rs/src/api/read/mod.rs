@@ -9,7 +9,9 @@ mod alerter;
 mod build;
 mod builder;
 mod deployment;
+mod maintenance;
 mod permission;
+mod probe;
 mod procedure;
 mod provider;
 mod repo;
@@ -31,7 +33,9 @@ pub use alerter::*;
 pub use build::*;
 pub use builder::*;
 pub use deployment::*;
+pub use maintenance::*;
 pub use permission::*;
+pub use probe::*;
 pub use procedure::*;
 pub use provider::*;
 pub use repo::*;
@@ -0,0 +1,39 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{probe::ProbeRecord, ResourceTarget};
+
+use super::KomodoReadRequest;
+
+/// Get a paginated list of the endpoint probe results
+/// on a Deployment or Stack, sorted by timestamp descending.
+/// Response: [ListProbeRecordsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListProbeRecordsResponse)]
+pub struct ListProbeRecords {
+  /// The Deployment or Stack target.
+  pub target: ResourceTarget,
+  /// Only include the results for this endpoint.
+  #[serde(default)]
+  pub endpoint: Option<String>,
+  /// Page of probe records. Default is 0, which is the most recent data.
+  /// Use with the `next_page` field of the response.
+  #[serde(default)]
+  pub page: u32,
+}
+
+/// Response for [ListProbeRecords].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListProbeRecordsResponse {
+  /// The page of probe records, newest first.
+  pub records: Vec<ProbeRecord>,
+  /// If there is a next page of data, pass this to `page` to get it.
+  pub next_page: Option<u32>,
+}
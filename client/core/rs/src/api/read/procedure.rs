@@ -3,8 +3,12 @@ use resolver_api::derive::Request;
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
 
-use crate::entities::procedure::{
-  Procedure, ProcedureActionState, ProcedureListItem, ProcedureQuery,
+use crate::entities::{
+  procedure::{
+    Procedure, ProcedureActionState, ProcedureListItem,
+    ProcedureQuery, ProcedureRun,
+  },
+  I64, U64,
 };
 
 use super::KomodoReadRequest;
@@ -108,3 +112,51 @@ pub struct GetProceduresSummaryResponse {
   /// The number of procedures with unknown state.
   pub unknown: u32,
 }
+
+//
+
+/// Get a paginated list of the runs of a procedure,
+/// sorted by start time descending. Response: [ListProcedureRunsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(ListProcedureRunsResponse)]
+pub struct ListProcedureRuns {
+  /// Id or name
+  #[serde(alias = "id", alias = "name")]
+  pub procedure: String,
+  /// Retrieve older results by incrementing the page.
+  /// `page: 0` is default, and returns the most recent results.
+  #[serde(default)]
+  pub page: U64,
+}
+
+/// Response for [ListProcedureRuns].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListProcedureRunsResponse {
+  pub runs: Vec<ProcedureRun>,
+  /// If more runs exist, the next page will be given here.
+  /// Otherwise it will be `null`
+  pub next_page: Option<I64>,
+}
+
+//
+
+/// Get a procedure run, with the timing and result of
+/// each stage and execution. Response: [ProcedureRun].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetProcedureRunResponse)]
+pub struct GetProcedureRun {
+  /// The id of the run, or of its `RunProcedure` update.
+  pub id: String,
+}
+
+#[typeshare]
+pub type GetProcedureRunResponse = ProcedureRun;
@@ -16,7 +16,8 @@ use crate::entities::{
   },
   stack::ComposeProject,
   stats::{
-    SystemInformation, SystemProcess, SystemStats, SystemStatsRecord,
+    ContainerStatsRecord, SystemInformation, SystemProcess,
+    SystemStats, SystemStatsRecord,
   },
   update::Log,
   ResourceTarget, SearchCombinator, Timelength, I64, U64,
@@ -551,6 +552,44 @@ pub struct GetHistoricalServerStatsResponse {
 
 //
 
+/// Paginated endpoint serving historical (timeseries) container stats
+/// for a Deployment or Stack service, for graphing.
+/// Only available for servers with `record_container_stats` enabled.
+/// Response: [GetHistoricalContainerStatsResponse].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoReadRequest)]
+#[response(GetHistoricalContainerStatsResponse)]
+pub struct GetHistoricalContainerStats {
+  /// The Deployment or Stack target.
+  pub target: ResourceTarget,
+  /// The Stack service name. Required for Stack targets.
+  #[serde(default)]
+  pub service: Option<String>,
+  /// The granularity of the data.
+  pub granularity: Timelength,
+  /// Page of historical data. Default is 0, which is the most recent data.
+  /// Use with the `next_page` field of the response.
+  #[serde(default)]
+  pub page: u32,
+}
+
+/// Response to [GetHistoricalContainerStats].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GetHistoricalContainerStatsResponse {
+  /// The timeseries page of data.
+  /// Stack services with multiple replicas include
+  /// the data for each container.
+  pub stats: Vec<ContainerStatsRecord>,
+  /// If there is a next page of data, pass this to `page` to get it.
+  pub next_page: Option<u32>,
+}
+
+//
+
 /// Gets a summary of data relating to all servers.
 /// Response: [GetServersSummaryResponse].
 #[typeshare]
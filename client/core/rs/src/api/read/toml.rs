@@ -54,6 +54,9 @@ pub struct ExportResourcesToToml {
   /// Whether to include variables
   #[serde(default)]
   pub include_variables: bool,
+  /// Whether to include maintenance windows
+  #[serde(default)]
+  pub include_maintenance_windows: bool,
 }
 
 #[typeshare]
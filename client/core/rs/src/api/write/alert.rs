@@ -0,0 +1,48 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::{alert::Alert, I64};
+
+use super::KomodoWriteRequest;
+
+/// Acknowledge an open alert, marking that it is being handled.
+/// Acknowledged alerts are not re-notified or escalated.
+/// Requires execute permissions on the alert target.
+/// Response: [Alert].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(AcknowledgeAlertResponse)]
+pub struct AcknowledgeAlert {
+  /// The id of the alert.
+  pub id: String,
+}
+
+#[typeshare]
+pub type AcknowledgeAlertResponse = Alert;
+
+//
+
+/// Snooze an open alert, so it is not re-notified or escalated
+/// until the snooze expires. Pass `minutes: 0` to end the snooze.
+/// Requires execute permissions on the alert target.
+/// Response: [Alert].
+#[typeshare]
+#[derive(
+  Serialize, Deserialize, Debug, Clone, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(SnoozeAlertResponse)]
+pub struct SnoozeAlert {
+  /// The id of the alert.
+  pub id: String,
+  /// How long to snooze the alert for, in minutes.
+  pub minutes: I64,
+}
+
+#[typeshare]
+pub type SnoozeAlertResponse = Alert;
@@ -0,0 +1,60 @@
+use derive_empty_traits::EmptyTraits;
+use resolver_api::derive::Request;
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use crate::entities::maintenance::MaintenanceWindow;
+
+use super::KomodoWriteRequest;
+
+/// **Admin only.** Create a maintenance window.
+/// Response: [MaintenanceWindow].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(CreateMaintenanceWindowResponse)]
+pub struct CreateMaintenanceWindow {
+  /// The maintenance window to create.
+  pub window: MaintenanceWindow,
+}
+
+#[typeshare]
+pub type CreateMaintenanceWindowResponse = MaintenanceWindow;
+
+//
+
+/// **Admin only.** Update a maintenance window,
+/// matched by `window.name`. Response: [MaintenanceWindow].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(UpdateMaintenanceWindowResponse)]
+pub struct UpdateMaintenanceWindow {
+  /// The full maintenance window to set.
+  pub window: MaintenanceWindow,
+}
+
+#[typeshare]
+pub type UpdateMaintenanceWindowResponse = MaintenanceWindow;
+
+//
+
+/// **Admin only.** Delete a maintenance window.
+/// Response: [MaintenanceWindow].
+#[typeshare]
+#[derive(
+  Debug, Clone, Serialize, Deserialize, Request, EmptyTraits,
+)]
+#[empty_traits(KomodoWriteRequest)]
+#[response(DeleteMaintenanceWindowResponse)]
+pub struct DeleteMaintenanceWindow {
+  /// The name of the maintenance window to delete.
+  pub name: String,
+}
+
+#[typeshare]
+pub type DeleteMaintenanceWindowResponse = MaintenanceWindow;
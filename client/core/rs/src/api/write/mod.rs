@@ -1,10 +1,12 @@
 mod action;
+mod alert;
 mod alerter;
 mod api_key;
 mod build;
 mod builder;
 mod deployment;
 mod description;
+mod maintenance;
 mod permissions;
 mod procedure;
 mod provider;
@@ -19,12 +21,14 @@ mod user_group;
 mod variable;
 
 pub use action::*;
+pub use alert::*;
 pub use alerter::*;
 pub use api_key::*;
 pub use build::*;
 pub use builder::*;
 pub use deployment::*;
 pub use description::*;
+pub use maintenance::*;
 pub use permissions::*;
 pub use procedure::*;
 pub use provider::*;
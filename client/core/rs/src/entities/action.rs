@@ -10,7 +10,7 @@ use crate::{
   deserializers::{
     file_contents_deserializer, option_file_contents_deserializer,
   },
-  entities::I64,
+  entities::{Parameter, I64},
 };
 
 use super::resource::{Resource, ResourceListItem, ResourceQuery};
@@ -71,6 +71,12 @@ pub struct ActionConfig {
   #[builder(default)]
   pub file_contents: String,
 
+  /// Typed parameters which can be given arguments when run.
+  #[serde(default, alias = "parameter")]
+  #[partial_attr(serde(alias = "parameter"))]
+  #[builder(default)]
+  pub parameters: Vec<Parameter>,
+
   /// Whether incoming webhooks actually trigger action.
   #[serde(default = "default_webhook_enabled")]
   #[builder(default = "default_webhook_enabled()")]
@@ -82,6 +88,13 @@ pub struct ActionConfig {
   #[serde(default)]
   #[builder(default)]
   pub webhook_secret: String,
+
+  /// Map fields of the incoming webhook payload into arguments.
+  /// One per line as `PARAMETER = path.to.field`,
+  /// eg. `TAG = head_commit.id`. Array items are indexed like `commits.0.id`.
+  #[serde(default)]
+  #[builder(default)]
+  pub webhook_args: String,
 }
 
 fn default_webhook_enabled() -> bool {
@@ -98,8 +111,10 @@ impl Default for ActionConfig {
   fn default() -> Self {
     Self {
       file_contents: Default::default(),
+      parameters: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      webhook_args: Default::default(),
     }
   }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use derive_variants::EnumVariants;
 use serde::{Deserialize, Serialize};
@@ -54,6 +54,36 @@ pub struct Alert {
 
   /// The timestamp of alert resolution
   pub resolved_ts: Option<I64>,
+
+  /// Whether the alert was held back from the Alerters
+  /// by an active [MaintenanceWindow][super::maintenance::MaintenanceWindow].
+  #[serde(default)]
+  pub suppressed: bool,
+
+  /// Alerter id -> unix timestamp in milliseconds
+  /// the alerter was last notified about this unresolved alert.
+  /// Used for re-notify and escalation.
+  #[serde(default)]
+  pub last_notified: HashMap<String, I64>,
+
+  /// Who acknowledged the alert, and when.
+  #[serde(default)]
+  pub acknowledged: Option<AlertAcknowledgement>,
+
+  /// Unix timestamp in milliseconds until which the alert
+  /// will not be re-notified or escalated.
+  #[serde(default)]
+  pub snoozed_until: I64,
+}
+
+/// Marks an open alert as being handled.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AlertAcknowledgement {
+  /// The id of the user who acknowledged the alert.
+  pub user_id: String,
+  /// Unix timestamp in milliseconds the alert was acknowledged.
+  pub ts: I64,
 }
 
 /// The variants of data related to the alert.
@@ -128,6 +158,34 @@ pub enum AlertData {
     total_gb: f64,
   },
 
+  /// A server has sustained high network throughput on an interface.
+  ServerNetwork {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The network interface name
+    interface: String,
+    /// The ingress throughput in MB/s
+    ingress_mb_per_sec: f64,
+    /// The egress throughput in MB/s
+    egress_mb_per_sec: f64,
+  },
+
+  /// A required process is not running on a server.
+  ServerProcessMissing {
+    /// The id of the server
+    id: String,
+    /// The name of the server
+    name: String,
+    /// The region of the server
+    region: Option<String>,
+    /// The name of the missing process
+    process: String,
+  },
+
   /// A container's state has changed unexpectedly.
   ContainerStateChange {
     /// The id of the deployment
@@ -144,6 +202,80 @@ pub enum AlertData {
     to: DeploymentState,
   },
 
+  /// A Deployment or Stack container is failing its health check.
+  ContainerUnhealthy {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+    /// The stack service, if the container is part of a Stack
+    service: Option<String>,
+  },
+
+  /// A Deployment or Stack container is repeatedly restarting.
+  ContainerRestartLoop {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+    /// The stack service, if the container is part of a Stack
+    service: Option<String>,
+    /// The number of restarts within the window
+    restarts: I64,
+    /// The window restarts are counted in, in minutes
+    window_minutes: I64,
+  },
+
+  /// A Deployment or Stack container has high cpu usage.
+  ContainerCpu {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+    /// The stack service, if the container is part of a Stack
+    service: Option<String>,
+    /// The cpu usage percentage
+    percentage: f64,
+  },
+
+  /// A Deployment or Stack container has high memory usage.
+  ContainerMem {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The server id of server that the container is on
+    server_id: String,
+    /// The server name
+    server_name: String,
+    /// The container name
+    container: String,
+    /// The stack service, if the container is part of a Stack
+    service: Option<String>,
+    /// The memory used in MB
+    used_mb: f64,
+    /// The memory limit in MB
+    limit_mb: f64,
+  },
+
   /// A Deployment has an image update available
   DeploymentImageUpdateAvailable {
     /// The id of the deployment
@@ -218,6 +350,46 @@ pub enum AlertData {
     images: Vec<String>,
   },
 
+  /// The TLS certificate served at one of a
+  /// Server / Deployment / Stack's https links is expiring soon.
+  CertificateExpiring {
+    /// The id of the server / deployment / stack
+    id: String,
+    /// The name of the server / deployment / stack
+    name: String,
+    /// The probed https link
+    url: String,
+    /// The certificate expiry (not after) timestamp in ms
+    expires_ts: I64,
+    /// The number of whole days until expiry.
+    /// Negative if the certificate has already expired.
+    days_remaining: I64,
+  },
+
+  /// An endpoint probe on a Deployment / Stack is failing.
+  EndpointDown {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The probed endpoint
+    endpoint: String,
+    /// The reason for the failure
+    error: String,
+  },
+
+  /// A failing endpoint probe on a Deployment / Stack is passing again.
+  EndpointRecovered {
+    /// The id of the deployment / stack
+    id: String,
+    /// The name of the deployment / stack
+    name: String,
+    /// The probed endpoint
+    endpoint: String,
+    /// How long the endpoint was down in milliseconds
+    down_ms: I64,
+  },
+
   /// An AWS builder failed to terminate.
   AwsBuilderTerminationFailed {
     /// The id of the aws instance which failed to terminate
@@ -10,7 +10,7 @@ use typeshare::typeshare;
 use super::{
   alert::AlertDataVariant,
   resource::{Resource, ResourceListItem, ResourceQuery},
-  ResourceTarget,
+  ResourceTarget, I64,
 };
 
 #[typeshare]
@@ -64,6 +64,42 @@ pub struct AlerterConfig {
   #[serde(default)]
   #[builder(default)]
   pub except_resources: Vec<ResourceTarget>,
+
+  /// Re-send unresolved alerts to this alerter
+  /// after this many minutes since it was last notified.
+  /// 0 means alerts are only sent once.
+  #[serde(default)]
+  #[builder(default)]
+  pub renotify_interval_minutes: I64,
+
+  /// Also send unresolved alerts to other alerters
+  /// once they have been open for the configured time.
+  #[serde(default)]
+  #[builder(default)]
+  pub escalations: Vec<AlerterEscalation>,
+
+  /// Optional [minijinja](https://docs.rs/minijinja) template
+  /// to shape the alert messages. If empty, the built in format is used.
+  ///
+  /// - `Custom`: The rendered template is posted as the request body,
+  ///   with content type `application/json` if it is valid JSON.
+  /// - Other endpoints: The rendered template is sent as the message.
+  ///   Endpoints with a title / subject use the first line.
+  ///
+  /// Available in the template:
+  /// - `alert`: The full [Alert][super::alert::Alert].
+  /// - `name`: The name of the alert target.
+  /// - `level`: The severity, `OK`, `WARNING`, or `CRITICAL`.
+  /// - `link`: Link to the alert target in the Komodo UI.
+  /// - `message`: The built in markdown message.
+  ///
+  /// Example (Microsoft Teams):
+  /// ```text
+  /// { "text": {{ (level ~ " | " ~ name ~ "\n" ~ link) | tojson }} }
+  /// ```
+  #[serde(default)]
+  #[builder(default)]
+  pub template: String,
 }
 
 impl AlerterConfig {
@@ -80,10 +116,25 @@ impl Default for AlerterConfig {
       alert_types: Default::default(),
       resources: Default::default(),
       except_resources: Default::default(),
+      renotify_interval_minutes: Default::default(),
+      escalations: Default::default(),
+      template: Default::default(),
     }
   }
 }
 
+/// Forward alerts which stay unresolved to another alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct AlerterEscalation {
+  /// Minutes the alert must stay unresolved before escalating.
+  pub after_minutes: I64,
+  /// The id or name of the alerter to escalate to.
+  pub alerter: String,
+}
+
 // ENDPOINTS
 
 #[typeshare]
@@ -110,6 +161,15 @@ pub enum AlerterEndpoint {
 
   /// Send alert to a Discord app
   Discord(DiscordAlerterEndpoint),
+
+  /// Send alert as an email over SMTP
+  Smtp(SmtpAlerterEndpoint),
+
+  /// Send alert as a push notification to a ntfy topic
+  Ntfy(NtfyAlerterEndpoint),
+
+  /// Send alert as a push notification to a Gotify server
+  Gotify(GotifyAlerterEndpoint),
 }
 
 impl Default for AlerterEndpoint {
@@ -119,6 +179,10 @@ impl Default for AlerterEndpoint {
 }
 
 /// Configuration for a Custom alerter endpoint.
+///
+/// The `headers`, `username`, `password`, `token`, and `signing_secret`
+/// fields support interpolation of Core variables and secrets,
+/// eg. `[[ALERT_WEBHOOK_TOKEN]]`.
 #[typeshare]
 #[derive(
   Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
@@ -128,12 +192,64 @@ pub struct CustomAlerterEndpoint {
   #[serde(default = "default_custom_url")]
   #[builder(default = "default_custom_url()")]
   pub url: String,
+
+  /// Extra headers to send with the request,
+  /// one `Header-Name: value` per line.
+  #[serde(default)]
+  #[builder(default)]
+  pub headers: String,
+
+  /// Username for basic auth.
+  /// If empty, will not use basic auth.
+  #[serde(default)]
+  #[builder(default)]
+  pub username: String,
+
+  /// Password for basic auth.
+  /// Use a secret here, eg. `[[ALERT_WEBHOOK_PASSWORD]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub password: String,
+
+  /// Bearer token sent in the `Authorization` header.
+  /// Only used when `username` is empty.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+
+  /// If set, the request body is signed with HMAC-SHA256 using this secret.
+  /// The signature is sent in the `X-Komodo-Signature` header,
+  /// as `sha256=<hex digest>`.
+  #[serde(default)]
+  #[builder(default)]
+  pub signing_secret: String,
+
+  /// Retry the request this many times when the receiver
+  /// responds with a 5xx status or can't be reached,
+  /// with exponential backoff starting at 1 second.
+  /// Default: `3`
+  #[serde(default = "default_custom_retries")]
+  #[builder(default = "default_custom_retries()")]
+  pub retries: I64,
+
+  /// Timeout for each request in seconds.
+  /// Default: `10`
+  #[serde(default = "default_custom_timeout_seconds")]
+  #[builder(default = "default_custom_timeout_seconds()")]
+  pub timeout_seconds: I64,
 }
 
 impl Default for CustomAlerterEndpoint {
   fn default() -> Self {
     Self {
       url: default_custom_url(),
+      headers: Default::default(),
+      username: Default::default(),
+      password: Default::default(),
+      token: Default::default(),
+      signing_secret: Default::default(),
+      retries: default_custom_retries(),
+      timeout_seconds: default_custom_timeout_seconds(),
     }
   }
 }
@@ -142,6 +258,14 @@ fn default_custom_url() -> String {
   String::from("http://localhost:7000")
 }
 
+fn default_custom_retries() -> I64 {
+  3
+}
+
+fn default_custom_timeout_seconds() -> I64 {
+  10
+}
+
 /// Configuration for a Slack alerter.
 #[typeshare]
 #[derive(
@@ -194,6 +318,153 @@ fn default_discord_url() -> String {
   )
 }
 
+/// Configuration for an SMTP (email) alerter.
+///
+/// The `username`, `password`, and `from` fields support interpolation
+/// of Core variables and secrets, eg. `[[SMTP_PASSWORD]]`.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct SmtpAlerterEndpoint {
+  /// The SMTP server host, eg. `smtp.gmail.com`
+  #[serde(default = "default_smtp_host")]
+  #[builder(default = "default_smtp_host()")]
+  pub host: String,
+
+  /// The SMTP server port.
+  /// Default: `587`
+  #[serde(default = "default_smtp_port")]
+  #[builder(default = "default_smtp_port()")]
+  pub port: u16,
+
+  /// How the connection to the SMTP server is secured.
+  /// Default: `StartTls`
+  #[serde(default)]
+  #[builder(default)]
+  pub tls: SmtpTlsMode,
+
+  /// The username to authenticate with.
+  /// If empty, will not authenticate.
+  #[serde(default)]
+  #[builder(default)]
+  pub username: String,
+
+  /// The password to authenticate with.
+  /// Use a secret here, eg. `[[SMTP_PASSWORD]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub password: String,
+
+  /// The sender mailbox, eg. `Komodo <alerts@example.com>`
+  #[serde(default)]
+  #[builder(default)]
+  pub from: String,
+
+  /// The recipient mailboxes.
+  #[serde(default)]
+  #[builder(default)]
+  pub to: Vec<String>,
+}
+
+impl Default for SmtpAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      host: default_smtp_host(),
+      port: default_smtp_port(),
+      tls: Default::default(),
+      username: Default::default(),
+      password: Default::default(),
+      from: Default::default(),
+      to: Default::default(),
+    }
+  }
+}
+
+fn default_smtp_host() -> String {
+  String::from("localhost")
+}
+
+fn default_smtp_port() -> u16 {
+  587
+}
+
+/// How the connection to the SMTP server is secured.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+)]
+pub enum SmtpTlsMode {
+  /// Plaintext connection. Only use with a local relay / test sink.
+  None,
+  /// Upgrade a plaintext connection with STARTTLS. Usually port `587`.
+  #[default]
+  StartTls,
+  /// Implicit TLS from the start of the connection. Usually port `465`.
+  Tls,
+}
+
+/// Configuration for a ntfy alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct NtfyAlerterEndpoint {
+  /// The full url of the ntfy topic, eg. `https://ntfy.sh/<topic>`.
+  /// Anyone who knows a topic on a public server can read it,
+  /// so use a hard to guess topic or a protected one. Required.
+  #[serde(default)]
+  #[builder(default)]
+  pub url: String,
+
+  /// Optional access token for protected topics.
+  /// Supports interpolation of Core variables and secrets,
+  /// eg. `[[NTFY_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+}
+
+/// Configuration for a Gotify alerter.
+#[typeshare]
+#[derive(
+  Debug, Clone, PartialEq, Serialize, Deserialize, Builder,
+)]
+pub struct GotifyAlerterEndpoint {
+  /// The url of the Gotify server, eg. `https://gotify.example.com`
+  #[serde(default = "default_gotify_url")]
+  #[builder(default = "default_gotify_url()")]
+  pub url: String,
+
+  /// The Gotify application token.
+  /// Supports interpolation of Core variables and secrets,
+  /// eg. `[[GOTIFY_TOKEN]]`.
+  #[serde(default)]
+  #[builder(default)]
+  pub token: String,
+}
+
+impl Default for GotifyAlerterEndpoint {
+  fn default() -> Self {
+    Self {
+      url: default_gotify_url(),
+      token: Default::default(),
+    }
+  }
+}
+
+fn default_gotify_url() -> String {
+  String::from("http://localhost:8080")
+}
+
 // QUERY
 
 #[typeshare]
@@ -70,8 +70,16 @@ pub struct Env {
   pub komodo_monitoring_interval: Option<Timelength>,
   /// Override `keep_stats_for_days`
   pub komodo_keep_stats_for_days: Option<u64>,
+  /// Override `keep_stats_rollups_for_days`
+  pub komodo_keep_stats_rollups_for_days: Option<u64>,
   /// Override `keep_alerts_for_days`
   pub komodo_keep_alerts_for_days: Option<u64>,
+  /// Override `keep_procedure_runs_for_days`
+  pub komodo_keep_procedure_runs_for_days: Option<u64>,
+  /// Override `certificate_expiry_warning_days`
+  pub komodo_certificate_expiry_warning_days: Option<u64>,
+  /// Override `certificate_expiry_critical_days`
+  pub komodo_certificate_expiry_critical_days: Option<u64>,
   /// Override `webhook_secret`
   pub komodo_webhook_secret: Option<String>,
   /// Override `webhook_secret` with file
@@ -406,17 +414,47 @@ pub struct CoreConfig {
   // = Pruning =
   // ===========
   /// Number of days to keep stats, or 0 to disable pruning.
-  /// Stats older than this number of days are deleted on a daily cycle
+  /// Stats older than this number of days are deleted on a daily cycle.
+  /// This also applies to container stats and endpoint probe history.
   /// Default: 14
   #[serde(default = "default_prune_days")]
   pub keep_stats_for_days: u64,
 
+  /// Number of days to keep the downsampled (5 minute / 1 hour) stats,
+  /// or 0 to disable pruning. These keep long term trends around
+  /// after the raw stats are deleted.
+  /// Default: 180
+  #[serde(default = "default_prune_rollup_days")]
+  pub keep_stats_rollups_for_days: u64,
+
   /// Number of days to keep alerts, or 0 to disable pruning.
   /// Alerts older than this number of days are deleted on a daily cycle
   /// Default: 14
   #[serde(default = "default_prune_days")]
   pub keep_alerts_for_days: u64,
 
+  /// Number of days to keep procedure run history, or 0 to disable pruning.
+  /// Runs older than this number of days are deleted on a daily cycle
+  /// Default: 14
+  #[serde(default = "default_prune_days")]
+  pub keep_procedure_runs_for_days: u64,
+
+  // ================
+  // = Certificates =
+  // ================
+  /// The https links on Servers, Deployments, and Stacks are probed hourly,
+  /// and a 'Warning' CertificateExpiring alert is sent when the served
+  /// certificate expires within this number of days. 0 to disable.
+  /// Default: 14
+  #[serde(default = "default_certificate_expiry_warning_days")]
+  pub certificate_expiry_warning_days: u64,
+
+  /// Send a 'Critical' CertificateExpiring alert when the served
+  /// certificate expires within this number of days. 0 to disable.
+  /// Default: 3
+  #[serde(default = "default_certificate_expiry_critical_days")]
+  pub certificate_expiry_critical_days: u64,
+
   // ==================
   // = Poll Intervals =
   // ==================
@@ -539,6 +577,18 @@ fn default_prune_days() -> u64 {
   14
 }
 
+fn default_prune_rollup_days() -> u64 {
+  180
+}
+
+fn default_certificate_expiry_warning_days() -> u64 {
+  14
+}
+
+fn default_certificate_expiry_critical_days() -> u64 {
+  3
+}
+
 fn default_poll_interval() -> Timelength {
   Timelength::FiveMinutes
 }
@@ -573,7 +623,14 @@ impl CoreConfig {
       resource_poll_interval: config.resource_poll_interval,
       monitoring_interval: config.monitoring_interval,
       keep_stats_for_days: config.keep_stats_for_days,
+      keep_stats_rollups_for_days: config.keep_stats_rollups_for_days,
       keep_alerts_for_days: config.keep_alerts_for_days,
+      keep_procedure_runs_for_days: config
+        .keep_procedure_runs_for_days,
+      certificate_expiry_warning_days: config
+        .certificate_expiry_warning_days,
+      certificate_expiry_critical_days: config
+        .certificate_expiry_critical_days,
       logging: config.logging,
       transparent_mode: config.transparent_mode,
       ui_write_disabled: config.ui_write_disabled,
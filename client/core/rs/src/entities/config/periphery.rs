@@ -143,6 +143,10 @@ pub struct Env {
   pub periphery_include_disk_mounts: Option<Vec<PathBuf>>,
   /// Override `exclude_disk_mounts`
   pub periphery_exclude_disk_mounts: Option<Vec<PathBuf>>,
+  /// Override `metrics_enabled`
+  pub periphery_metrics_enabled: Option<bool>,
+  /// Override `metrics_port`
+  pub periphery_metrics_port: Option<u16>,
 
   /// Override `ssl_enabled`
   pub periphery_ssl_enabled: Option<bool>,
@@ -209,6 +213,17 @@ pub struct PeripheryConfig {
   #[serde(default)]
   pub exclude_disk_mounts: Vec<PathBuf>,
 
+  /// Serve system and container stats in the Prometheus
+  /// text format at `/metrics`.
+  /// Default: false
+  #[serde(default)]
+  pub metrics_enabled: bool,
+
+  /// Serve `/metrics` on a separate port.
+  /// If not provided, it is served on the main `port`.
+  #[serde(default)]
+  pub metrics_port: Option<u16>,
+
   /// Mapping on local periphery secrets. These can be interpolated into eg. Deployment environment variables.
   /// Default: none
   #[serde(default)]
@@ -281,6 +296,8 @@ impl Default for PeripheryConfig {
       passkeys: Default::default(),
       include_disk_mounts: Default::default(),
       exclude_disk_mounts: Default::default(),
+      metrics_enabled: Default::default(),
+      metrics_port: Default::default(),
       secrets: Default::default(),
       git_providers: Default::default(),
       docker_registries: Default::default(),
@@ -308,6 +325,8 @@ impl PeripheryConfig {
         .collect(),
       include_disk_mounts: self.include_disk_mounts.clone(),
       exclude_disk_mounts: self.exclude_disk_mounts.clone(),
+      metrics_enabled: self.metrics_enabled,
+      metrics_port: self.metrics_port,
       secrets: self
         .secrets
         .iter()
@@ -20,9 +20,10 @@ use crate::{
 };
 
 use super::{
-  docker::container::ContainerStateStatusEnum,
+  docker::container::{ContainerStateStatusEnum, HealthStatusEnum},
+  probe::EndpointProbe,
   resource::{Resource, ResourceListItem, ResourceQuery},
-  TerminationSignal, Version,
+  TerminationSignal, Version, I64,
 };
 
 #[typeshare]
@@ -39,6 +40,8 @@ pub struct DeploymentListItemInfo {
   pub state: DeploymentState,
   /// The status of the docker container (eg. up 12 hours, exited 5 minutes ago.)
   pub status: Option<String>,
+  /// The health check status of the docker container.
+  pub health: HealthStatusEnum,
   /// The image attached to the deployment.
   pub image: String,
   /// Whether there is a newer image available at the same tag.
@@ -108,6 +111,56 @@ pub struct DeploymentConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// Whether to send ContainerRestartLoop alerts for this deployment.
+  /// Requires `send_alerts` to also be enabled.
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_restart_loop_alerts: bool,
+
+  /// Send a ContainerRestartLoop alert when a container restarts
+  /// more than this many times within `restart_loop_window_minutes`.
+  #[serde(default = "default_restart_loop_threshold")]
+  #[builder(default = "default_restart_loop_threshold()")]
+  #[partial_default(default_restart_loop_threshold())]
+  pub restart_loop_threshold: I64,
+
+  /// The window used to count container restarts, in minutes.
+  #[serde(default = "default_restart_loop_window_minutes")]
+  #[builder(default = "default_restart_loop_window_minutes()")]
+  #[partial_default(default_restart_loop_window_minutes())]
+  pub restart_loop_window_minutes: I64,
+
+  /// Send a 'Warning' ContainerCpu alert when the container
+  /// cpu usage in % is above this threshhold. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub cpu_warning: f64,
+
+  /// Send a 'Critical' ContainerCpu alert when the container
+  /// cpu usage in % is above this threshhold. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub cpu_critical: f64,
+
+  /// Send a 'Warning' ContainerMem alert when the container
+  /// memory usage in MB is above this threshhold. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub mem_warning_mb: f64,
+
+  /// Send a 'Critical' ContainerMem alert when the container
+  /// memory usage in MB is above this threshhold. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub mem_critical_mb: f64,
+
+  /// HTTP / TCP uptime checks on the deployment.
+  /// Failing probes send EndpointDown alerts.
+  #[serde(default)]
+  #[builder(default)]
+  pub probes: Vec<EndpointProbe>,
+
   /// Configure quick links that are displayed in the resource header
   #[serde(default)]
   #[builder(default)]
@@ -214,6 +267,14 @@ fn default_send_alerts() -> bool {
   true
 }
 
+fn default_restart_loop_threshold() -> I64 {
+  3
+}
+
+fn default_restart_loop_window_minutes() -> I64 {
+  10
+}
+
 fn default_termination_timeout() -> i32 {
   10
 }
@@ -227,6 +288,15 @@ impl Default for DeploymentConfig {
     Self {
       server_id: Default::default(),
       send_alerts: default_send_alerts(),
+      send_restart_loop_alerts: default_send_alerts(),
+      restart_loop_threshold: default_restart_loop_threshold(),
+      restart_loop_window_minutes:
+        default_restart_loop_window_minutes(),
+      cpu_warning: Default::default(),
+      cpu_critical: Default::default(),
+      mem_warning_mb: Default::default(),
+      mem_critical_mb: Default::default(),
+      probes: Default::default(),
       links: Default::default(),
       image: Default::default(),
       image_registry_account: Default::default(),
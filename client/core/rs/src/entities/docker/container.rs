@@ -33,6 +33,14 @@ pub struct ContainerListItem {
   pub state: ContainerStateStatusEnum,
   /// Additional human-readable status of this container (e.g. `Exit 0`)
   pub status: Option<String>,
+  /// The health check status of this container.
+  /// `none` if the container has no health check.
+  #[serde(default)]
+  pub health: HealthStatusEnum,
+  /// The number of times the container has been restarted
+  /// by its restart policy. Only included in the Server monitoring lists.
+  #[serde(default)]
+  pub restart_count: Option<I64>,
   /// The network mode
   pub network_mode: Option<String>,
   /// The network names attached to container
@@ -1166,3 +1174,64 @@ pub struct ContainerStats {
   #[serde(alias = "PIDs")]
   pub pids: String,
 }
+
+impl ContainerStats {
+  /// The cpu usage percentage
+  pub fn cpu_perc(&self) -> Option<f64> {
+    parse_percentage(&self.cpu_perc)
+  }
+
+  /// The memory usage percentage of the limit
+  pub fn mem_perc(&self) -> Option<f64> {
+    parse_percentage(&self.mem_perc)
+  }
+
+  /// The (used, limit) memory in bytes
+  pub fn mem_usage_bytes(&self) -> Option<(f64, f64)> {
+    parse_bytes_pair(&self.mem_usage)
+  }
+
+  /// The total (received, sent) network usage in bytes
+  pub fn net_io_bytes(&self) -> Option<(f64, f64)> {
+    parse_bytes_pair(&self.net_io)
+  }
+
+  /// The total (read, written) block io in bytes
+  pub fn block_io_bytes(&self) -> Option<(f64, f64)> {
+    parse_bytes_pair(&self.block_io)
+  }
+}
+
+/// Parses docker stats percentages, eg. `0.52%`
+fn parse_percentage(percentage: &str) -> Option<f64> {
+  percentage.trim().trim_end_matches('%').parse().ok()
+}
+
+/// Parses docker stats size pairs, eg. `12.5MiB / 1.2GiB`
+fn parse_bytes_pair(pair: &str) -> Option<(f64, f64)> {
+  let (first, second) = pair.split_once('/')?;
+  Some((parse_bytes(first)?, parse_bytes(second)?))
+}
+
+/// Parses docker stats sizes, eg. `12.5MiB` or `1.2GB`
+fn parse_bytes(size: &str) -> Option<f64> {
+  let size = size.trim();
+  let split = size
+    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+    .unwrap_or(size.len());
+  let (value, unit) = size.split_at(split);
+  let value = value.parse::<f64>().ok()?;
+  let multiplier = match unit.trim() {
+    "" | "B" => 1.0,
+    "kB" | "KB" => 1e3,
+    "MB" => 1e6,
+    "GB" => 1e9,
+    "TB" => 1e12,
+    "KiB" => 1024.0,
+    "MiB" => 1024.0 * 1024.0,
+    "GiB" => 1024.0 * 1024.0 * 1024.0,
+    "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+    _ => return None,
+  };
+  Some(value * multiplier)
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{ResourceTarget, I64};
+
+/// A window of time during which alerts on the matching resources
+/// are held back from the Alerters. The alerts are still recorded,
+/// and marked as `suppressed`.
+///
+/// The window is active while enabled, between `start_ts` and `end_ts` (if given),
+/// and during each `schedule` occurrence (if given).
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+pub struct MaintenanceWindow {
+  /// Unique name associated with the maintenance window.
+  #[cfg_attr(feature = "mongo", unique_index)]
+  pub name: String,
+  /// The reason for the maintenance, eg. `Monthly host patching`.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub reason: String,
+  /// Whether the maintenance window is enabled.
+  #[serde(default = "default_enabled")]
+  pub enabled: bool,
+  /// Suppress alerts on these resources.
+  /// The resources can be referenced by id or name.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub targets: Vec<ResourceTarget>,
+  /// Suppress alerts on resources with any of these tags (ids or names).
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  /// Unix timestamp in milliseconds the window starts.
+  /// If 0, the window has no start bound.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub start_ts: I64,
+  /// Unix timestamp in milliseconds the window ends.
+  /// If 0, the window has no end bound.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub end_ts: I64,
+  /// Optional cron expression to make the window recurring,
+  /// eg. `0 0 2 * * SUN` for every Sunday at 2 AM.
+  /// Each occurrence opens the window for `duration_minutes`.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub schedule: String,
+  /// How long each recurring window lasts, in minutes.
+  /// Required when `schedule` is set.
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub duration_minutes: I64,
+  /// Optional IANA timezone for the schedule, eg. `America/New_York`.
+  /// If empty, uses the Core server's local timezone.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub schedule_timezone: String,
+}
+
+impl Default for MaintenanceWindow {
+  fn default() -> Self {
+    Self {
+      name: Default::default(),
+      reason: Default::default(),
+      enabled: default_enabled(),
+      targets: Default::default(),
+      tags: Default::default(),
+      start_ts: Default::default(),
+      end_ts: Default::default(),
+      schedule: Default::default(),
+      duration_minutes: Default::default(),
+      schedule_timezone: Default::default(),
+    }
+  }
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+fn is_zero(ts: &I64) -> bool {
+  *ts == 0
+}
@@ -42,8 +42,12 @@ pub mod deployment;
 pub mod docker;
 /// Subtypes of [LogConfig][logger::LogConfig].
 pub mod logger;
+/// Subtypes of [MaintenanceWindow][maintenance::MaintenanceWindow].
+pub mod maintenance;
 /// Subtypes of [Permission][permission::Permission].
 pub mod permission;
+/// Subtypes of [EndpointProbe][probe::EndpointProbe].
+pub mod probe;
 /// Subtypes of [Procedure][procedure::Procedure].
 pub mod procedure;
 /// Subtypes of [GitProviderAccount][provider::GitProviderAccount] and [DockerRegistryAccount][provider::DockerRegistryAccount]
@@ -367,6 +371,47 @@ pub fn environment_vars_from_str(
   })
 }
 
+/// A typed parameter declared by a Procedure or Action,
+/// which can be given an argument at run time.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Parameter {
+  /// The parameter name, eg. `TAG`.
+  /// Procedures reference it in executions as `[[ARG_TAG]]`.
+  pub name: String,
+  /// The type the argument must parse as.
+  #[serde(default, rename = "type")]
+  pub kind: ParameterType,
+  /// The value used when no argument is given.
+  /// If not provided, the argument is required.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub default: Option<String>,
+  /// Describe the parameter to the user.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub description: String,
+}
+
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+)]
+pub enum ParameterType {
+  #[default]
+  String,
+  Number,
+  Boolean,
+}
+
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LatestCommit {
@@ -775,6 +820,15 @@ pub enum Operation {
   UpdateVariableValue,
   DeleteVariable,
 
+  // alert
+  AcknowledgeAlert,
+  SnoozeAlert,
+
+  // maintenance window
+  CreateMaintenanceWindow,
+  UpdateMaintenanceWindow,
+  DeleteMaintenanceWindow,
+
   // git provider
   CreateGitProviderAccount,
   UpdateGitProviderAccount,
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+
+use super::{ResourceTarget, I64, U64};
+
+/// An uptime check on a Deployment or Stack, run periodically by Core.
+/// Failing probes send an `EndpointDown` alert,
+/// and an `EndpointRecovered` alert once they pass again.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointProbe {
+  /// The endpoint to probe.
+  /// - `http://` / `https://` urls are probed with a GET request.
+  /// - Anything else is treated as `host:port`,
+  ///   and probed by opening a TCP connection.
+  pub endpoint: String,
+
+  /// The expected HTTP response status.
+  /// 0 accepts any status below 400. Ignored for TCP probes.
+  /// Default: 200
+  #[serde(default = "default_expected_status")]
+  pub expected_status: u16,
+
+  /// The number of seconds between probes.
+  /// Default: 60
+  #[serde(default = "default_interval_secs")]
+  pub interval_secs: U64,
+
+  /// The number of seconds to wait for a response
+  /// before the probe fails.
+  /// Clamped between 1 and `interval_secs`.
+  /// Default: 10
+  #[serde(default = "default_timeout_secs")]
+  pub timeout_secs: U64,
+}
+
+fn default_expected_status() -> u16 {
+  200
+}
+
+fn default_interval_secs() -> U64 {
+  60
+}
+
+fn default_timeout_secs() -> U64 {
+  10
+}
+
+impl Default for EndpointProbe {
+  fn default() -> Self {
+    Self {
+      endpoint: Default::default(),
+      expected_status: default_expected_status(),
+      interval_secs: default_interval_secs(),
+      timeout_secs: default_timeout_secs(),
+    }
+  }
+}
+
+impl EndpointProbe {
+  /// Whether the endpoint is probed over HTTP, rather than TCP.
+  pub fn is_http(&self) -> bool {
+    self.endpoint.starts_with("http://")
+      || self.endpoint.starts_with("https://")
+  }
+}
+
+/// The result of an [EndpointProbe] stored on the database.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", collection_name(Probes))]
+#[cfg_attr(feature = "mongo", doc_index({ "target.type": 1 }))]
+#[cfg_attr(feature = "mongo", doc_index({ "target.id": 1 }))]
+pub struct ProbeRecord {
+  /// Unix timestamp in milliseconds
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+  /// The Deployment / Stack the probe is configured on
+  pub target: ResourceTarget,
+  /// The probed endpoint
+  #[cfg_attr(feature = "mongo", index)]
+  pub endpoint: String,
+  /// Whether the probe passed
+  pub success: bool,
+  /// The HTTP response status, if one was received
+  pub status: Option<u16>,
+  /// The time taken to get a response / connect in milliseconds.
+  pub response_time_ms: I64,
+  /// The reason for the failure
+  pub error: Option<String>,
+}
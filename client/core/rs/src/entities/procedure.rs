@@ -9,8 +9,11 @@ use typeshare::typeshare;
 use crate::api::execute::Execution;
 
 use super::{
+  build::BuildState,
+  deployment::DeploymentState,
   resource::{Resource, ResourceListItem, ResourceQuery},
-  I64,
+  stack::StackState,
+  MongoId, Parameter, I64,
 };
 
 #[typeshare]
@@ -23,6 +26,13 @@ pub struct ProcedureListItemInfo {
   pub stages: I64,
   /// Reflect whether last run successful / currently running.
   pub state: ProcedureState,
+  /// Unix timestamp in ms of the last time the procedure was run.
+  pub last_run_at: Option<I64>,
+  /// If the procedure has a schedule enabled,
+  /// the unix timestamp in ms of the next scheduled run.
+  pub next_scheduled_run: Option<I64>,
+  /// If there is an error parsing the schedule, it will be given here.
+  pub schedule_error: Option<String>,
 }
 
 #[typeshare]
@@ -61,6 +71,12 @@ pub struct ProcedureConfig {
   #[builder(default)]
   pub stages: Vec<ProcedureStage>,
 
+  /// Typed parameters which can be given arguments when run.
+  #[serde(default, alias = "parameter")]
+  #[partial_attr(serde(alias = "parameter"))]
+  #[builder(default)]
+  pub parameters: Vec<Parameter>,
+
   /// Whether incoming webhooks actually trigger action.
   #[serde(default = "default_webhook_enabled")]
   #[builder(default = "default_webhook_enabled()")]
@@ -72,6 +88,45 @@ pub struct ProcedureConfig {
   #[serde(default)]
   #[builder(default)]
   pub webhook_secret: String,
+
+  /// Map fields of the incoming webhook payload into arguments.
+  /// One per line as `PARAMETER = path.to.field`,
+  /// eg. `TAG = head_commit.id`. Array items are indexed like `commits.0.id`.
+  #[serde(default)]
+  #[builder(default)]
+  pub webhook_args: String,
+
+  /// Choose whether to specify the schedule as a Cron expression,
+  /// or as a fixed interval.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule_format: ScheduleFormat,
+
+  /// Optionally provide a schedule for the procedure to run on.
+  ///
+  /// - `Cron`: A cron expression, with optional leading seconds field.
+  ///   eg. `0 0 * * *` (daily at midnight), `0 30 2 * * SUN` (sundays at 02:30:00).
+  /// - `Interval`: A fixed interval, aligned to the unix epoch.
+  ///   eg. `15-min`, `1-hr`, `12-hr`, `1-day`.
+  ///
+  /// If empty, the procedure will not be scheduled.
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule: String,
+
+  /// Whether the schedule is enabled, if one is provided.
+  /// Can be used to temporarily disable the schedule.
+  #[serde(default = "default_schedule_enabled")]
+  #[builder(default = "default_schedule_enabled()")]
+  #[partial_default(default_schedule_enabled())]
+  pub schedule_enabled: bool,
+
+  /// Optional. A TZ identifier used to evaluate `Cron` schedules,
+  /// eg. `America/New_York`. If empty, uses the Core local timezone.
+  /// https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+  #[serde(default)]
+  #[builder(default)]
+  pub schedule_timezone: String,
 }
 
 impl ProcedureConfig {
@@ -84,16 +139,47 @@ fn default_webhook_enabled() -> bool {
   true
 }
 
+fn default_schedule_enabled() -> bool {
+  true
+}
+
 impl Default for ProcedureConfig {
   fn default() -> Self {
     Self {
       stages: Default::default(),
+      parameters: Default::default(),
       webhook_enabled: default_webhook_enabled(),
       webhook_secret: Default::default(),
+      webhook_args: Default::default(),
+      schedule_format: Default::default(),
+      schedule: Default::default(),
+      schedule_enabled: default_schedule_enabled(),
+      schedule_timezone: Default::default(),
     }
   }
 }
 
+/// The format used to specify a [ProcedureConfig] schedule.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+)]
+pub enum ScheduleFormat {
+  /// Cron expression, eg. `0 0 * * *`
+  #[default]
+  Cron,
+  /// Fixed interval, eg. `1-hr`
+  Interval,
+}
+
 /// A single stage of a procedure. Runs a list of executions in parallel.
 #[typeshare]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -106,6 +192,92 @@ pub struct ProcedureStage {
   /// The executions in the stage
   #[serde(default, alias = "execution")]
   pub executions: Vec<EnabledExecution>,
+  /// What to do when an execution in the stage fails.
+  /// Can be overridden on each execution.
+  /// Default: `Abort`
+  #[serde(default)]
+  pub on_failure: ProcedureFailurePolicy,
+  /// Retry failed executions this many times before they count as failed,
+  /// waiting 5s, 10s, 20s, ... between attempts. At most 10.
+  /// Can be overridden on each execution.
+  #[serde(default)]
+  pub retries: I64,
+  /// Cancel executions taking longer than this many seconds,
+  /// and count them as failed. 0 means no timeout. At most 1 day (86400).
+  /// Can be overridden on each execution.
+  #[serde(default)]
+  pub timeout_seconds: I64,
+  /// Mark this as a cleanup stage. Cleanup stages are skipped in normal runs,
+  /// and only run after an execution fails with `on_failure: Cleanup`.
+  #[serde(default)]
+  pub cleanup: bool,
+  /// Run at most this many executions of the stage at once,
+  /// after Batch executions are expanded. 0 means no limit.
+  #[serde(default)]
+  pub max_concurrency: I64,
+  /// Run at most this many executions at once on each Server.
+  /// Only applies to executions targeting a Server, Deployment, Stack, or Repo.
+  /// 0 means no limit.
+  #[serde(default)]
+  pub max_concurrency_per_server: I64,
+  /// Only run the stage if this condition passes when the stage is reached.
+  /// Otherwise the stage is skipped. If empty, the stage always runs.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub condition: Option<StageCondition>,
+}
+
+/// A condition checked before running a [ProcedureStage].
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "params")]
+pub enum StageCondition {
+  /// Passes if the previous stage had this result.
+  /// There is no previous stage for the first stage, so it won't pass.
+  PreviousStage { result: StageResult },
+  /// Passes if the Stack is in this state.
+  StackState {
+    /// Id or name
+    stack: String,
+    state: StackState,
+  },
+  /// Passes if the Stack compose files have changed since
+  /// it was last deployed, or it has never been deployed.
+  StackChanged {
+    /// Id or name
+    stack: String,
+  },
+  /// Passes if the Deployment is in this state.
+  DeploymentState {
+    /// Id or name
+    deployment: String,
+    state: DeploymentState,
+  },
+  /// Passes if the Build is in this state.
+  BuildState {
+    /// Id or name
+    build: String,
+    state: BuildState,
+  },
+  /// Passes if the Variable has this value.
+  Variable {
+    /// The name of the Variable
+    name: String,
+    value: String,
+  },
+}
+
+/// The result of running a [ProcedureStage].
+#[typeshare]
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display,
+)]
+pub enum StageResult {
+  /// All the executions succeeded.
+  Succeeded,
+  /// Some executions failed with `on_failure: Continue`.
+  Failed,
+  /// The stage was disabled, or its condition didn't pass.
+  Skipped,
 }
 
 /// Allows to enable / disabled procedures in the sequence / parallel vec on the fly
@@ -117,6 +289,39 @@ pub struct EnabledExecution {
   /// Whether the execution is enabled to run in the procedure.
   #[serde(default = "default_enabled")]
   pub enabled: bool,
+  /// Override the stage `on_failure` for this execution.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub on_failure: Option<ProcedureFailurePolicy>,
+  /// Override the stage `retries` for this execution.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub retries: Option<I64>,
+  /// Override the stage `timeout_seconds` for this execution.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub timeout_seconds: Option<I64>,
+}
+
+/// What to do when a procedure execution fails,
+/// after any retries.
+#[typeshare]
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+  Display,
+)]
+pub enum ProcedureFailurePolicy {
+  /// Fail the procedure once the current stage finishes.
+  #[default]
+  Abort,
+  /// Log the failure and continue with the procedure.
+  Continue,
+  /// Run the cleanup stages, then fail the procedure.
+  Cleanup,
 }
 
 fn default_enabled() -> bool {
@@ -129,6 +334,83 @@ pub struct ProcedureActionState {
   pub running: bool,
 }
 
+/// A record of a single procedure run,
+/// with the timing and result of each stage and execution.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", doc_index({ "procedure_id": 1, "start_ts": -1 }))]
+pub struct ProcedureRun {
+  /// The Mongo ID of the run.
+  /// This field is de/serialized from/to JSON as
+  /// `{ "_id": { "$oid": "..." }, ...(rest of serialized ProcedureRun) }`
+  #[serde(
+    default,
+    rename = "_id",
+    skip_serializing_if = "String::is_empty",
+    with = "bson::serde_helpers::hex_string_as_object_id"
+  )]
+  pub id: MongoId,
+  /// The id of the procedure which was run.
+  pub procedure_id: String,
+  /// The id of the `RunProcedure` update.
+  #[cfg_attr(feature = "mongo", index)]
+  pub update_id: String,
+  /// Unix timestamp in ms the run started.
+  pub start_ts: I64,
+  /// Unix timestamp in ms the run finished.
+  /// Null while the run is in progress.
+  pub end_ts: Option<I64>,
+  /// `Running` while in progress, then `Ok` or `Failed`.
+  pub state: ProcedureState,
+  /// The stages reached by the run, in order.
+  /// Cleanup stages are included after the stage which failed.
+  pub stages: Vec<ProcedureStageRun>,
+}
+
+/// The record of a stage in a [ProcedureRun].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcedureStageRun {
+  /// The stage name.
+  pub name: String,
+  /// Whether this is a cleanup stage.
+  pub cleanup: bool,
+  /// The result of the stage.
+  pub result: StageResult,
+  /// Unix timestamp in ms the stage started.
+  pub start_ts: I64,
+  /// Unix timestamp in ms the stage finished.
+  pub end_ts: I64,
+  /// The executions run by the stage,
+  /// with Batch executions expanded.
+  pub executions: Vec<ProcedureExecutionRun>,
+}
+
+/// The record of an execution in a [ProcedureStageRun].
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProcedureExecutionRun {
+  /// The execution which was run.
+  pub execution: Execution,
+  /// The id of the update created by the execution, if any.
+  /// With retries, this is the update of the last attempt.
+  pub update_id: Option<String>,
+  /// The number of attempts made, including retries.
+  pub attempts: I64,
+  /// Unix timestamp in ms the execution started.
+  pub start_ts: I64,
+  /// Unix timestamp in ms the execution finished.
+  pub end_ts: I64,
+  /// Whether the execution succeeded.
+  pub success: bool,
+  /// The error, if the execution failed.
+  pub error: Option<String>,
+}
+
 // QUERY
 
 #[typeshare]
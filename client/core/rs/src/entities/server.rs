@@ -1,8 +1,12 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+};
 
 use derive_builder::Builder;
 use partial_derive2::Partial;
 use serde::{Deserialize, Serialize};
+use strum::Display;
 use typeshare::typeshare;
 
 use crate::deserializers::{
@@ -94,6 +98,13 @@ pub struct ServerConfig {
   #[partial_default(default_stats_monitoring())]
   pub stats_monitoring: bool,
 
+  /// Whether to store historical per-container stats,
+  /// retained for as long as the server stats.
+  /// default: false
+  #[serde(default)]
+  #[builder(default)]
+  pub record_container_stats: bool,
+
   /// Whether to trigger 'docker image prune -a -f' every 24 hours.
   /// default: true
   #[serde(default = "default_auto_prune")]
@@ -134,6 +145,18 @@ pub struct ServerConfig {
   #[partial_default(default_send_alerts())]
   pub send_disk_alerts: bool,
 
+  /// Whether to send alerts about the servers NETWORK throughput
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_network_alerts: bool,
+
+  /// Whether to send alerts about missing required processes
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_process_alerts: bool,
+
   /// The percentage threshhold which triggers WARNING state for CPU.
   #[serde(default = "default_cpu_warning")]
   #[builder(default = "default_cpu_warning()")]
@@ -169,12 +192,59 @@ pub struct ServerConfig {
   #[builder(default = "default_disk_critical()")]
   #[partial_default(default_disk_critical())]
   pub disk_critical: f64,
+
+  /// Override the disk thresholds for specific mount points.
+  /// Mounts without an override use `disk_warning` / `disk_critical`.
+  #[serde(default)]
+  #[builder(default)]
+  pub disk_thresholds: Vec<DiskThreshold>,
+
+  /// The throughput threshhold in MB/s, on any single network interface,
+  /// which triggers WARNING state for NETWORK. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub network_warning: f64,
+
+  /// The throughput threshhold in MB/s, on any single network interface,
+  /// which triggers CRITICAL state for NETWORK. 0 to disable.
+  #[serde(default)]
+  #[builder(default)]
+  pub network_critical: f64,
+
+  /// How long the throughput must stay above the threshhold
+  /// before a NETWORK alert is opened.
+  /// default: 5
+  #[serde(default = "default_network_sustained_minutes")]
+  #[builder(default = "default_network_sustained_minutes()")]
+  #[partial_default(default_network_sustained_minutes())]
+  pub network_sustained_minutes: I64,
+
+  /// Process names which should always be running on the server,
+  /// eg. `sshd` or `tailscaled`. A 'Critical' alert is opened
+  /// if any of them are missing from the process list.
+  #[serde(default, deserialize_with = "string_list_deserializer")]
+  #[partial_attr(serde(
+    default,
+    deserialize_with = "option_string_list_deserializer"
+  ))]
+  #[builder(default)]
+  pub required_processes: Vec<String>,
 }
 
 impl ServerConfig {
   pub fn builder() -> ServerConfigBuilder {
     ServerConfigBuilder::default()
   }
+
+  /// Get the (warning, critical) disk thresholds for the mount point.
+  pub fn disk_thresholds_for(&self, mount: &Path) -> (f64, f64) {
+    self
+      .disk_thresholds
+      .iter()
+      .find(|threshold| threshold.mount == mount)
+      .map(|threshold| (threshold.warning, threshold.critical))
+      .unwrap_or((self.disk_warning, self.disk_critical))
+  }
 }
 
 fn default_address() -> String {
@@ -225,6 +295,10 @@ fn default_disk_critical() -> f64 {
   95.0
 }
 
+fn default_network_sustained_minutes() -> I64 {
+  5
+}
+
 impl Default for ServerConfig {
   fn default() -> Self {
     Self {
@@ -233,12 +307,15 @@ impl Default for ServerConfig {
       timeout_seconds: default_timeout_seconds(),
       ignore_mounts: Default::default(),
       stats_monitoring: default_stats_monitoring(),
+      record_container_stats: Default::default(),
       auto_prune: default_auto_prune(),
       links: Default::default(),
       send_unreachable_alerts: default_send_alerts(),
       send_cpu_alerts: default_send_alerts(),
       send_mem_alerts: default_send_alerts(),
       send_disk_alerts: default_send_alerts(),
+      send_network_alerts: default_send_alerts(),
+      send_process_alerts: default_send_alerts(),
       region: Default::default(),
       cpu_warning: default_cpu_warning(),
       cpu_critical: default_cpu_critical(),
@@ -246,10 +323,29 @@ impl Default for ServerConfig {
       mem_critical: default_mem_critical(),
       disk_warning: default_disk_warning(),
       disk_critical: default_disk_critical(),
+      disk_thresholds: Default::default(),
+      network_warning: Default::default(),
+      network_critical: Default::default(),
+      network_sustained_minutes: default_network_sustained_minutes(),
+      required_processes: Default::default(),
     }
   }
 }
 
+/// Disk usage thresholds for a specific mount point.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct DiskThreshold {
+  /// The mount point, eg. `/` or `/mnt/data`.
+  pub mount: PathBuf,
+  /// The percentage threshhold which triggers WARNING state.
+  pub warning: f64,
+  /// The percentage threshhold which triggers CRITICAL state.
+  pub critical: f64,
+}
+
 /// The health of a part of the server.
 #[typeshare]
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -266,6 +362,13 @@ pub struct ServerHealth {
   pub cpu: ServerHealthState,
   pub mem: ServerHealthState,
   pub disks: HashMap<PathBuf, ServerHealthState>,
+  /// Throughput health by network interface name.
+  #[serde(default)]
+  pub network: HashMap<String, ServerHealthState>,
+  /// Health of the required processes by process name.
+  /// Only present when the process list could be retrieved.
+  #[serde(default)]
+  pub processes: HashMap<String, ServerHealthState>,
 }
 
 /// Current pending actions on the server.
@@ -309,6 +412,7 @@ pub struct ServerActionState {
   Clone,
   Copy,
   Default,
+  Display,
 )]
 pub enum ServerState {
   /// Server is unreachable.
@@ -16,9 +16,10 @@ use crate::deserializers::{
 };
 
 use super::{
-  docker::container::ContainerListItem,
+  docker::container::{ContainerListItem, HealthStatusEnum},
+  probe::EndpointProbe,
   resource::{Resource, ResourceListItem, ResourceQuery},
-  to_komodo_name, FileContents, SystemCommand,
+  to_komodo_name, FileContents, SystemCommand, I64,
 };
 
 #[typeshare]
@@ -103,6 +104,8 @@ pub struct StackServiceWithUpdate {
   pub image: String,
   /// Whether there is a newer image available for this service
   pub update_available: bool,
+  /// The health check status of the service container
+  pub health: HealthStatusEnum,
 }
 
 #[typeshare]
@@ -367,6 +370,38 @@ pub struct StackConfig {
   #[partial_default(default_send_alerts())]
   pub send_alerts: bool,
 
+  /// Whether to send ContainerRestartLoop alerts for this stack.
+  /// Requires `send_alerts` to also be enabled.
+  #[serde(default = "default_send_alerts")]
+  #[builder(default = "default_send_alerts()")]
+  #[partial_default(default_send_alerts())]
+  pub send_restart_loop_alerts: bool,
+
+  /// Send a ContainerRestartLoop alert when a container restarts
+  /// more than this many times within `restart_loop_window_minutes`.
+  #[serde(default = "default_restart_loop_threshold")]
+  #[builder(default = "default_restart_loop_threshold()")]
+  #[partial_default(default_restart_loop_threshold())]
+  pub restart_loop_threshold: I64,
+
+  /// The window used to count container restarts, in minutes.
+  #[serde(default = "default_restart_loop_window_minutes")]
+  #[builder(default = "default_restart_loop_window_minutes()")]
+  #[partial_default(default_restart_loop_window_minutes())]
+  pub restart_loop_window_minutes: I64,
+
+  /// Per service cpu / memory alert thresholds.
+  /// Services without an entry don't send ContainerCpu / ContainerMem alerts.
+  #[serde(default)]
+  #[builder(default)]
+  pub service_thresholds: Vec<StackServiceThresholds>,
+
+  /// HTTP / TCP uptime checks on the stack.
+  /// Failing probes send EndpointDown alerts.
+  #[serde(default)]
+  #[builder(default)]
+  pub probes: Vec<EndpointProbe>,
+
   /// Used with `registry_account` to login to a registry before docker compose up.
   #[serde(default)]
   #[builder(default)]
@@ -473,6 +508,14 @@ fn default_send_alerts() -> bool {
   true
 }
 
+fn default_restart_loop_threshold() -> I64 {
+  3
+}
+
+fn default_restart_loop_window_minutes() -> I64 {
+  10
+}
+
 impl Default for StackConfig {
   fn default() -> Self {
     Self {
@@ -508,11 +551,39 @@ impl Default for StackConfig {
       webhook_secret: Default::default(),
       webhook_force_deploy: Default::default(),
       send_alerts: default_send_alerts(),
+      send_restart_loop_alerts: default_send_alerts(),
+      restart_loop_threshold: default_restart_loop_threshold(),
+      restart_loop_window_minutes:
+        default_restart_loop_window_minutes(),
+      service_thresholds: Default::default(),
+      probes: Default::default(),
       links: Default::default(),
     }
   }
 }
 
+/// Cpu / memory alert thresholds for a stack service.
+#[typeshare]
+#[derive(
+  Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct StackServiceThresholds {
+  /// The service name
+  pub service: String,
+  /// The cpu usage in % which triggers a 'Warning' alert. 0 to disable.
+  #[serde(default)]
+  pub cpu_warning: f64,
+  /// The cpu usage in % which triggers a 'Critical' alert. 0 to disable.
+  #[serde(default)]
+  pub cpu_critical: f64,
+  /// The memory usage in MB which triggers a 'Warning' alert. 0 to disable.
+  #[serde(default)]
+  pub mem_warning_mb: f64,
+  /// The memory usage in MB which triggers a 'Critical' alert. 0 to disable.
+  #[serde(default)]
+  pub mem_critical_mb: f64,
+}
+
 #[typeshare]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ComposeProject {
@@ -51,15 +51,130 @@ pub struct SystemStatsRecord {
   pub disk_total_gb: f64,
   /// Breakdown of individual disks, ie their usages, sizes, and mount points
   pub disks: Vec<SingleDiskUsage>,
-   /// Network ingress usage in bytes
-   #[serde(default)]
-   pub network_ingress_bytes: f64,
-   /// Network egress usage in bytes
-   #[serde(default)]
-   pub network_egress_bytes: f64,
-   /// Network usage by interface name (ingress, egress in bytes)
-   #[serde(default)]
-   pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Network ingress usage in bytes
+  #[serde(default)]
+  pub network_ingress_bytes: f64,
+  /// Network egress usage in bytes
+  #[serde(default)]
+  pub network_egress_bytes: f64,
+  /// Network usage by interface name (ingress, egress in bytes)
+  #[serde(default)]
+  pub network_usage_interface: Vec<SingleNetworkInterfaceUsage>, // interface -> (ingress, egress)
+  /// Only present on records served from [SystemStatsRollup]s,
+  /// where the other values are averages over the period.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub max: Option<SystemStatsMax>,
+}
+
+/// Downsampled system stats stored on the database.
+/// These are written periodically from the raw [SystemStatsRecord]s,
+/// and kept around for longer to show long term trends.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", unique_doc_index({ "sid": 1, "granularity": 1, "ts": 1 }))]
+#[cfg_attr(feature = "mongo", collection_name(StatsRollup))]
+pub struct SystemStatsRollup {
+  /// Unix timestamp in milliseconds of the start of the period
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+  /// Server id
+  pub sid: String,
+  /// The length of the period, `5-min` or `1-hr`
+  pub granularity: Timelength,
+  /// The number of raw stats in the period
+  pub samples: I64,
+  /// Average cpu usage percentage
+  pub cpu_perc: f32,
+  /// Average memory used in GB
+  pub mem_used_gb: f64,
+  /// Total memory in GB
+  pub mem_total_gb: f64,
+  /// Average disk used in GB
+  pub disk_used_gb: f64,
+  /// Total disk size in GB
+  pub disk_total_gb: f64,
+  /// Average network ingress usage in bytes
+  pub network_ingress_bytes: f64,
+  /// Average network egress usage in bytes
+  pub network_egress_bytes: f64,
+  /// The max values over the period
+  pub max: SystemStatsMax,
+}
+
+/// Max system stats over a period.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SystemStatsMax {
+  /// Max cpu usage percentage
+  pub cpu_perc: f32,
+  /// Max memory used in GB
+  pub mem_used_gb: f64,
+  /// Max disk used in GB
+  pub disk_used_gb: f64,
+  /// Max network ingress usage in bytes
+  pub network_ingress_bytes: f64,
+  /// Max network egress usage in bytes
+  pub network_egress_bytes: f64,
+}
+
+impl From<SystemStatsRollup> for SystemStatsRecord {
+  fn from(rollup: SystemStatsRollup) -> Self {
+    SystemStatsRecord {
+      ts: rollup.ts,
+      sid: rollup.sid,
+      cpu_perc: rollup.cpu_perc,
+      mem_used_gb: rollup.mem_used_gb,
+      mem_total_gb: rollup.mem_total_gb,
+      disk_used_gb: rollup.disk_used_gb,
+      disk_total_gb: rollup.disk_total_gb,
+      disks: Vec::new(),
+      network_ingress_bytes: rollup.network_ingress_bytes,
+      network_egress_bytes: rollup.network_egress_bytes,
+      network_usage_interface: Vec::new(),
+      max: Some(rollup.max),
+    }
+  }
+}
+
+/// Container stats stored on the database.
+/// Only recorded for servers with `record_container_stats` enabled.
+#[typeshare]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(
+  feature = "mongo",
+  derive(mongo_indexed::derive::MongoIndexed)
+)]
+#[cfg_attr(feature = "mongo", collection_name(ContainerStats))]
+pub struct ContainerStatsRecord {
+  /// Unix timestamp in milliseconds
+  #[cfg_attr(feature = "mongo", index)]
+  pub ts: I64,
+  /// Server id
+  #[cfg_attr(feature = "mongo", index)]
+  pub sid: String,
+  /// The container name
+  #[cfg_attr(feature = "mongo", index)]
+  pub name: String,
+  /// Cpu usage percentage
+  pub cpu_perc: f64,
+  /// Memory usage percentage of the limit
+  pub mem_perc: f64,
+  /// Memory used in bytes
+  pub mem_used_bytes: f64,
+  /// Memory limit in bytes
+  pub mem_limit_bytes: f64,
+  /// Total network received by the container in bytes
+  pub net_rx_bytes: f64,
+  /// Total network sent by the container in bytes
+  pub net_tx_bytes: f64,
+  /// Total block io read by the container in bytes
+  pub block_read_bytes: f64,
+  /// Total block io written by the container in bytes
+  pub block_write_bytes: f64,
 }
 
 /// Realtime system stats data.
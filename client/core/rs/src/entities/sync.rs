@@ -89,6 +89,9 @@ pub struct ResourceSyncInfo {
   /// The list of pending updates to user groups
   #[serde(default)]
   pub user_group_updates: Vec<DiffData>,
+  /// The list of pending updates to maintenance windows
+  #[serde(default)]
+  pub maintenance_window_updates: Vec<DiffData>,
   /// The list of pending deploys to resources.
   #[serde(default)]
   pub pending_deploy: SyncDeployUpdate,
@@ -6,7 +6,8 @@ use typeshare::typeshare;
 use super::{
   action::_PartialActionConfig, alerter::_PartialAlerterConfig,
   build::_PartialBuildConfig, builder::_PartialBuilderConfig,
-  deployment::_PartialDeploymentConfig, permission::PermissionLevel,
+  deployment::_PartialDeploymentConfig,
+  maintenance::MaintenanceWindow, permission::PermissionLevel,
   procedure::_PartialProcedureConfig, repo::_PartialRepoConfig,
   server::_PartialServerConfig,
   server_template::PartialServerTemplateConfig,
@@ -109,6 +110,13 @@ pub struct ResourcesToml {
     skip_serializing_if = "Vec::is_empty"
   )]
   pub variables: Vec<Variable>,
+
+  #[serde(
+    default,
+    alias = "maintenance_window",
+    skip_serializing_if = "Vec::is_empty"
+  )]
+  pub maintenance_windows: Vec<MaintenanceWindow>,
 }
 
 #[typeshare]
@@ -48,6 +48,7 @@ pub struct Update {
   /// - `Procedure`: The operation was triggered as part of a procedure run
   /// - `Github`: The operation was triggered by a github webhook
   /// - `Auto Redeploy`: The operation (always `Deploy`) was triggered by an attached build finishing.
+  /// - `Schedule`: The operation (always `RunProcedure`) was triggered by the procedure schedule.
   #[cfg_attr(feature = "mongo", index)]
   pub operator: String,
 
@@ -122,6 +123,7 @@ pub struct UpdateListItem {
   /// - `Procedure`: The operation was triggered as part of a procedure run
   /// - `Github`: The operation was triggered by a github webhook
   /// - `Auto Redeploy`: The operation (always `Deploy`) was triggered by an attached build finishing.
+  /// - `Schedule`: The operation (always `RunProcedure`) was triggered by the procedure schedule.
   pub operator: String,
   /// The target resource to which this update refers
   pub target: ResourceTarget,
@@ -104,6 +104,8 @@ impl User {
         | "000000000000000000000007"
         | "Repo Manager"
         | "000000000000000000000008"
+        | "Schedule"
+        | "000000000000000000000009"
     )
   }
 }
@@ -137,6 +139,9 @@ pub fn admin_service_user(user_id: &str) -> Option<User> {
     "000000000000000000000008" | "Repo Manager" => {
       repo_user().to_owned().into()
     }
+    "000000000000000000000009" | "Schedule" => {
+      schedule_user().to_owned().into()
+    }
     _ => None,
   }
 }
@@ -267,6 +272,20 @@ pub fn repo_user() -> &'static User {
   })
 }
 
+pub fn schedule_user() -> &'static User {
+  static SCHEDULE_USER: OnceLock<User> = OnceLock::new();
+  SCHEDULE_USER.get_or_init(|| {
+    let id_name = String::from("Schedule");
+    User {
+      id: "000000000000000000000009".to_string(),
+      username: id_name,
+      enabled: true,
+      admin: true,
+      ..Default::default()
+    }
+  })
+}
+
 #[typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
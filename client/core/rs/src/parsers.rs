@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::Context;
 
 pub const QUOTE_PATTERN: &[char] = &['"', '\''];
@@ -93,6 +95,57 @@ pub fn parse_key_value_list(
     .collect::<anyhow::Result<Vec<_>>>()
 }
 
+/// Parses run time arguments given on the command line,
+/// eg. `TAG=1.2.0,DRY_RUN=true`.
+///
+/// Pairs are separated by newlines, or by commas followed by
+/// the next `NAME=`, so values like `NOTE=a,b` keep their commas.
+/// Wrap a value in double quotes to keep everything in it as is,
+/// eg. `FILTER="a,b=c"`. Otherwise supports the same formats
+/// as [parse_key_value_list].
+pub fn parse_arguments(
+  input: &str,
+) -> anyhow::Result<HashMap<String, String>> {
+  let mut lines = String::with_capacity(input.len());
+  let mut quoted = false;
+  for (i, c) in input.char_indices() {
+    match c {
+      '"' => quoted = !quoted,
+      ',' if !quoted && starts_with_argument(&input[i + 1..]) => {
+        lines.push('\n');
+        continue;
+      }
+      _ => {}
+    }
+    lines.push(c);
+  }
+  parse_key_value_list(&lines).map(|list| {
+    list
+      .into_iter()
+      .map(|(name, value)| {
+        let value = value
+          .strip_prefix('"')
+          .and_then(|value| value.strip_suffix('"'))
+          .map(str::to_string)
+          .unwrap_or(value);
+        (name, value)
+      })
+      .collect()
+  })
+}
+
+/// Whether the input starts with `NAME=`.
+fn starts_with_argument(input: &str) -> bool {
+  let Some((name, _)) = input.split_once('=') else {
+    return false;
+  };
+  let name = name.trim();
+  !name.is_empty()
+    && name
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 /// Parses commands out of multiline string
 /// and chains them together with '&&'
 ///
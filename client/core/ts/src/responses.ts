@@ -43,6 +43,8 @@ export type ReadResponses = {
   GetProceduresSummary: Types.GetProceduresSummaryResponse;
   GetProcedure: Types.GetProcedureResponse;
   GetProcedureActionState: Types.GetProcedureActionStateResponse;
+  GetProcedureRun: Types.GetProcedureRunResponse;
+  ListProcedureRuns: Types.ListProcedureRunsResponse;
   ListProcedures: Types.ListProceduresResponse;
   ListFullProcedures: Types.ListFullProceduresResponse;
 
@@ -80,6 +82,7 @@ export type ReadResponses = {
   ListComposeProjects: Types.ListComposeProjectsResponse;
   GetServerActionState: Types.GetServerActionStateResponse;
   GetHistoricalServerStats: Types.GetHistoricalServerStatsResponse;
+  GetHistoricalContainerStats: Types.GetHistoricalContainerStatsResponse;
   ListServers: Types.ListServersResponse;
   ListFullServers: Types.ListFullServersResponse;
 
@@ -162,6 +165,10 @@ export type ReadResponses = {
   // ==== ALERT ====
   ListAlerts: Types.ListAlertsResponse;
   GetAlert: Types.GetAlertResponse;
+  GetAlertStats: Types.GetAlertStatsResponse;
+
+  // ==== PROBE ====
+  ListProbeRecords: Types.ListProbeRecordsResponse;
 
   // ==== SERVER STATS ====
   GetSystemInformation: Types.GetSystemInformationResponse;
@@ -172,6 +179,10 @@ export type ReadResponses = {
   GetVariable: Types.GetVariableResponse;
   ListVariables: Types.ListVariablesResponse;
 
+  // ==== MAINTENANCE WINDOW ====
+  GetMaintenanceWindow: Types.GetMaintenanceWindowResponse;
+  ListMaintenanceWindows: Types.ListMaintenanceWindowsResponse;
+
   // ==== PROVIDER ====
   GetGitProviderAccount: Types.GetGitProviderAccountResponse;
   ListGitProviderAccounts: Types.ListGitProviderAccountsResponse;
@@ -258,6 +269,10 @@ export type WriteResponses = {
   CreateRepoWebhook: Types.CreateRepoWebhookResponse;
   DeleteRepoWebhook: Types.DeleteRepoWebhookResponse;
 
+  // ==== ALERT ====
+  AcknowledgeAlert: Types.AcknowledgeAlertResponse;
+  SnoozeAlert: Types.SnoozeAlertResponse;
+
   // ==== ALERTER ====
   CreateAlerter: Types.Alerter;
   CopyAlerter: Types.Alerter;
@@ -315,6 +330,11 @@ export type WriteResponses = {
   UpdateVariableIsSecret: Types.UpdateVariableIsSecretResponse;
   DeleteVariable: Types.DeleteVariableResponse;
 
+  // ==== MAINTENANCE WINDOW ====
+  CreateMaintenanceWindow: Types.CreateMaintenanceWindowResponse;
+  UpdateMaintenanceWindow: Types.UpdateMaintenanceWindowResponse;
+  DeleteMaintenanceWindow: Types.DeleteMaintenanceWindowResponse;
+
   // ==== PROVIDERS ====
   CreateGitProviderAccount: Types.CreateGitProviderAccountResponse;
   UpdateGitProviderAccount: Types.UpdateGitProviderAccountResponse;
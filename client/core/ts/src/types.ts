@@ -10,6 +10,388 @@ export type MongoId = MongoIdObj;
 
 export type I64 = number;
 
+/** Severity level of problem. */
+export enum SeverityLevel {
+	/** No problem. */
+	Ok = "OK",
+	/** Problem is imminent. */
+	Warning = "WARNING",
+	/** Problem fully realized. */
+	Critical = "CRITICAL",
+}
+
+/** Used to reference a specific resource across all resource types */
+export type ResourceTarget = 
+	| { type: "System", id: string }
+	| { type: "Server", id: string }
+	| { type: "Stack", id: string }
+	| { type: "Deployment", id: string }
+	| { type: "Build", id: string }
+	| { type: "Repo", id: string }
+	| { type: "Procedure", id: string }
+	| { type: "Action", id: string }
+	| { type: "Builder", id: string }
+	| { type: "Alerter", id: string }
+	| { type: "ServerTemplate", id: string }
+	| { type: "ResourceSync", id: string };
+
+/** The variants of data related to the alert. */
+export type AlertData = 
+	/** A null alert */
+	| { type: "None", data: {
+}}
+	/** A server could not be reached. */
+	| { type: "ServerUnreachable", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The error data */
+	err?: _Serror;
+}}
+	/** A server has high CPU usage. */
+	| { type: "ServerCpu", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The cpu usage percentage */
+	percentage: number;
+}}
+	/** A server has high memory usage. */
+	| { type: "ServerMem", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The used memory */
+	used_gb: number;
+	/** The total memory */
+	total_gb: number;
+}}
+	/** A server has high disk usage. */
+	| { type: "ServerDisk", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The mount path of the disk */
+	path: string;
+	/** The used portion of the disk in GB */
+	used_gb: number;
+	/** The total size of the disk in GB */
+	total_gb: number;
+}}
+	/** A server has sustained high network throughput on an interface. */
+	| { type: "ServerNetwork", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The network interface name */
+	interface: string;
+	/** The ingress throughput in MB/s */
+	ingress_mb_per_sec: number;
+	/** The egress throughput in MB/s */
+	egress_mb_per_sec: number;
+}}
+	/** A required process is not running on a server. */
+	| { type: "ServerProcessMissing", data: {
+	/** The id of the server */
+	id: string;
+	/** The name of the server */
+	name: string;
+	/** The region of the server */
+	region?: string;
+	/** The name of the missing process */
+	process: string;
+}}
+	/** A container's state has changed unexpectedly. */
+	| { type: "ContainerStateChange", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The previous container state */
+	from: DeploymentState;
+	/** The current container state */
+	to: DeploymentState;
+}}
+	/** A Deployment or Stack container is failing its health check. */
+	| { type: "ContainerUnhealthy", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+	/** The stack service, if the container is part of a Stack */
+	service?: string;
+}}
+	/** A Deployment or Stack container is repeatedly restarting. */
+	| { type: "ContainerRestartLoop", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+	/** The stack service, if the container is part of a Stack */
+	service?: string;
+	/** The number of restarts within the window */
+	restarts: I64;
+	/** The window restarts are counted in, in minutes */
+	window_minutes: I64;
+}}
+	/** A Deployment or Stack container has high cpu usage. */
+	| { type: "ContainerCpu", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+	/** The stack service, if the container is part of a Stack */
+	service?: string;
+	/** The cpu usage percentage */
+	percentage: number;
+}}
+	/** A Deployment or Stack container has high memory usage. */
+	| { type: "ContainerMem", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The server id of server that the container is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The container name */
+	container: string;
+	/** The stack service, if the container is part of a Stack */
+	service?: string;
+	/** The memory used in MB */
+	used_mb: number;
+	/** The memory limit in MB */
+	limit_mb: number;
+}}
+	/** A Deployment has an image update available */
+	| { type: "DeploymentImageUpdateAvailable", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The image with update */
+	image: string;
+}}
+	/** A Deployment has an image update available */
+	| { type: "DeploymentAutoUpdated", data: {
+	/** The id of the deployment */
+	id: string;
+	/** The name of the deployment */
+	name: string;
+	/** The server id of server that the deployment is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The updated image */
+	image: string;
+}}
+	/** A stack's state has changed unexpectedly. */
+	| { type: "StackStateChange", data: {
+	/** The id of the stack */
+	id: string;
+	/** The name of the stack */
+	name: string;
+	/** The server id of server that the stack is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The previous stack state */
+	from: StackState;
+	/** The current stack state */
+	to: StackState;
+}}
+	/** A Stack has an image update available */
+	| { type: "StackImageUpdateAvailable", data: {
+	/** The id of the stack */
+	id: string;
+	/** The name of the stack */
+	name: string;
+	/** The server id of server that the stack is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** The service name to update */
+	service: string;
+	/** The image with update */
+	image: string;
+}}
+	/** A Stack was auto updated */
+	| { type: "StackAutoUpdated", data: {
+	/** The id of the stack */
+	id: string;
+	/** The name of the stack */
+	name: string;
+	/** The server id of server that the stack is on */
+	server_id: string;
+	/** The server name */
+	server_name: string;
+	/** One or more images that were updated */
+	images: string[];
+}}
+	/**
+	 * The TLS certificate served at one of a
+	 * Server / Deployment / Stack's https links is expiring soon.
+	 */
+	| { type: "CertificateExpiring", data: {
+	/** The id of the server / deployment / stack */
+	id: string;
+	/** The name of the server / deployment / stack */
+	name: string;
+	/** The probed https link */
+	url: string;
+	/** The certificate expiry (not after) timestamp in ms */
+	expires_ts: I64;
+	/**
+	 * The number of whole days until expiry.
+	 * Negative if the certificate has already expired.
+	 */
+	days_remaining: I64;
+}}
+	/** An endpoint probe on a Deployment / Stack is failing. */
+	| { type: "EndpointDown", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The probed endpoint */
+	endpoint: string;
+	/** The reason for the failure */
+	error: string;
+}}
+	/** A failing endpoint probe on a Deployment / Stack is passing again. */
+	| { type: "EndpointRecovered", data: {
+	/** The id of the deployment / stack */
+	id: string;
+	/** The name of the deployment / stack */
+	name: string;
+	/** The probed endpoint */
+	endpoint: string;
+	/** How long the endpoint was down in milliseconds */
+	down_ms: I64;
+}}
+	/** An AWS builder failed to terminate. */
+	| { type: "AwsBuilderTerminationFailed", data: {
+	/** The id of the aws instance which failed to terminate */
+	instance_id: string;
+	/** A reason for the failure */
+	message: string;
+}}
+	/** A resource sync has pending updates */
+	| { type: "ResourceSyncPendingUpdates", data: {
+	/** The id of the resource sync */
+	id: string;
+	/** The name of the resource sync */
+	name: string;
+}}
+	/** A build has failed */
+	| { type: "BuildFailed", data: {
+	/** The id of the build */
+	id: string;
+	/** The name of the build */
+	name: string;
+	/** The version that failed to build */
+	version: Version;
+}}
+	/** A repo has failed */
+	| { type: "RepoBuildFailed", data: {
+	/** The id of the repo */
+	id: string;
+	/** The name of the repo */
+	name: string;
+}};
+
+/** Marks an open alert as being handled. */
+export interface AlertAcknowledgement {
+	/** The id of the user who acknowledged the alert. */
+	user_id: string;
+	/** Unix timestamp in milliseconds the alert was acknowledged. */
+	ts: I64;
+}
+
+/** Representation of an alert in the system. */
+export interface Alert {
+	/**
+	 * The Mongo ID of the alert.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Alert) }`
+	 */
+	_id?: MongoId;
+	/** Unix timestamp in milliseconds the alert was opened */
+	ts: I64;
+	/** Whether the alert is already resolved */
+	resolved: boolean;
+	/** The severity of the alert */
+	level: SeverityLevel;
+	/** The target of the alert */
+	target: ResourceTarget;
+	/** The data attached to the alert */
+	data: AlertData;
+	/** The timestamp of alert resolution */
+	resolved_ts?: I64;
+	/**
+	 * Whether the alert was held back from the Alerters
+	 * by an active [MaintenanceWindow][super::maintenance::MaintenanceWindow].
+	 */
+	suppressed?: boolean;
+	/**
+	 * Alerter id -> unix timestamp in milliseconds
+	 * the alerter was last notified about this unresolved alert.
+	 * Used for re-notify and escalation.
+	 */
+	last_notified?: Record<string, I64>;
+	/** Who acknowledged the alert, and when. */
+	acknowledged?: AlertAcknowledgement;
+	/**
+	 * Unix timestamp in milliseconds until which the alert
+	 * will not be re-notified or escalated.
+	 */
+	snoozed_until?: I64;
+}
+
+export type AcknowledgeAlertResponse = Alert;
+
 /** The levels of permission that a User or UserGroup can have on a resource. */
 export enum PermissionLevel {
 	/** No permissions. */
@@ -51,9 +433,38 @@ export interface Resource<Config, Info> {
 	base_permission?: PermissionLevel;
 }
 
+export enum ParameterType {
+	String = "String",
+	Number = "Number",
+	Boolean = "Boolean",
+}
+
+/**
+ * A typed parameter declared by a Procedure or Action,
+ * which can be given an argument at run time.
+ */
+export interface Parameter {
+	/**
+	 * The parameter name, eg. `TAG`.
+	 * Procedures reference it in executions as `[[ARG_TAG]]`.
+	 */
+	name: string;
+	/** The type the argument must parse as. */
+	type?: ParameterType;
+	/**
+	 * The value used when no argument is given.
+	 * If not provided, the argument is required.
+	 */
+	default?: string;
+	/** Describe the parameter to the user. */
+	description?: string;
+}
+
 export interface ActionConfig {
 	/** Typescript file contents using pre-initialized `komodo` client. */
 	file_contents?: string;
+	/** Typed parameters which can be given arguments when run. */
+	parameters?: Parameter[];
 	/** Whether incoming webhooks actually trigger action. */
 	webhook_enabled: boolean;
 	/**
@@ -61,6 +472,12 @@ export interface ActionConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Map fields of the incoming webhook payload into arguments.
+	 * One per line as `PARAMETER = path.to.field`,
+	 * eg. `TAG = head_commit.id`. Array items are indexed like `commits.0.id`.
+	 */
+	webhook_args?: string;
 }
 
 export interface ActionInfo {
@@ -130,22 +547,21 @@ export type AlerterEndpoint =
 	/** Send alert to a Slack app */
 	| { type: "Slack", params: SlackAlerterEndpoint }
 	/** Send alert to a Discord app */
-	| { type: "Discord", params: DiscordAlerterEndpoint };
-
-/** Used to reference a specific resource across all resource types */
-export type ResourceTarget = 
-	| { type: "System", id: string }
-	| { type: "Server", id: string }
-	| { type: "Stack", id: string }
-	| { type: "Deployment", id: string }
-	| { type: "Build", id: string }
-	| { type: "Repo", id: string }
-	| { type: "Procedure", id: string }
-	| { type: "Action", id: string }
-	| { type: "Builder", id: string }
-	| { type: "Alerter", id: string }
-	| { type: "ServerTemplate", id: string }
-	| { type: "ResourceSync", id: string };
+	| { type: "Discord", params: DiscordAlerterEndpoint }
+	/** Send alert as an email over SMTP */
+	| { type: "Smtp", params: SmtpAlerterEndpoint }
+	/** Send alert as a push notification to a ntfy topic */
+	| { type: "Ntfy", params: NtfyAlerterEndpoint }
+	/** Send alert as a push notification to a Gotify server */
+	| { type: "Gotify", params: GotifyAlerterEndpoint };
+
+/** Forward alerts which stay unresolved to another alerter. */
+export interface AlerterEscalation {
+	/** Minutes the alert must stay unresolved before escalating. */
+	after_minutes: I64;
+	/** The id or name of the alerter to escalate to. */
+	alerter: string;
+}
 
 export interface AlerterConfig {
 	/** Whether the alerter is enabled */
@@ -168,6 +584,39 @@ export interface AlerterConfig {
 	resources?: ResourceTarget[];
 	/** DON'T send alerts on these resources. */
 	except_resources?: ResourceTarget[];
+	/**
+	 * Re-send unresolved alerts to this alerter
+	 * after this many minutes since it was last notified.
+	 * 0 means alerts are only sent once.
+	 */
+	renotify_interval_minutes?: I64;
+	/**
+	 * Also send unresolved alerts to other alerters
+	 * once they have been open for the configured time.
+	 */
+	escalations?: AlerterEscalation[];
+	/**
+	 * Optional [minijinja](https://docs.rs/minijinja) template
+	 * to shape the alert messages. If empty, the built in format is used.
+	 * 
+	 * - `Custom`: The rendered template is posted as the request body,
+	 * with content type `application/json` if it is valid JSON.
+	 * - Other endpoints: The rendered template is sent as the message.
+	 * Endpoints with a title / subject use the first line.
+	 * 
+	 * Available in the template:
+	 * - `alert`: The full [Alert][super::alert::Alert].
+	 * - `name`: The name of the alert target.
+	 * - `level`: The severity, `OK`, `WARNING`, or `CRITICAL`.
+	 * - `link`: Link to the alert target in the Komodo UI.
+	 * - `message`: The built in markdown message.
+	 * 
+	 * Example (Microsoft Teams):
+	 * ```text
+	 * { "text": {{ (level ~ " | " ~ name ~ "\n" ~ link) | tojson }} }
+	 * ```
+	 */
+	template?: string;
 }
 
 export type Alerter = Resource<AlerterConfig, undefined>;
@@ -486,14 +935,85 @@ export type Execution =
 	| { type: "BatchDestroyStack", params: BatchDestroyStack }
 	| { type: "Sleep", params: Sleep };
 
+/**
+ * What to do when a procedure execution fails,
+ * after any retries.
+ */
+export enum ProcedureFailurePolicy {
+	/** Fail the procedure once the current stage finishes. */
+	Abort = "Abort",
+	/** Log the failure and continue with the procedure. */
+	Continue = "Continue",
+	/** Run the cleanup stages, then fail the procedure. */
+	Cleanup = "Cleanup",
+}
+
 /** Allows to enable / disabled procedures in the sequence / parallel vec on the fly */
 export interface EnabledExecution {
 	/** The execution request to run. */
 	execution: Execution;
 	/** Whether the execution is enabled to run in the procedure. */
 	enabled: boolean;
+	/** Override the stage `on_failure` for this execution. */
+	on_failure?: ProcedureFailurePolicy;
+	/** Override the stage `retries` for this execution. */
+	retries?: I64;
+	/** Override the stage `timeout_seconds` for this execution. */
+	timeout_seconds?: I64;
+}
+
+/** The result of running a [ProcedureStage]. */
+export enum StageResult {
+	/** All the executions succeeded. */
+	Succeeded = "Succeeded",
+	/** Some executions failed with `on_failure: Continue`. */
+	Failed = "Failed",
+	/** The stage was disabled, or its condition didn't pass. */
+	Skipped = "Skipped",
 }
 
+/** A condition checked before running a [ProcedureStage]. */
+export type StageCondition = 
+	/**
+	 * Passes if the previous stage had this result.
+	 * There is no previous stage for the first stage, so it won't pass.
+	 */
+	| { type: "PreviousStage", params: {
+	result: StageResult;
+}}
+	/** Passes if the Stack is in this state. */
+	| { type: "StackState", params: {
+	/** Id or name */
+	stack: string;
+	state: StackState;
+}}
+	/**
+	 * Passes if the Stack compose files have changed since
+	 * it was last deployed, or it has never been deployed.
+	 */
+	| { type: "StackChanged", params: {
+	/** Id or name */
+	stack: string;
+}}
+	/** Passes if the Deployment is in this state. */
+	| { type: "DeploymentState", params: {
+	/** Id or name */
+	deployment: string;
+	state: DeploymentState;
+}}
+	/** Passes if the Build is in this state. */
+	| { type: "BuildState", params: {
+	/** Id or name */
+	build: string;
+	state: BuildState;
+}}
+	/** Passes if the Variable has this value. */
+	| { type: "Variable", params: {
+	/** The name of the Variable */
+	name: string;
+	value: string;
+}};
+
 /** A single stage of a procedure. Runs a list of executions in parallel. */
 export interface ProcedureStage {
 	/** A name for the procedure */
@@ -502,12 +1022,61 @@ export interface ProcedureStage {
 	enabled: boolean;
 	/** The executions in the stage */
 	executions?: EnabledExecution[];
+	/**
+	 * What to do when an execution in the stage fails.
+	 * Can be overridden on each execution.
+	 * Default: `Abort`
+	 */
+	on_failure?: ProcedureFailurePolicy;
+	/**
+	 * Retry failed executions this many times before they count as failed,
+	 * waiting 5s, 10s, 20s, ... between attempts. At most 10.
+	 * Can be overridden on each execution.
+	 */
+	retries?: I64;
+	/**
+	 * Cancel executions taking longer than this many seconds,
+	 * and count them as failed. 0 means no timeout. At most 1 day (86400).
+	 * Can be overridden on each execution.
+	 */
+	timeout_seconds?: I64;
+	/**
+	 * Mark this as a cleanup stage. Cleanup stages are skipped in normal runs,
+	 * and only run after an execution fails with `on_failure: Cleanup`.
+	 */
+	cleanup?: boolean;
+	/**
+	 * Run at most this many executions of the stage at once,
+	 * after Batch executions are expanded. 0 means no limit.
+	 */
+	max_concurrency?: I64;
+	/**
+	 * Run at most this many executions at once on each Server.
+	 * Only applies to executions targeting a Server, Deployment, Stack, or Repo.
+	 * 0 means no limit.
+	 */
+	max_concurrency_per_server?: I64;
+	/**
+	 * Only run the stage if this condition passes when the stage is reached.
+	 * Otherwise the stage is skipped. If empty, the stage always runs.
+	 */
+	condition?: StageCondition;
+}
+
+/** The format used to specify a [ProcedureConfig] schedule. */
+export enum ScheduleFormat {
+	/** Cron expression, eg. `0 0 * * *` */
+	Cron = "Cron",
+	/** Fixed interval, eg. `1-hr` */
+	Interval = "Interval",
 }
 
 /** Config for the [Procedure] */
 export interface ProcedureConfig {
 	/** The stages to be run by the procedure. */
 	stages?: ProcedureStage[];
+	/** Typed parameters which can be given arguments when run. */
+	parameters?: Parameter[];
 	/** Whether incoming webhooks actually trigger action. */
 	webhook_enabled: boolean;
 	/**
@@ -515,6 +1084,39 @@ export interface ProcedureConfig {
 	 * If its an empty string, use the default secret from the config.
 	 */
 	webhook_secret?: string;
+	/**
+	 * Map fields of the incoming webhook payload into arguments.
+	 * One per line as `PARAMETER = path.to.field`,
+	 * eg. `TAG = head_commit.id`. Array items are indexed like `commits.0.id`.
+	 */
+	webhook_args?: string;
+	/**
+	 * Choose whether to specify the schedule as a Cron expression,
+	 * or as a fixed interval.
+	 */
+	schedule_format?: ScheduleFormat;
+	/**
+	 * Optionally provide a schedule for the procedure to run on.
+	 * 
+	 * - `Cron`: A cron expression, with optional leading seconds field.
+	 * eg. `0 0 * * *` (daily at midnight), `0 30 2 * * SUN` (sundays at 02:30:00).
+	 * - `Interval`: A fixed interval, aligned to the unix epoch.
+	 * eg. `15-min`, `1-hr`, `12-hr`, `1-day`.
+	 * 
+	 * If empty, the procedure will not be scheduled.
+	 */
+	schedule?: string;
+	/**
+	 * Whether the schedule is enabled, if one is provided.
+	 * Can be used to temporarily disable the schedule.
+	 */
+	schedule_enabled: boolean;
+	/**
+	 * Optional. A TZ identifier used to evaluate `Cron` schedules,
+	 * eg. `America/New_York`. If empty, uses the Core local timezone.
+	 * https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+	 */
+	schedule_timezone?: string;
 }
 
 /**
@@ -615,6 +1217,58 @@ export interface JwtResponse {
 /** Response for [CreateLocalUser]. */
 export type CreateLocalUserResponse = JwtResponse;
 
+/**
+ * A window of time during which alerts on the matching resources
+ * are held back from the Alerters. The alerts are still recorded,
+ * and marked as `suppressed`.
+ * 
+ * The window is active while enabled, between `start_ts` and `end_ts` (if given),
+ * and during each `schedule` occurrence (if given).
+ */
+export interface MaintenanceWindow {
+	/** Unique name associated with the maintenance window. */
+	name: string;
+	/** The reason for the maintenance, eg. `Monthly host patching`. */
+	reason?: string;
+	/** Whether the maintenance window is enabled. */
+	enabled: boolean;
+	/**
+	 * Suppress alerts on these resources.
+	 * The resources can be referenced by id or name.
+	 */
+	targets?: ResourceTarget[];
+	/** Suppress alerts on resources with any of these tags (ids or names). */
+	tags?: string[];
+	/**
+	 * Unix timestamp in milliseconds the window starts.
+	 * If 0, the window has no start bound.
+	 */
+	start_ts?: I64;
+	/**
+	 * Unix timestamp in milliseconds the window ends.
+	 * If 0, the window has no end bound.
+	 */
+	end_ts?: I64;
+	/**
+	 * Optional cron expression to make the window recurring,
+	 * eg. `0 0 2 * * SUN` for every Sunday at 2 AM.
+	 * Each occurrence opens the window for `duration_minutes`.
+	 */
+	schedule?: string;
+	/**
+	 * How long each recurring window lasts, in minutes.
+	 * Required when `schedule` is set.
+	 */
+	duration_minutes?: I64;
+	/**
+	 * Optional IANA timezone for the schedule, eg. `America/New_York`.
+	 * If empty, uses the Core server's local timezone.
+	 */
+	schedule_timezone?: string;
+}
+
+export type CreateMaintenanceWindowResponse = MaintenanceWindow;
+
 export type CreateProcedureResponse = Procedure;
 
 export type CreateRepoWebhookResponse = NoData;
@@ -719,6 +1373,8 @@ export type DeleteDockerRegistryAccountResponse = DockerRegistryAccount;
 
 export type DeleteGitProviderAccountResponse = GitProviderAccount;
 
+export type DeleteMaintenanceWindowResponse = MaintenanceWindow;
+
 export type DeleteProcedureResponse = Procedure;
 
 export type DeleteRepoWebhookResponse = NoData;
@@ -745,8 +1401,43 @@ export type DeploymentImage =
 	 * Use a custom / older version of the image produced by the build.
 	 * if version is 0.0.0, this means `latest` image.
 	 */
-	version?: Version;
-}};
+	version?: Version;
+}};
+
+export type U64 = number;
+
+/**
+ * An uptime check on a Deployment or Stack, run periodically by Core.
+ * Failing probes send an `EndpointDown` alert,
+ * and an `EndpointRecovered` alert once they pass again.
+ */
+export interface EndpointProbe {
+	/**
+	 * The endpoint to probe.
+	 * - `http://` / `https://` urls are probed with a GET request.
+	 * - Anything else is treated as `host:port`,
+	 * and probed by opening a TCP connection.
+	 */
+	endpoint: string;
+	/**
+	 * The expected HTTP response status.
+	 * 0 accepts any status below 400. Ignored for TCP probes.
+	 * Default: 200
+	 */
+	expected_status: number;
+	/**
+	 * The number of seconds between probes.
+	 * Default: 60
+	 */
+	interval_secs: U64;
+	/**
+	 * The number of seconds to wait for a response
+	 * before the probe fails.
+	 * Clamped between 1 and `interval_secs`.
+	 * Default: 10
+	 */
+	timeout_secs: U64;
+}
 
 export enum RestartMode {
 	NoRestart = "no",
@@ -794,6 +1485,43 @@ export interface DeploymentConfig {
 	auto_update?: boolean;
 	/** Whether to send ContainerStateChange alerts for this deployment. */
 	send_alerts: boolean;
+	/**
+	 * Whether to send ContainerRestartLoop alerts for this deployment.
+	 * Requires `send_alerts` to also be enabled.
+	 */
+	send_restart_loop_alerts: boolean;
+	/**
+	 * Send a ContainerRestartLoop alert when a container restarts
+	 * more than this many times within `restart_loop_window_minutes`.
+	 */
+	restart_loop_threshold: I64;
+	/** The window used to count container restarts, in minutes. */
+	restart_loop_window_minutes: I64;
+	/**
+	 * Send a 'Warning' ContainerCpu alert when the container
+	 * cpu usage in % is above this threshhold. 0 to disable.
+	 */
+	cpu_warning?: number;
+	/**
+	 * Send a 'Critical' ContainerCpu alert when the container
+	 * cpu usage in % is above this threshhold. 0 to disable.
+	 */
+	cpu_critical?: number;
+	/**
+	 * Send a 'Warning' ContainerMem alert when the container
+	 * memory usage in MB is above this threshhold. 0 to disable.
+	 */
+	mem_warning_mb?: number;
+	/**
+	 * Send a 'Critical' ContainerMem alert when the container
+	 * memory usage in MB is above this threshhold. 0 to disable.
+	 */
+	mem_critical_mb?: number;
+	/**
+	 * HTTP / TCP uptime checks on the deployment.
+	 * Failing probes send EndpointDown alerts.
+	 */
+	probes?: EndpointProbe[];
 	/** Configure quick links that are displayed in the resource header */
 	links?: string[];
 	/**
@@ -863,11 +1591,21 @@ export enum DeploymentState {
 	Dead = "dead",
 }
 
+export enum HealthStatusEnum {
+	Empty = "",
+	None = "none",
+	Starting = "starting",
+	Healthy = "healthy",
+	Unhealthy = "unhealthy",
+}
+
 export interface DeploymentListItemInfo {
 	/** The state of the deployment / underlying docker container. */
 	state: DeploymentState;
 	/** The status of the docker container (eg. up 12 hours, exited 5 minutes ago.) */
 	status?: string;
+	/** The health check status of the docker container. */
+	health: HealthStatusEnum;
 	/** The image attached to the deployment. */
 	image: string;
 	/** Whether there is a newer image available at the same tag. */
@@ -910,209 +1648,45 @@ export type GetActionActionStateResponse = ActionActionState;
 
 export type GetActionResponse = Action;
 
-/** Severity level of problem. */
-export enum SeverityLevel {
-	/** No problem. */
-	Ok = "OK",
-	/** Problem is imminent. */
-	Warning = "WARNING",
-	/** Problem fully realized. */
-	Critical = "CRITICAL",
-}
+export type GetAlertResponse = Alert;
 
-/** The variants of data related to the alert. */
-export type AlertData = 
-	/** A null alert */
-	| { type: "None", data: {
-}}
-	/** A server could not be reached. */
-	| { type: "ServerUnreachable", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The error data */
-	err?: _Serror;
-}}
-	/** A server has high CPU usage. */
-	| { type: "ServerCpu", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The cpu usage percentage */
-	percentage: number;
-}}
-	/** A server has high memory usage. */
-	| { type: "ServerMem", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The used memory */
-	used_gb: number;
-	/** The total memory */
-	total_gb: number;
-}}
-	/** A server has high disk usage. */
-	| { type: "ServerDisk", data: {
-	/** The id of the server */
-	id: string;
-	/** The name of the server */
-	name: string;
-	/** The region of the server */
-	region?: string;
-	/** The mount path of the disk */
-	path: string;
-	/** The used portion of the disk in GB */
-	used_gb: number;
-	/** The total size of the disk in GB */
-	total_gb: number;
-}}
-	/** A container's state has changed unexpectedly. */
-	| { type: "ContainerStateChange", data: {
-	/** The id of the deployment */
-	id: string;
-	/** The name of the deployment */
-	name: string;
-	/** The server id of server that the deployment is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The previous container state */
-	from: DeploymentState;
-	/** The current container state */
-	to: DeploymentState;
-}}
-	/** A Deployment has an image update available */
-	| { type: "DeploymentImageUpdateAvailable", data: {
-	/** The id of the deployment */
-	id: string;
-	/** The name of the deployment */
-	name: string;
-	/** The server id of server that the deployment is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The image with update */
-	image: string;
-}}
-	/** A Deployment has an image update available */
-	| { type: "DeploymentAutoUpdated", data: {
-	/** The id of the deployment */
-	id: string;
-	/** The name of the deployment */
-	name: string;
-	/** The server id of server that the deployment is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The updated image */
-	image: string;
-}}
-	/** A stack's state has changed unexpectedly. */
-	| { type: "StackStateChange", data: {
-	/** The id of the stack */
-	id: string;
-	/** The name of the stack */
-	name: string;
-	/** The server id of server that the stack is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The previous stack state */
-	from: StackState;
-	/** The current stack state */
-	to: StackState;
-}}
-	/** A Stack has an image update available */
-	| { type: "StackImageUpdateAvailable", data: {
-	/** The id of the stack */
-	id: string;
-	/** The name of the stack */
-	name: string;
-	/** The server id of server that the stack is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** The service name to update */
-	service: string;
-	/** The image with update */
-	image: string;
-}}
-	/** A Stack was auto updated */
-	| { type: "StackAutoUpdated", data: {
-	/** The id of the stack */
-	id: string;
-	/** The name of the stack */
-	name: string;
-	/** The server id of server that the stack is on */
-	server_id: string;
-	/** The server name */
-	server_name: string;
-	/** One or more images that were updated */
-	images: string[];
-}}
-	/** An AWS builder failed to terminate. */
-	| { type: "AwsBuilderTerminationFailed", data: {
-	/** The id of the aws instance which failed to terminate */
-	instance_id: string;
-	/** A reason for the failure */
-	message: string;
-}}
-	/** A resource sync has pending updates */
-	| { type: "ResourceSyncPendingUpdates", data: {
-	/** The id of the resource sync */
-	id: string;
-	/** The name of the resource sync */
-	name: string;
-}}
-	/** A build has failed */
-	| { type: "BuildFailed", data: {
-	/** The id of the build */
-	id: string;
-	/** The name of the build */
-	name: string;
-	/** The version that failed to build */
-	version: Version;
-}}
-	/** A repo has failed */
-	| { type: "RepoBuildFailed", data: {
-	/** The id of the repo */
-	id: string;
-	/** The name of the repo */
+/** Aggregated alerts on a target for an alert type and severity level. */
+export interface AlertStats {
+	/** The target of the alerts. */
+	target: ResourceTarget;
+	/** The name of the target on the latest alert. */
 	name: string;
-}};
-
-/** Representation of an alert in the system. */
-export interface Alert {
+	/** The type of the alerts. */
+	alert_type: AlertData["type"];
+	/** The severity level of the group. */
+	level: SeverityLevel;
+	/** The number of alerts opened in the range. */
+	count: I64;
+	/** The number of these alerts which have been resolved. */
+	resolved: I64;
 	/**
-	 * The Mongo ID of the alert.
-	 * This field is de/serialized from/to JSON as
-	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized Alert) }`
+	 * The mean time from open to resolve in milliseconds,
+	 * over the resolved alerts. Null if none are resolved.
 	 */
-	_id?: MongoId;
-	/** Unix timestamp in milliseconds the alert was opened */
-	ts: I64;
-	/** Whether the alert is already resolved */
-	resolved: boolean;
-	/** The severity of the alert */
-	level: SeverityLevel;
-	/** The target of the alert */
-	target: ResourceTarget;
-	/** The data attached to the alert */
-	data: AlertData;
-	/** The timestamp of alert resolution */
-	resolved_ts?: I64;
+	mean_time_to_resolve_ms?: number;
+	/** The number of these alerts which are still open. */
+	open: I64;
+	/**
+	 * How long the oldest open alert has been open in milliseconds.
+	 * Null if none are open.
+	 */
+	open_duration_ms?: I64;
 }
 
-export type GetAlertResponse = Alert;
+/** Response for [GetAlertStats]. */
+export interface GetAlertStatsResponse {
+	/** The start of the range used. */
+	from: I64;
+	/** The end of the range used. */
+	to: I64;
+	/** The alert stats, sorted by count descending. */
+	stats: AlertStats[];
+}
 
 export type GetAlerterResponse = Alerter;
 
@@ -1180,15 +1754,90 @@ export type GetDockerRegistryAccountResponse = DockerRegistryAccount;
 
 export type GetGitProviderAccountResponse = GitProviderAccount;
 
+export type GetMaintenanceWindowResponse = MaintenanceWindow;
+
 export type GetPermissionLevelResponse = PermissionLevel;
 
 export interface ProcedureActionState {
 	running: boolean;
 }
 
-export type GetProcedureActionStateResponse = ProcedureActionState;
-
-export type GetProcedureResponse = Procedure;
+export type GetProcedureActionStateResponse = ProcedureActionState;
+
+export type GetProcedureResponse = Procedure;
+
+/** The record of an execution in a [ProcedureStageRun]. */
+export interface ProcedureExecutionRun {
+	/** The execution which was run. */
+	execution: Execution;
+	/**
+	 * The id of the update created by the execution, if any.
+	 * With retries, this is the update of the last attempt.
+	 */
+	update_id?: string;
+	/** The number of attempts made, including retries. */
+	attempts: I64;
+	/** Unix timestamp in ms the execution started. */
+	start_ts: I64;
+	/** Unix timestamp in ms the execution finished. */
+	end_ts: I64;
+	/** Whether the execution succeeded. */
+	success: boolean;
+	/** The error, if the execution failed. */
+	error?: string;
+}
+
+/** The record of a stage in a [ProcedureRun]. */
+export interface ProcedureStageRun {
+	/** The stage name. */
+	name: string;
+	/** Whether this is a cleanup stage. */
+	cleanup: boolean;
+	/** The result of the stage. */
+	result: StageResult;
+	/** Unix timestamp in ms the stage started. */
+	start_ts: I64;
+	/** Unix timestamp in ms the stage finished. */
+	end_ts: I64;
+	/**
+	 * The executions run by the stage,
+	 * with Batch executions expanded.
+	 */
+	executions: ProcedureExecutionRun[];
+}
+
+/**
+ * A record of a single procedure run,
+ * with the timing and result of each stage and execution.
+ */
+export interface ProcedureRun {
+	/**
+	 * The Mongo ID of the run.
+	 * This field is de/serialized from/to JSON as
+	 * `{ "_id": { "$oid": "..." }, ...(rest of serialized ProcedureRun) }`
+	 */
+	_id?: MongoId;
+	/** The id of the procedure which was run. */
+	procedure_id: string;
+	/** The id of the `RunProcedure` update. */
+	update_id: string;
+	/** Unix timestamp in ms the run started. */
+	start_ts: I64;
+	/**
+	 * Unix timestamp in ms the run finished.
+	 * Null while the run is in progress.
+	 */
+	end_ts?: I64;
+	/** `Running` while in progress, then `Ok` or `Failed`. */
+	state: ProcedureState;
+	/**
+	 * The stages reached by the run, in order.
+	 * Cleanup stages are included after the stage which failed.
+	 */
+	stages: ProcedureStageRun[];
+}
+
+export type GetProcedureRunResponse = ProcedureRun;
 
 export interface RepoActionState {
 	/** Whether Repo currently cloning on the attached Server */
@@ -1419,6 +2068,8 @@ export interface ResourceSyncInfo {
 	variable_updates?: DiffData[];
 	/** The list of pending updates to user groups */
 	user_group_updates?: DiffData[];
+	/** The list of pending updates to maintenance windows */
+	maintenance_window_updates?: DiffData[];
 	/** The list of pending deploys to resources. */
 	pending_deploy?: SyncDeployUpdate;
 	/** If there is an error, it will be stored here */
@@ -1467,6 +2118,16 @@ export interface ServerActionState {
 
 export type GetServerActionStateResponse = ServerActionState;
 
+/** Disk usage thresholds for a specific mount point. */
+export interface DiskThreshold {
+	/** The mount point, eg. `/` or `/mnt/data`. */
+	mount: string;
+	/** The percentage threshhold which triggers WARNING state. */
+	warning: number;
+	/** The percentage threshhold which triggers CRITICAL state. */
+	critical: number;
+}
+
 /** Server configuration. */
 export interface ServerConfig {
 	/**
@@ -1498,6 +2159,12 @@ export interface ServerConfig {
 	 * default: true
 	 */
 	stats_monitoring: boolean;
+	/**
+	 * Whether to store historical per-container stats,
+	 * retained for as long as the server stats.
+	 * default: false
+	 */
+	record_container_stats?: boolean;
 	/**
 	 * Whether to trigger 'docker image prune -a -f' every 24 hours.
 	 * default: true
@@ -1513,6 +2180,10 @@ export interface ServerConfig {
 	send_mem_alerts: boolean;
 	/** Whether to send alerts about the servers DISK status */
 	send_disk_alerts: boolean;
+	/** Whether to send alerts about the servers NETWORK throughput */
+	send_network_alerts: boolean;
+	/** Whether to send alerts about missing required processes */
+	send_process_alerts: boolean;
 	/** The percentage threshhold which triggers WARNING state for CPU. */
 	cpu_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for CPU. */
@@ -1525,6 +2196,33 @@ export interface ServerConfig {
 	disk_warning: number;
 	/** The percentage threshhold which triggers CRITICAL state for DISK. */
 	disk_critical: number;
+	/**
+	 * Override the disk thresholds for specific mount points.
+	 * Mounts without an override use `disk_warning` / `disk_critical`.
+	 */
+	disk_thresholds?: DiskThreshold[];
+	/**
+	 * The throughput threshhold in MB/s, on any single network interface,
+	 * which triggers WARNING state for NETWORK. 0 to disable.
+	 */
+	network_warning?: number;
+	/**
+	 * The throughput threshhold in MB/s, on any single network interface,
+	 * which triggers CRITICAL state for NETWORK. 0 to disable.
+	 */
+	network_critical?: number;
+	/**
+	 * How long the throughput must stay above the threshhold
+	 * before a NETWORK alert is opened.
+	 * default: 5
+	 */
+	network_sustained_minutes: I64;
+	/**
+	 * Process names which should always be running on the server,
+	 * eg. `sshd` or `tailscaled`. A 'Critical' alert is opened
+	 * if any of them are missing from the process list.
+	 */
+	required_processes?: string[];
 }
 
 export type Server = Resource<ServerConfig, undefined>;
@@ -1554,6 +2252,20 @@ export interface StackActionState {
 
 export type GetStackActionStateResponse = StackActionState;
 
+/** Cpu / memory alert thresholds for a stack service. */
+export interface StackServiceThresholds {
+	/** The service name */
+	service: string;
+	/** The cpu usage in % which triggers a 'Warning' alert. 0 to disable. */
+	cpu_warning?: number;
+	/** The cpu usage in % which triggers a 'Critical' alert. 0 to disable. */
+	cpu_critical?: number;
+	/** The memory usage in MB which triggers a 'Warning' alert. 0 to disable. */
+	mem_warning_mb?: number;
+	/** The memory usage in MB which triggers a 'Critical' alert. 0 to disable. */
+	mem_critical_mb?: number;
+}
+
 /** The compose file configuration. */
 export interface StackConfig {
 	/** The server to deploy the stack on. */
@@ -1658,6 +2370,28 @@ export interface StackConfig {
 	webhook_force_deploy?: boolean;
 	/** Whether to send StackStateChange alerts for this stack. */
 	send_alerts: boolean;
+	/**
+	 * Whether to send ContainerRestartLoop alerts for this stack.
+	 * Requires `send_alerts` to also be enabled.
+	 */
+	send_restart_loop_alerts: boolean;
+	/**
+	 * Send a ContainerRestartLoop alert when a container restarts
+	 * more than this many times within `restart_loop_window_minutes`.
+	 */
+	restart_loop_threshold: I64;
+	/** The window used to count container restarts, in minutes. */
+	restart_loop_window_minutes: I64;
+	/**
+	 * Per service cpu / memory alert thresholds.
+	 * Services without an entry don't send ContainerCpu / ContainerMem alerts.
+	 */
+	service_thresholds?: StackServiceThresholds[];
+	/**
+	 * HTTP / TCP uptime checks on the stack.
+	 * Failing probes send EndpointDown alerts.
+	 */
+	probes?: EndpointProbe[];
 	/** Used with `registry_account` to login to a registry before docker compose up. */
 	registry_provider?: string;
 	/** Used with `registry_provider` to login to a registry before docker compose up. */
@@ -1998,6 +2732,11 @@ export enum Operation {
 	CreateVariable = "CreateVariable",
 	UpdateVariableValue = "UpdateVariableValue",
 	DeleteVariable = "DeleteVariable",
+	AcknowledgeAlert = "AcknowledgeAlert",
+	SnoozeAlert = "SnoozeAlert",
+	CreateMaintenanceWindow = "CreateMaintenanceWindow",
+	UpdateMaintenanceWindow = "UpdateMaintenanceWindow",
+	DeleteMaintenanceWindow = "DeleteMaintenanceWindow",
 	CreateGitProviderAccount = "CreateGitProviderAccount",
 	UpdateGitProviderAccount = "UpdateGitProviderAccount",
 	DeleteGitProviderAccount = "DeleteGitProviderAccount",
@@ -2037,6 +2776,7 @@ export interface Update {
 	 * - `Procedure`: The operation was triggered as part of a procedure run
 	 * - `Github`: The operation was triggered by a github webhook
 	 * - `Auto Redeploy`: The operation (always `Deploy`) was triggered by an attached build finishing.
+	 * - `Schedule`: The operation (always `RunProcedure`) was triggered by the procedure schedule.
 	 */
 	operator: string;
 	/** The target resource to which this update refers */
@@ -2104,14 +2844,6 @@ export enum ContainerStateStatusEnum {
 	Dead = "dead",
 }
 
-export enum HealthStatusEnum {
-	Empty = "",
-	None = "none",
-	Starting = "starting",
-	Healthy = "healthy",
-	Unhealthy = "unhealthy",
-}
-
 /** HealthcheckResult stores information about a single run of a healthcheck probe */
 export interface HealthcheckResult {
 	/** Date and time at which this check started in [RFC 3339](https://www.ietf.org/rfc/rfc3339.txt) format with nano-seconds. */
@@ -2744,8 +3476,6 @@ export enum VolumeScopeEnum {
 	Global = "global",
 }
 
-export type U64 = number;
-
 /** The version number of the object such as node, service, etc. This is needed to avoid conflicting writes. The client must send the version number along with the modified specification when updating these objects.  This approach ensures safe concurrency and determinism in that the change on the object may not be applied if the version number has changed from the last read. In other words, if two update requests specify the same base version, only one of the requests can succeed. As a result, two separate update requests that happen at the same time will not unintentionally overwrite each other. */
 export interface ObjectVersion {
 	Index?: U64;
@@ -2919,6 +3649,16 @@ export interface ContainerListItem {
 	state: ContainerStateStatusEnum;
 	/** Additional human-readable status of this container (e.g. `Exit 0`) */
 	status?: string;
+	/**
+	 * The health check status of this container.
+	 * `none` if the container has no health check.
+	 */
+	health?: HealthStatusEnum;
+	/**
+	 * The number of times the container has been restarted
+	 * by its restart policy. Only included in the Server monitoring lists.
+	 */
+	restart_count?: I64;
 	/** The network mode */
 	network_mode?: string;
 	/** The network names attached to container */
@@ -3110,6 +3850,8 @@ export interface GitProvider {
 
 export type ListGitProvidersFromConfigResponse = GitProvider[];
 
+export type ListMaintenanceWindowsResponse = MaintenanceWindow[];
+
 export type UserTarget = 
 	/** User Id */
 	| { type: "User", id: string }
@@ -3146,6 +3888,15 @@ export interface ProcedureListItemInfo {
 	stages: I64;
 	/** Reflect whether last run successful / currently running. */
 	state: ProcedureState;
+	/** Unix timestamp in ms of the last time the procedure was run. */
+	last_run_at?: I64;
+	/**
+	 * If the procedure has a schedule enabled,
+	 * the unix timestamp in ms of the next scheduled run.
+	 */
+	next_scheduled_run?: I64;
+	/** If there is an error parsing the schedule, it will be given here. */
+	schedule_error?: string;
 }
 
 export type ProcedureListItem = ResourceListItem<ProcedureListItemInfo>;
@@ -3325,6 +4076,8 @@ export interface StackServiceWithUpdate {
 	image: string;
 	/** Whether there is a newer image available for this service */
 	update_available: boolean;
+	/** The health check status of the service container */
+	health: HealthStatusEnum;
 }
 
 export interface StackListItemInfo {
@@ -3455,6 +4208,8 @@ export type ServerTemplateQuery = ResourceQuery<ServerTemplateQuerySpecifics>;
 
 export type SetLastSeenUpdateResponse = NoData;
 
+export type SnoozeAlertResponse = Alert;
+
 export interface StackQuerySpecifics {
 	/** Filter syncs by their repo. */
 	repos: string[];
@@ -3468,6 +4223,8 @@ export type UpdateDockerRegistryAccountResponse = DockerRegistryAccount;
 
 export type UpdateGitProviderAccountResponse = GitProviderAccount;
 
+export type UpdateMaintenanceWindowResponse = MaintenanceWindow;
+
 export type UpdatePermissionOnResourceTypeResponse = NoData;
 
 export type UpdatePermissionOnTargetResponse = NoData;
@@ -3535,6 +4292,17 @@ export interface __Serror {
 
 export type _Serror = __Serror;
 
+/**
+ * Acknowledge an open alert, marking that it is being handled.
+ * Acknowledged alerts are not re-notified or escalated.
+ * Requires execute permissions on the alert target.
+ * Response: [Alert].
+ */
+export interface AcknowledgeAlert {
+	/** The id of the alert. */
+	id: string;
+}
+
 /** **Admin only.** Add a user to a user group. Response: [UserGroup] */
 export interface AddUserToUserGroup {
 	/** The name or id of UserGroup that user should be added to. */
@@ -3938,6 +4706,35 @@ export interface CommitSync {
 	sync: string;
 }
 
+/**
+ * Container stats stored on the database.
+ * Only recorded for servers with `record_container_stats` enabled.
+ */
+export interface ContainerStatsRecord {
+	/** Unix timestamp in milliseconds */
+	ts: I64;
+	/** Server id */
+	sid: string;
+	/** The container name */
+	name: string;
+	/** Cpu usage percentage */
+	cpu_perc: number;
+	/** Memory usage percentage of the limit */
+	mem_perc: number;
+	/** Memory used in bytes */
+	mem_used_bytes: number;
+	/** Memory limit in bytes */
+	mem_limit_bytes: number;
+	/** Total network received by the container in bytes */
+	net_rx_bytes: number;
+	/** Total network sent by the container in bytes */
+	net_tx_bytes: number;
+	/** Total block io read by the container in bytes */
+	block_read_bytes: number;
+	/** Total block io written by the container in bytes */
+	block_write_bytes: number;
+}
+
 export interface Conversion {
 	/** reference on the server. */
 	local: string;
@@ -4197,6 +4994,15 @@ export interface CreateLocalUser {
 	password: string;
 }
 
+/**
+ * **Admin only.** Create a maintenance window.
+ * Response: [MaintenanceWindow].
+ */
+export interface CreateMaintenanceWindow {
+	/** The maintenance window to create. */
+	window: MaintenanceWindow;
+}
+
 /**
  * Create a docker network on the server.
  * Response: [Update]
@@ -4346,10 +5152,54 @@ export interface CreateVariable {
 	is_secret?: boolean;
 }
 
-/** Configuration for a Custom alerter endpoint. */
+/**
+ * Configuration for a Custom alerter endpoint.
+ * 
+ * The `headers`, `username`, `password`, `token`, and `signing_secret`
+ * fields support interpolation of Core variables and secrets,
+ * eg. `[[ALERT_WEBHOOK_TOKEN]]`.
+ */
 export interface CustomAlerterEndpoint {
 	/** The http/s endpoint to send the POST to */
 	url: string;
+	/**
+	 * Extra headers to send with the request,
+	 * one `Header-Name: value` per line.
+	 */
+	headers?: string;
+	/**
+	 * Username for basic auth.
+	 * If empty, will not use basic auth.
+	 */
+	username?: string;
+	/**
+	 * Password for basic auth.
+	 * Use a secret here, eg. `[[ALERT_WEBHOOK_PASSWORD]]`.
+	 */
+	password?: string;
+	/**
+	 * Bearer token sent in the `Authorization` header.
+	 * Only used when `username` is empty.
+	 */
+	token?: string;
+	/**
+	 * If set, the request body is signed with HMAC-SHA256 using this secret.
+	 * The signature is sent in the `X-Komodo-Signature` header,
+	 * as `sha256=<hex digest>`.
+	 */
+	signing_secret?: string;
+	/**
+	 * Retry the request this many times when the receiver
+	 * responds with a 5xx status or can't be reached,
+	 * with exponential backoff starting at 1 second.
+	 * Default: `3`
+	 */
+	retries: I64;
+	/**
+	 * Timeout for each request in seconds.
+	 * Default: `10`
+	 */
+	timeout_seconds: I64;
 }
 
 /**
@@ -4464,6 +5314,15 @@ export interface DeleteImage {
 	name: string;
 }
 
+/**
+ * **Admin only.** Delete a maintenance window.
+ * Response: [MaintenanceWindow].
+ */
+export interface DeleteMaintenanceWindow {
+	/** The name of the maintenance window to delete. */
+	name: string;
+}
+
 /**
  * Delete a docker network.
  * Response: [Update]
@@ -4745,6 +5604,8 @@ export interface ExportResourcesToToml {
 	user_groups?: string[];
 	/** Whether to include variables */
 	include_variables?: boolean;
+	/** Whether to include maintenance windows */
+	include_maintenance_windows?: boolean;
 }
 
 /** Find resources matching a common query. Response: [FindResourcesResponse]. */
@@ -4817,6 +5678,35 @@ export interface GetAlert {
 	id: string;
 }
 
+/**
+ * Get alert analytics over a time range, grouped by
+ * target, alert type and severity level.
+ * Only alerts opened within the range are included.
+ * Response: [GetAlertStatsResponse].
+ */
+export interface GetAlertStats {
+	/**
+	 * Unix timestamp in milliseconds of the start of the range.
+	 * Default: 7 days before `to`.
+	 */
+	from?: I64;
+	/**
+	 * Unix timestamp in milliseconds of the end of the range.
+	 * Default: now.
+	 */
+	to?: I64;
+	/**
+	 * Only include alerts on these targets.
+	 * If empty, includes all targets.
+	 */
+	targets?: ResourceTarget[];
+	/**
+	 * Only include these alert types.
+	 * If empty, includes all alert types.
+	 */
+	alert_types?: AlertData["type"][];
+}
+
 /** Get a specific alerter. Response: [Alerter]. */
 export interface GetAlerter {
 	/** Id or name */
@@ -5077,6 +5967,38 @@ export interface GetGitProviderAccount {
 	id: string;
 }
 
+/**
+ * Paginated endpoint serving historical (timeseries) container stats
+ * for a Deployment or Stack service, for graphing.
+ * Only available for servers with `record_container_stats` enabled.
+ * Response: [GetHistoricalContainerStatsResponse].
+ */
+export interface GetHistoricalContainerStats {
+	/** The Deployment or Stack target. */
+	target: ResourceTarget;
+	/** The Stack service name. Required for Stack targets. */
+	service?: string;
+	/** The granularity of the data. */
+	granularity: Timelength;
+	/**
+	 * Page of historical data. Default is 0, which is the most recent data.
+	 * Use with the `next_page` field of the response.
+	 */
+	page?: number;
+}
+
+/** Response to [GetHistoricalContainerStats]. */
+export interface GetHistoricalContainerStatsResponse {
+	/**
+	 * The timeseries page of data.
+	 * Stack services with multiple replicas include
+	 * the data for each container.
+	 */
+	stats: ContainerStatsRecord[];
+	/** If there is a next page of data, pass this to `page` to get it. */
+	next_page?: number;
+}
+
 /**
  * Paginated endpoint serving historical (timeseries) server stats for graphing.
  * Response: [GetHistoricalServerStatsResponse].
@@ -5093,6 +6015,20 @@ export interface GetHistoricalServerStats {
 	page?: number;
 }
 
+/** Max system stats over a period. */
+export interface SystemStatsMax {
+	/** Max cpu usage percentage */
+	cpu_perc: number;
+	/** Max memory used in GB */
+	mem_used_gb: number;
+	/** Max disk used in GB */
+	disk_used_gb: number;
+	/** Max network ingress usage in bytes */
+	network_ingress_bytes: number;
+	/** Max network egress usage in bytes */
+	network_egress_bytes: number;
+}
+
 /** System stats stored on the database. */
 export interface SystemStatsRecord {
 	/** Unix timestamp in milliseconds */
@@ -5117,6 +6053,11 @@ export interface SystemStatsRecord {
 	network_egress_bytes?: number;
 	/** Network usage by interface name (ingress, egress in bytes) */
 	network_usage_interface?: SingleNetworkInterfaceUsage[];
+	/**
+	 * Only present on records served from [SystemStatsRollup]s,
+	 * where the other values are averages over the period.
+	 */
+	max?: SystemStatsMax;
 }
 
 /** Response to [GetHistoricalServerStats]. */
@@ -5149,6 +6090,15 @@ export interface GetLoginOptionsResponse {
 	registration_disabled: boolean;
 }
 
+/**
+ * Get a specific maintenance window by name.
+ * Response: [MaintenanceWindow]
+ */
+export interface GetMaintenanceWindow {
+	/** The name of the maintenance window to get. */
+	name: string;
+}
+
 /**
  * Get the version of the Komodo Periphery agent on the target server.
  * Response: [GetPeripheryVersionResponse].
@@ -5186,6 +6136,15 @@ export interface GetProcedureActionState {
 	procedure: string;
 }
 
+/**
+ * Get a procedure run, with the timing and result of
+ * each stage and execution. Response: [ProcedureRun].
+ */
+export interface GetProcedureRun {
+	/** The id of the run, or of its `RunProcedure` update. */
+	id: string;
+}
+
 /**
  * Gets a summary of data relating to all procedures.
  * Response: [GetProceduresSummaryResponse].
@@ -5558,6 +6517,18 @@ export interface GetVersionResponse {
 	version: string;
 }
 
+/** Configuration for a Gotify alerter. */
+export interface GotifyAlerterEndpoint {
+	/** The url of the Gotify server, eg. `https://gotify.example.com` */
+	url: string;
+	/**
+	 * The Gotify application token.
+	 * Supports interpolation of Core variables and secrets,
+	 * eg. `[[GOTIFY_TOKEN]]`.
+	 */
+	token?: string;
+}
+
 export enum HetznerDatacenter {
 	Nuremberg1Dc3 = "Nuremberg1Dc3",
 	Helsinki1Dc2 = "Helsinki1Dc2",
@@ -5756,6 +6727,11 @@ export interface ListAlerts {
 	 * This will filter to only include open alerts that have CRITICAL level on those two servers.
 	 */
 	query?: MongoDocument;
+	/**
+	 * Only include acknowledged (`true`) or
+	 * unacknowledged (`false`) alerts.
+	 */
+	acknowledged?: boolean;
 	/**
 	 * Retrieve older results by incrementing the page.
 	 * `page: 0` is default, and returns the most recent results.
@@ -6047,6 +7023,15 @@ export interface ListGitProvidersFromConfig {
 	target?: ResourceTarget;
 }
 
+/**
+ * List the maintenance windows.
+ * Response: [ListMaintenanceWindowsResponse]
+ */
+export interface ListMaintenanceWindows {
+	/** Only include the windows which are currently active. */
+	active?: boolean;
+}
+
 /**
  * List permissions for the calling user.
  * Does not include any permissions on UserGroups they may be a part of.
@@ -6055,6 +7040,73 @@ export interface ListGitProvidersFromConfig {
 export interface ListPermissions {
 }
 
+/**
+ * Get a paginated list of the endpoint probe results
+ * on a Deployment or Stack, sorted by timestamp descending.
+ * Response: [ListProbeRecordsResponse].
+ */
+export interface ListProbeRecords {
+	/** The Deployment or Stack target. */
+	target: ResourceTarget;
+	/** Only include the results for this endpoint. */
+	endpoint?: string;
+	/**
+	 * Page of probe records. Default is 0, which is the most recent data.
+	 * Use with the `next_page` field of the response.
+	 */
+	page?: number;
+}
+
+/** The result of an [EndpointProbe] stored on the database. */
+export interface ProbeRecord {
+	/** Unix timestamp in milliseconds */
+	ts: I64;
+	/** The Deployment / Stack the probe is configured on */
+	target: ResourceTarget;
+	/** The probed endpoint */
+	endpoint: string;
+	/** Whether the probe passed */
+	success: boolean;
+	/** The HTTP response status, if one was received */
+	status?: number;
+	/** The time taken to get a response / connect in milliseconds. */
+	response_time_ms: I64;
+	/** The reason for the failure */
+	error?: string;
+}
+
+/** Response for [ListProbeRecords]. */
+export interface ListProbeRecordsResponse {
+	/** The page of probe records, newest first. */
+	records: ProbeRecord[];
+	/** If there is a next page of data, pass this to `page` to get it. */
+	next_page?: number;
+}
+
+/**
+ * Get a paginated list of the runs of a procedure,
+ * sorted by start time descending. Response: [ListProcedureRunsResponse].
+ */
+export interface ListProcedureRuns {
+	/** Id or name */
+	procedure: string;
+	/**
+	 * Retrieve older results by incrementing the page.
+	 * `page: 0` is default, and returns the most recent results.
+	 */
+	page?: U64;
+}
+
+/** Response for [ListProcedureRuns]. */
+export interface ListProcedureRunsResponse {
+	runs: ProcedureRun[];
+	/**
+	 * If more runs exist, the next page will be given here.
+	 * Otherwise it will be `null`
+	 */
+	next_page?: I64;
+}
+
 /** List procedures matching optional query. Response: [ListProceduresResponse]. */
 export interface ListProcedures {
 	/** optional structured query to filter procedures. */
@@ -6162,6 +7214,7 @@ export interface UpdateListItem {
 	 * - `Procedure`: The operation was triggered as part of a procedure run
 	 * - `Github`: The operation was triggered by a github webhook
 	 * - `Auto Redeploy`: The operation (always `Deploy`) was triggered by an attached build finishing.
+	 * - `Schedule`: The operation (always `RunProcedure`) was triggered by the procedure schedule.
 	 */
 	operator: string;
 	/** The target resource to which this update refers */
@@ -6241,6 +7294,22 @@ export interface NameAndId {
 	id: string;
 }
 
+/** Configuration for a ntfy alerter. */
+export interface NtfyAlerterEndpoint {
+	/**
+	 * The full url of the ntfy topic, eg. `https://ntfy.sh/<topic>`.
+	 * Anyone who knows a topic on a public server can read it,
+	 * so use a hard to guess topic or a protected one. Required.
+	 */
+	url: string;
+	/**
+	 * Optional access token for protected topics.
+	 * Supports interpolation of Core variables and secrets,
+	 * eg. `[[NTFY_TOKEN]]`.
+	 */
+	token?: string;
+}
+
 /** Pauses all containers on the target server. Response: [Update] */
 export interface PauseAllContainers {
 	/** Name or id */
@@ -6643,6 +7712,7 @@ export interface ResourcesToml {
 	resource_syncs?: ResourceToml<_PartialResourceSyncConfig>[];
 	user_groups?: UserGroupToml[];
 	variables?: Variable[];
+	maintenance_windows?: MaintenanceWindow[];
 }
 
 /** Restarts all containers on the target server. Response: [Update] */
@@ -6685,6 +7755,11 @@ export interface RestartStack {
 export interface RunAction {
 	/** Id or name */
 	action: string;
+	/**
+	 * Arguments for the Action parameters, by parameter name.
+	 * Parameters not given here use their default.
+	 */
+	args?: Record<string, string>;
 }
 
 /**
@@ -6704,6 +7779,11 @@ export interface RunBuild {
 export interface RunProcedure {
 	/** Id or name */
 	procedure: string;
+	/**
+	 * Arguments for the Procedure parameters, by parameter name.
+	 * Parameters not given here use their default.
+	 */
+	args?: Record<string, string>;
 }
 
 /** Runs the target resource sync. Response: [Update] */
@@ -6822,6 +7902,13 @@ export interface ServerHealth {
 	cpu: ServerHealthState;
 	mem: ServerHealthState;
 	disks: Record<string, ServerHealthState>;
+	/** Throughput health by network interface name. */
+	network?: Record<string, ServerHealthState>;
+	/**
+	 * Health of the required processes by process name.
+	 * Only present when the process list could be retrieved.
+	 */
+	processes?: Record<string, ServerHealthState>;
 }
 
 /**
@@ -6853,6 +7940,64 @@ export interface Sleep {
 	duration_ms?: I64;
 }
 
+/** How the connection to the SMTP server is secured. */
+export enum SmtpTlsMode {
+	/** Plaintext connection. Only use with a local relay / test sink. */
+	None = "None",
+	/** Upgrade a plaintext connection with STARTTLS. Usually port `587`. */
+	StartTls = "StartTls",
+	/** Implicit TLS from the start of the connection. Usually port `465`. */
+	Tls = "Tls",
+}
+
+/**
+ * Configuration for an SMTP (email) alerter.
+ * 
+ * The `username`, `password`, and `from` fields support interpolation
+ * of Core variables and secrets, eg. `[[SMTP_PASSWORD]]`.
+ */
+export interface SmtpAlerterEndpoint {
+	/** The SMTP server host, eg. `smtp.gmail.com` */
+	host: string;
+	/**
+	 * The SMTP server port.
+	 * Default: `587`
+	 */
+	port: number;
+	/**
+	 * How the connection to the SMTP server is secured.
+	 * Default: `StartTls`
+	 */
+	tls?: SmtpTlsMode;
+	/**
+	 * The username to authenticate with.
+	 * If empty, will not authenticate.
+	 */
+	username?: string;
+	/**
+	 * The password to authenticate with.
+	 * Use a secret here, eg. `[[SMTP_PASSWORD]]`.
+	 */
+	password?: string;
+	/** The sender mailbox, eg. `Komodo <alerts@example.com>` */
+	from?: string;
+	/** The recipient mailboxes. */
+	to?: string[];
+}
+
+/**
+ * Snooze an open alert, so it is not re-notified or escalated
+ * until the snooze expires. Pass `minutes: 0` to end the snooze.
+ * Requires execute permissions on the alert target.
+ * Response: [Alert].
+ */
+export interface SnoozeAlert {
+	/** The id of the alert. */
+	id: string;
+	/** How long to snooze the alert for, in minutes. */
+	minutes: I64;
+}
+
 /** Starts all containers on the target server. Response: [Update] */
 export interface StartAllContainers {
 	/** Name or id */
@@ -6935,6 +8080,38 @@ export interface StopStack {
 	service?: string;
 }
 
+/**
+ * Downsampled system stats stored on the database.
+ * These are written periodically from the raw [SystemStatsRecord]s,
+ * and kept around for longer to show long term trends.
+ */
+export interface SystemStatsRollup {
+	/** Unix timestamp in milliseconds of the start of the period */
+	ts: I64;
+	/** Server id */
+	sid: string;
+	/** The length of the period, `5-min` or `1-hr` */
+	granularity: Timelength;
+	/** The number of raw stats in the period */
+	samples: I64;
+	/** Average cpu usage percentage */
+	cpu_perc: number;
+	/** Average memory used in GB */
+	mem_used_gb: number;
+	/** Total memory in GB */
+	mem_total_gb: number;
+	/** Average disk used in GB */
+	disk_used_gb: number;
+	/** Total disk size in GB */
+	disk_total_gb: number;
+	/** Average network ingress usage in bytes */
+	network_ingress_bytes: number;
+	/** Average network egress usage in bytes */
+	network_egress_bytes: number;
+	/** The max values over the period */
+	max: SystemStatsMax;
+}
+
 export interface TerminationSignalLabel {
 	signal: TerminationSignal;
 	label: string;
@@ -7111,6 +8288,15 @@ export interface UpdateGitProviderAccount {
 	account: _PartialGitProviderAccount;
 }
 
+/**
+ * **Admin only.** Update a maintenance window,
+ * matched by `window.name`. Response: [MaintenanceWindow].
+ */
+export interface UpdateMaintenanceWindow {
+	/** The full maintenance window to set. */
+	window: MaintenanceWindow;
+}
+
 /**
  * **Admin only.** Update a user or user groups base permission level on a resource type.
  * Response: [NoData].
@@ -7463,6 +8649,8 @@ export type ReadRequest =
 	| { type: "GetProceduresSummary", params: GetProceduresSummary }
 	| { type: "GetProcedure", params: GetProcedure }
 	| { type: "GetProcedureActionState", params: GetProcedureActionState }
+	| { type: "GetProcedureRun", params: GetProcedureRun }
+	| { type: "ListProcedureRuns", params: ListProcedureRuns }
 	| { type: "ListProcedures", params: ListProcedures }
 	| { type: "ListFullProcedures", params: ListFullProcedures }
 	| { type: "GetActionsSummary", params: GetActionsSummary }
@@ -7480,6 +8668,7 @@ export type ReadRequest =
 	| { type: "GetPeripheryVersion", params: GetPeripheryVersion }
 	| { type: "GetServerActionState", params: GetServerActionState }
 	| { type: "GetHistoricalServerStats", params: GetHistoricalServerStats }
+	| { type: "GetHistoricalContainerStats", params: GetHistoricalContainerStats }
 	| { type: "ListServers", params: ListServers }
 	| { type: "ListFullServers", params: ListFullServers }
 	| { type: "InspectDockerContainer", params: InspectDockerContainer }
@@ -7554,11 +8743,15 @@ export type ReadRequest =
 	| { type: "ListUpdates", params: ListUpdates }
 	| { type: "ListAlerts", params: ListAlerts }
 	| { type: "GetAlert", params: GetAlert }
+	| { type: "GetAlertStats", params: GetAlertStats }
+	| { type: "ListProbeRecords", params: ListProbeRecords }
 	| { type: "GetSystemInformation", params: GetSystemInformation }
 	| { type: "GetSystemStats", params: GetSystemStats }
 	| { type: "ListSystemProcesses", params: ListSystemProcesses }
 	| { type: "GetVariable", params: GetVariable }
 	| { type: "ListVariables", params: ListVariables }
+	| { type: "GetMaintenanceWindow", params: GetMaintenanceWindow }
+	| { type: "ListMaintenanceWindows", params: ListMaintenanceWindows }
 	| { type: "GetGitProviderAccount", params: GetGitProviderAccount }
 	| { type: "ListGitProviderAccounts", params: ListGitProviderAccounts }
 	| { type: "GetDockerRegistryAccount", params: GetDockerRegistryAccount }
@@ -7626,6 +8819,8 @@ export type WriteRequest =
 	| { type: "RefreshRepoCache", params: RefreshRepoCache }
 	| { type: "CreateRepoWebhook", params: CreateRepoWebhook }
 	| { type: "DeleteRepoWebhook", params: DeleteRepoWebhook }
+	| { type: "AcknowledgeAlert", params: AcknowledgeAlert }
+	| { type: "SnoozeAlert", params: SnoozeAlert }
 	| { type: "CreateAlerter", params: CreateAlerter }
 	| { type: "CopyAlerter", params: CopyAlerter }
 	| { type: "DeleteAlerter", params: DeleteAlerter }
@@ -7669,6 +8864,9 @@ export type WriteRequest =
 	| { type: "UpdateVariableDescription", params: UpdateVariableDescription }
 	| { type: "UpdateVariableIsSecret", params: UpdateVariableIsSecret }
 	| { type: "DeleteVariable", params: DeleteVariable }
+	| { type: "CreateMaintenanceWindow", params: CreateMaintenanceWindow }
+	| { type: "UpdateMaintenanceWindow", params: UpdateMaintenanceWindow }
+	| { type: "DeleteMaintenanceWindow", params: DeleteMaintenanceWindow }
 	| { type: "CreateGitProviderAccount", params: CreateGitProviderAccount }
 	| { type: "UpdateGitProviderAccount", params: UpdateGitProviderAccount }
 	| { type: "DeleteGitProviderAccount", params: DeleteGitProviderAccount }
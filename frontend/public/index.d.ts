@@ -754,6 +754,11 @@ declare global {
 
   /** Pre initialized Komodo client */
   var komodo: ReturnType<typeof KomodoClient>;
+  /**
+   * The arguments the Action was run with, by parameter name.
+   * Number and Boolean parameters are parsed to their type.
+   */
+  var ARGS: Record<string, string | number | boolean>;
   /** YAML parsing utilities */
   var YAML: {
     /**
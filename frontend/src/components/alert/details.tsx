@@ -1,5 +1,5 @@
 import { ResourceLink } from "@components/resources/common";
-import { useRead } from "@lib/hooks";
+import { useRead, useWrite } from "@lib/hooks";
 import { UsableResource } from "@types";
 import { Button } from "@ui/button";
 import {
@@ -18,6 +18,9 @@ import {
 } from "@lib/color";
 import { MonacoEditor } from "@components/monaco";
 import { Types } from "komodo_client";
+import { Select, SelectContent, SelectItem, SelectTrigger } from "@ui/select";
+
+const SNOOZE_MINUTES = [15, 60, 240, 1440];
 
 export const AlertDetailsDialog = ({ id }: { id: string }) => {
   const [open, set] = useState(false);
@@ -89,6 +92,9 @@ export const AlertDetailsDialogContent = ({
                   </div>
                 </div>
 
+                {/** Acknowledge / Snooze */}
+                {!alert.resolved && <AlertHandlingActions alert={alert} />}
+
                 {/** Alert data */}
                 <MonacoEditor
                   value={JSON.stringify(alert.data.data, undefined, 2)}
@@ -103,3 +109,53 @@ export const AlertDetailsDialogContent = ({
     )}
   </>
 );
+
+const AlertHandlingActions = ({ alert }: { alert: Types.Alert }) => {
+  const { mutate: acknowledge, isPending: acknowledging } =
+    useWrite("AcknowledgeAlert");
+  const { mutate: snooze, isPending: snoozing } = useWrite("SnoozeAlert");
+  const snoozed = alert.snoozed_until > Date.now();
+  return (
+    <div className="flex gap-4 items-center">
+      {alert.acknowledged ? (
+        <div className="flex gap-2">
+          <div className="text-muted-foreground">acknowledged:</div>
+          {fmt_date_with_minutes(new Date(alert.acknowledged.ts))}
+        </div>
+      ) : (
+        <Button
+          variant="secondary"
+          onClick={() => acknowledge({ id: alert._id?.$oid! })}
+          disabled={acknowledging}
+        >
+          Acknowledge
+        </Button>
+      )}
+      <Select
+        value={undefined}
+        onValueChange={(minutes) =>
+          snooze({ id: alert._id?.$oid!, minutes: Number(minutes) })
+        }
+        disabled={snoozing}
+      >
+        <SelectTrigger className="w-[200px]">
+          {snoozed
+            ? `Snoozed until ${fmt_date_with_minutes(
+                new Date(alert.snoozed_until)
+              )}`
+            : "Snooze"}
+        </SelectTrigger>
+        <SelectContent align="start">
+          {SNOOZE_MINUTES.map((minutes) => (
+            <SelectItem key={minutes} value={minutes.toString()}>
+              {minutes < 60
+                ? `${minutes} minutes`
+                : `${minutes / 60} hour${minutes === 60 ? "" : "s"}`}
+            </SelectItem>
+          ))}
+          {snoozed && <SelectItem value="0">End Snooze</SelectItem>}
+        </SelectContent>
+      </Select>
+    </div>
+  );
+};
@@ -0,0 +1,176 @@
+import { ConfigItem } from "@components/config/util";
+import { TextUpdateMenuMonaco } from "@components/util";
+import { Types } from "komodo_client";
+import { Button } from "@ui/button";
+import { Input } from "@ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@ui/select";
+import { MinusCircle, PlusCircle } from "lucide-react";
+
+const PARAMETER_TYPES = Object.values(Types.ParameterType);
+
+export const ParametersConfig = ({
+  parameters,
+  set,
+  disabled,
+  description,
+}: {
+  parameters: Types.Parameter[];
+  set: (parameters: Types.Parameter[]) => void;
+  disabled: boolean;
+  description: string;
+}) => {
+  const update = (i: number, parameter: Partial<Types.Parameter>) =>
+    set(parameters.map((p, index) => (index === i ? { ...p, ...parameter } : p)));
+  return (
+    <ConfigItem label="Parameters" description={description} boldLabel>
+      <div className="flex flex-col gap-2 w-fit">
+        {parameters.map((parameter, i) => (
+          <div key={i} className="flex items-center gap-4">
+            <Input
+              className="w-[200px]"
+              placeholder="Name"
+              value={parameter.name}
+              onChange={(e) => update(i, { name: e.target.value })}
+              disabled={disabled}
+            />
+            <Select
+              value={parameter.type ?? Types.ParameterType.String}
+              onValueChange={(type) =>
+                update(i, { type: type as Types.ParameterType })
+              }
+              disabled={disabled}
+            >
+              <SelectTrigger className="w-[150px]">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent align="start">
+                {PARAMETER_TYPES.map((type) => (
+                  <SelectItem key={type} value={type}>
+                    {type}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Input
+              className="w-[200px]"
+              placeholder="Default (required if empty)"
+              value={parameter.default ?? ""}
+              onChange={(e) =>
+                update(i, { default: e.target.value || undefined })
+              }
+              disabled={disabled}
+            />
+            <Input
+              className="w-[300px]"
+              placeholder="Description"
+              value={parameter.description ?? ""}
+              onChange={(e) => update(i, { description: e.target.value })}
+              disabled={disabled}
+            />
+            {!disabled && (
+              <Button
+                variant="secondary"
+                onClick={() =>
+                  set(parameters.filter((_, index) => index !== i))
+                }
+              >
+                <MinusCircle className="w-4 h-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        {!disabled && (
+          <Button
+            variant="secondary"
+            className="flex items-center gap-2 w-[200px]"
+            onClick={() =>
+              set([
+                ...parameters,
+                { name: "", type: Types.ParameterType.String },
+              ])
+            }
+          >
+            <PlusCircle className="w-4 h-4" />
+            Add Parameter
+          </Button>
+        )}
+      </div>
+    </ConfigItem>
+  );
+};
+
+/** Inputs for the run arguments, shown in the run confirmation dialog. */
+export const ArgumentsInput = ({
+  parameters,
+  args,
+  set,
+}: {
+  parameters: Types.Parameter[];
+  args: Record<string, string>;
+  set: (args: Record<string, string>) => void;
+}) => (
+  <div className="flex flex-col gap-2">
+    <div className="text-muted-foreground">Arguments</div>
+    {parameters.map((parameter) => (
+      <div key={parameter.name} className="flex items-center gap-4">
+        <div className="w-[150px] text-sm" title={parameter.description}>
+          {parameter.name}
+        </div>
+        <Input
+          placeholder={parameter.default ?? `Required ${parameter.type ?? ""}`}
+          value={args[parameter.name] ?? ""}
+          onChange={(e) => set({ ...args, [parameter.name]: e.target.value })}
+        />
+      </div>
+    ))}
+  </div>
+);
+
+/** Drops the empty arguments, so their parameters use the default. */
+export const filled_args = (args: Record<string, string>) => {
+  const filled = Object.fromEntries(
+    Object.entries(args).filter(([_, value]) => value)
+  );
+  return Object.keys(filled).length ? filled : undefined;
+};
+
+/** Edit execution arguments as `PARAMETER = value` lines. */
+export const ExecutionArgs = ({
+  args,
+  set,
+  disabled,
+}: {
+  args: Record<string, string> | undefined;
+  set: (args: Record<string, string> | undefined) => void;
+  disabled: boolean;
+}) => (
+  <TextUpdateMenuMonaco
+    title="Arguments"
+    value={
+      Object.entries(args ?? {})
+        .map(([name, value]) => `${name} = ${value}`)
+        .join("\n") || "# PARAMETER = value\n"
+    }
+    onUpdate={(value) => set(parse_args(value))}
+    disabled={disabled}
+    language="key_value"
+  />
+);
+
+const parse_args = (value: string) => {
+  const args: Record<string, string> = {};
+  for (const line of value.split("\n")) {
+    const trimmed = line.trim();
+    if (!trimmed || trimmed.startsWith("#")) continue;
+    const index = trimmed.search(/[=:]/);
+    if (index === -1) continue;
+    args[trimmed.slice(0, index).trim()] = trimmed.slice(index + 1).trim();
+  }
+  return Object.keys(args).length ? args : undefined;
+};
@@ -1260,3 +1260,95 @@ export const WebhookIdOrNameSelector = () => {
     </Select>
   );
 };
+
+export const EndpointProbesConfig = ({
+  probes,
+  set,
+  disabled,
+}: {
+  probes: Types.EndpointProbe[];
+  set: (probes: Types.EndpointProbe[]) => void;
+  disabled: boolean;
+}) => {
+  const update = (i: number, probe: Partial<Types.EndpointProbe>) =>
+    set(probes.map((p, index) => (index === i ? { ...p, ...probe } : p)));
+  return (
+    <ConfigItem
+      label="Endpoint Probes"
+      boldLabel
+      description="Probe http(s) urls with a GET request, or host:port with a TCP connection. Send an alert when the probe fails. Status 0 accepts any status below 400."
+    >
+      <div className="flex flex-col gap-2 w-fit">
+        {probes.map((probe, i) => (
+          <div key={i} className="flex items-center gap-4">
+            <Input
+              className="w-[300px]"
+              placeholder="https://example.com/health"
+              value={probe.endpoint}
+              onChange={(e) => update(i, { endpoint: e.target.value })}
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">Status</div>
+            <Input
+              className="w-[80px]"
+              type="number"
+              value={probe.expected_status}
+              onChange={(e) =>
+                update(i, { expected_status: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">Interval (s)</div>
+            <Input
+              className="w-[80px]"
+              type="number"
+              value={probe.interval_secs}
+              onChange={(e) =>
+                update(i, { interval_secs: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">Timeout (s)</div>
+            <Input
+              className="w-[80px]"
+              type="number"
+              value={probe.timeout_secs}
+              onChange={(e) =>
+                update(i, { timeout_secs: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            {!disabled && (
+              <Button
+                variant="secondary"
+                onClick={() => set(probes.filter((_, index) => index !== i))}
+              >
+                <MinusCircle className="w-4 h-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        {!disabled && (
+          <Button
+            variant="secondary"
+            className="flex items-center gap-2 w-[200px]"
+            onClick={() =>
+              set([
+                ...probes,
+                {
+                  endpoint: "",
+                  expected_status: 200,
+                  interval_secs: 60,
+                  timeout_secs: 10,
+                },
+              ])
+            }
+          >
+            <PlusCircle className="w-4 h-4" />
+            Add Probe
+          </Button>
+        )}
+      </div>
+    </ConfigItem>
+  );
+};
@@ -16,6 +16,7 @@ import { useState } from "react";
 import { CopyWebhook } from "../common";
 import { ActionInfo } from "./info";
 import { Switch } from "@ui/switch";
+import { ParametersConfig } from "@components/config/parameters";
 
 const ACTION_GIT_PROVIDER = "Action";
 
@@ -103,6 +104,19 @@ export const ActionConfig = ({ id }: { id: string }) => {
               },
             },
           },
+          {
+            label: "Parameters",
+            components: {
+              parameters: (parameters, set) => (
+                <ParametersConfig
+                  parameters={parameters ?? []}
+                  set={(parameters) => set({ parameters })}
+                  disabled={disabled}
+                  description="Give arguments when running the Action. Read them in the file from ARGS."
+                />
+              ),
+            },
+          },
           {
             label: "Webhook",
             description: `Configure your ${webhook_integration}-style repo provider to send webhooks to Komodo`,
@@ -152,6 +166,19 @@ export const ActionConfig = ({ id }: { id: string }) => {
                   "Provide a custom webhook secret for this resource, or use the global default.",
                 placeholder: "Input custom secret",
               },
+              webhook_args: (webhook_args, set) => (
+                <ConfigItem
+                  label="Webhook Args"
+                  description="Map webhook payload fields to arguments, one 'PARAMETER = path.to.field' per line."
+                >
+                  <MonacoEditor
+                    value={webhook_args}
+                    language="key_value"
+                    onValueChange={(webhook_args) => set({ webhook_args })}
+                    readOnly={disabled}
+                  />
+                </ConfigItem>
+              ),
             },
           },
         ],
@@ -18,6 +18,11 @@ import { Types } from "komodo_client";
 import { DashboardPieChart } from "@pages/home/dashboard";
 import { RenameResource } from "@components/config/util";
 import { GroupActions } from "@components/group-actions";
+import {
+  ArgumentsInput,
+  filled_args,
+} from "@components/config/parameters";
+import { useState } from "react";
 
 const useAction = (id?: string) =>
   useRead("ListActions", {}).data?.find((d) => d.id === id);
@@ -89,15 +94,27 @@ export const ActionComponents: RequiredResourceComponents = {
       ).data?.running;
       const { mutate, isPending } = useExecute("RunAction");
       const action = useAction(id);
+      const parameters =
+        useRead("GetAction", { action: id }).data?.config?.parameters ?? [];
+      const [args, setArgs] = useState<Record<string, string>>({});
       if (!action) return null;
       return (
         <ActionWithDialog
           name={action.name}
           title={running ? "Running" : "Run Action"}
           icon={<Clapperboard className="h-4 w-4" />}
-          onClick={() => mutate({ action: id })}
+          onClick={() => mutate({ action: id, args: filled_args(args) })}
           disabled={running || isPending}
           loading={running}
+          additional={
+            parameters.length > 0 && (
+              <ArgumentsInput
+                parameters={parameters}
+                args={args}
+                set={setArgs}
+              />
+            )
+          }
         />
       );
     },
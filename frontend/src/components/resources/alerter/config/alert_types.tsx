@@ -10,15 +10,24 @@ const ALERT_TYPES: Types.AlertData["type"][] = [
   "ServerCpu",
   "ServerMem",
   "ServerDisk",
+  "ServerNetwork",
+  "ServerProcessMissing",
   // Stack
   "StackStateChange",
   "StackImageUpdateAvailable",
   "StackAutoUpdated",
   // Deployment
   "ContainerStateChange",
+  "ContainerUnhealthy",
+  "ContainerRestartLoop",
+  "ContainerCpu",
+  "ContainerMem",
   "DeploymentImageUpdateAvailable",
   "DeploymentAutoUpdated",
+  "EndpointDown",
+  "EndpointRecovered",
   // Misc
+  "CertificateExpiring",
   "AwsBuilderTerminationFailed",
   "ResourceSyncPendingUpdates",
   "BuildFailed",
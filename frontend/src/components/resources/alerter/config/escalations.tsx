@@ -0,0 +1,102 @@
+import { ConfigItem } from "@components/config/util";
+import { useRead } from "@lib/hooks";
+import { Types } from "komodo_client";
+import { Button } from "@ui/button";
+import { Input } from "@ui/input";
+import { Select, SelectContent, SelectItem, SelectTrigger } from "@ui/select";
+import { MinusCircle, PlusCircle } from "lucide-react";
+
+export const EscalationsConfig = ({
+  id,
+  escalations,
+  set,
+  disabled,
+}: {
+  id: string;
+  escalations: Types.AlerterEscalation[];
+  set: (escalations: Types.AlerterEscalation[]) => void;
+  disabled: boolean;
+}) => {
+  const alerters = (useRead("ListAlerters", {}).data ?? []).filter(
+    (alerter) => alerter.id !== id
+  );
+  const update = (i: number, escalation: Partial<Types.AlerterEscalation>) =>
+    set(
+      escalations.map((e, index) =>
+        index === i ? { ...e, ...escalation } : e
+      )
+    );
+  return (
+    <ConfigItem
+      label="Escalations"
+      description="Also send alerts to other alerters if they stay unresolved."
+      boldLabel
+    >
+      <div className="flex flex-col gap-2 w-fit">
+        {escalations.map((escalation, i) => (
+          <div key={i} className="flex items-center gap-4">
+            <div className="text-muted-foreground">After</div>
+            <Input
+              className="w-[100px]"
+              type="number"
+              value={escalation.after_minutes}
+              onChange={(e) =>
+                update(i, { after_minutes: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">minutes, send to</div>
+            <Select
+              value={
+                alerters.find(
+                  (alerter) =>
+                    alerter.id === escalation.alerter ||
+                    alerter.name === escalation.alerter
+                )?.id ?? escalation.alerter
+              }
+              onValueChange={(alerter) => update(i, { alerter })}
+              disabled={disabled}
+            >
+              <SelectTrigger className="w-[200px]">
+                {alerters.find(
+                  (alerter) =>
+                    alerter.id === escalation.alerter ||
+                    alerter.name === escalation.alerter
+                )?.name ?? "Select Alerter"}
+              </SelectTrigger>
+              <SelectContent align="start">
+                {alerters.map((alerter) => (
+                  <SelectItem key={alerter.id} value={alerter.id}>
+                    {alerter.name}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            {!disabled && (
+              <Button
+                variant="secondary"
+                onClick={() =>
+                  set(escalations.filter((_, index) => index !== i))
+                }
+              >
+                <MinusCircle className="w-4 h-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        {!disabled && (
+          <Button
+            variant="secondary"
+            className="flex items-center gap-2 w-[200px]"
+            onClick={() =>
+              set([...escalations, { after_minutes: 30, alerter: "" }])
+            }
+          >
+            <PlusCircle className="w-4 h-4" />
+            Add Escalation
+          </Button>
+        )}
+      </div>
+    </ConfigItem>
+  );
+};
@@ -4,6 +4,8 @@ import { Types } from "komodo_client";
 import { EndpointConfig } from "./endpoint";
 import { AlertTypeConfig } from "./alert_types";
 import { ResourcesConfig } from "./resources";
+import { EscalationsConfig } from "./escalations";
+import { MonacoEditor } from "@components/monaco";
 
 export const AlerterConfig = ({ id }: { id: string }) => {
   const perms = useRead("GetPermissionLevel", {
@@ -55,6 +57,27 @@ export const AlerterConfig = ({ id }: { id: string }) => {
               ),
             },
           },
+          {
+            label: "Template",
+            description: (
+              <>
+                Optional minijinja template for the message. Available
+                variables: <code>alert</code>, <code>name</code>,{" "}
+                <code>level</code>, <code>link</code>, <code>message</code>.
+                Custom endpoints post the rendered template as the body.
+              </>
+            ),
+            components: {
+              template: (template, set) => (
+                <MonacoEditor
+                  value={template}
+                  language={undefined}
+                  onValueChange={(template) => set({ template })}
+                  readOnly={disabled}
+                />
+              ),
+            },
+          },
           {
             label: "Filter",
             labelHidden: true,
@@ -84,6 +107,25 @@ export const AlerterConfig = ({ id }: { id: string }) => {
               ),
             },
           },
+          {
+            label: "Escalation",
+            labelHidden: true,
+            components: {
+              renotify_interval_minutes: {
+                boldLabel: true,
+                description:
+                  "Re-send unresolved alerts after this many minutes. 0 to only send once.",
+              },
+              escalations: (escalations, set) => (
+                <EscalationsConfig
+                  id={id}
+                  escalations={escalations!}
+                  set={(escalations) => set({ escalations })}
+                  disabled={disabled}
+                />
+              ),
+            },
+          },
         ],
       }}
     />
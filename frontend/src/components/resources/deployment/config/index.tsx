@@ -7,6 +7,7 @@ import {
   ConfigItem,
   ConfigList,
   ConfigSwitch,
+  EndpointProbesConfig,
   InputList,
 } from "@components/config/util";
 import { ImageConfig } from "./components/image";
@@ -344,6 +345,42 @@ export const DeploymentConfig = ({
               ),
             },
           },
+          {
+            label: "Usage Alerts",
+            description:
+              "Alert when the container usage is above these thresholds. Set to 0 to disable.",
+            components: {
+              cpu_warning: {
+                description:
+                  "Send a 'Warning' alert if the CPU usage in % is above this threshold.",
+              },
+              cpu_critical: {
+                description:
+                  "Send a 'Critical' alert if the CPU usage in % is above this threshold.",
+              },
+              mem_warning_mb: {
+                description:
+                  "Send a 'Warning' alert if the memory usage in MB is above this threshold.",
+              },
+              mem_critical_mb: {
+                description:
+                  "Send a 'Critical' alert if the memory usage in MB is above this threshold.",
+              },
+            },
+          },
+          {
+            label: "Endpoint Probes",
+            labelHidden: true,
+            components: {
+              probes: (probes, set) => (
+                <EndpointProbesConfig
+                  probes={probes ?? []}
+                  set={(probes) => set({ probes })}
+                  disabled={disabled}
+                />
+              ),
+            },
+          },
           {
             label: "Termination",
             description:
@@ -57,10 +57,22 @@ import {
   DropdownMenuTrigger,
 } from "@ui/dropdown-menu";
 import { DotsHorizontalIcon } from "@radix-ui/react-icons";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@ui/select";
 import { filterBySplit } from "@lib/utils";
 import { useToast } from "@ui/use-toast";
 import { fmt_upper_camelcase } from "@lib/formatting";
 import { TextUpdateMenuMonaco } from "@components/util";
+import { MonacoEditor } from "@components/monaco";
+import {
+  ExecutionArgs,
+  ParametersConfig,
+} from "@components/config/parameters";
 
 export const ProcedureConfig = ({ id }: { id: string }) => {
   const procedure = useRead("GetProcedure", { procedure: id }).data;
@@ -217,6 +229,20 @@ const ProcedureConfigInner = ({
           Add Stage
         </Button>
       </ConfigLayout>
+      <Section>
+        <Card>
+          <CardContent className="pt-6">
+            <ParametersConfig
+              parameters={
+                config.parameters ?? procedure.config?.parameters ?? []
+              }
+              set={(parameters) => setConfig({ ...config, parameters })}
+              disabled={disabled}
+              description="Give arguments when running the Procedure. Use them in executions with [[ARG_NAME]]."
+            />
+          </CardContent>
+        </Card>
+      </Section>
       <Section>
         <Card>
           <CardHeader>
@@ -286,6 +312,19 @@ const ProcedureConfigInner = ({
                   setConfig({ ...config, webhook_secret })
                 }
               />
+              <ConfigItem
+                label="Webhook Args"
+                description="Map webhook payload fields to arguments, one 'PARAMETER = path.to.field' per line."
+              >
+                <MonacoEditor
+                  value={config.webhook_args ?? procedure.config?.webhook_args}
+                  language="key_value"
+                  onValueChange={(webhook_args) =>
+                    setConfig({ ...config, webhook_args })
+                  }
+                  readOnly={disabled}
+                />
+              </ConfigItem>
             </div>
           </CardContent>
         </Card>
@@ -391,6 +430,21 @@ const Stage = ({
           </DropdownMenu>
         </div>
       </div>
+      <StageFailureConfig
+        stage={stage}
+        setStage={setStage}
+        disabled={disabled}
+      />
+      <StageConditionConfig
+        stage={stage}
+        setStage={setStage}
+        disabled={disabled}
+      />
+      <StageConcurrencyConfig
+        stage={stage}
+        setStage={setStage}
+        disabled={disabled}
+      />
       <DataTable
         tableKey="procedure-stage-executions"
         data={stage.executions!}
@@ -540,6 +594,330 @@ const Stage = ({
   );
 };
 
+const EnumSelector = <T extends string>({
+  value,
+  options,
+  onSelect,
+  disabled,
+}: {
+  value: T;
+  options: T[];
+  onSelect: (value: T) => void;
+  disabled: boolean;
+}) => (
+  <Select value={value} onValueChange={onSelect} disabled={disabled}>
+    <SelectTrigger className="w-[150px]" disabled={disabled}>
+      <SelectValue />
+    </SelectTrigger>
+    <SelectContent>
+      {options.map((option) => (
+        <SelectItem key={option} value={option}>
+          {option}
+        </SelectItem>
+      ))}
+    </SelectContent>
+  </Select>
+);
+
+const FAILURE_POLICIES = [
+  Types.ProcedureFailurePolicy.Abort,
+  Types.ProcedureFailurePolicy.Continue,
+  Types.ProcedureFailurePolicy.Cleanup,
+];
+
+const StageFailureConfig = ({
+  stage,
+  setStage,
+  disabled,
+}: {
+  stage: Types.ProcedureStage;
+  setStage: (stage: Types.ProcedureStage) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-wrap gap-4 items-center text-sm text-muted-foreground">
+      <div className="flex gap-2 items-center">
+        On failure:
+        <EnumSelector
+          value={stage.on_failure ?? Types.ProcedureFailurePolicy.Abort}
+          options={FAILURE_POLICIES}
+          onSelect={(on_failure) => setStage({ ...stage, on_failure })}
+          disabled={disabled}
+        />
+      </div>
+      <div className="flex gap-2 items-center">
+        Retries:
+        <Input
+          className="w-[80px]"
+          type="number"
+          placeholder="0"
+          value={stage.retries ?? 0}
+          onChange={(e) =>
+            setStage({ ...stage, retries: Number(e.target.value) })
+          }
+          disabled={disabled}
+        />
+      </div>
+      <div className="flex gap-2 items-center">
+        Timeout (seconds):
+        <Input
+          className="w-[100px]"
+          type="number"
+          placeholder="0"
+          value={stage.timeout_seconds ?? 0}
+          onChange={(e) =>
+            setStage({ ...stage, timeout_seconds: Number(e.target.value) })
+          }
+          disabled={disabled}
+        />
+      </div>
+      <div className="flex gap-2 items-center">
+        Cleanup stage:
+        <Switch
+          checked={stage.cleanup ?? false}
+          onCheckedChange={(cleanup) => setStage({ ...stage, cleanup })}
+          disabled={disabled}
+        />
+      </div>
+    </div>
+  );
+};
+
+const StageConcurrencyConfig = ({
+  stage,
+  setStage,
+  disabled,
+}: {
+  stage: Types.ProcedureStage;
+  setStage: (stage: Types.ProcedureStage) => void;
+  disabled: boolean;
+}) => {
+  return (
+    <div className="flex flex-wrap gap-4 items-center text-sm text-muted-foreground">
+      <div className="flex gap-2 items-center">
+        Max concurrency:
+        <Input
+          className="w-[80px]"
+          type="number"
+          placeholder="0"
+          value={stage.max_concurrency ?? 0}
+          onChange={(e) =>
+            setStage({ ...stage, max_concurrency: Number(e.target.value) })
+          }
+          disabled={disabled}
+        />
+      </div>
+      <div className="flex gap-2 items-center">
+        Per Server:
+        <Input
+          className="w-[80px]"
+          type="number"
+          placeholder="0"
+          value={stage.max_concurrency_per_server ?? 0}
+          onChange={(e) =>
+            setStage({
+              ...stage,
+              max_concurrency_per_server: Number(e.target.value),
+            })
+          }
+          disabled={disabled}
+        />
+      </div>
+      <div>0 means no limit.</div>
+    </div>
+  );
+};
+
+const CONDITION_TYPES: Types.StageCondition["type"][] = [
+  "PreviousStage",
+  "StackState",
+  "StackChanged",
+  "DeploymentState",
+  "BuildState",
+  "Variable",
+];
+
+const default_condition = (
+  type: Types.StageCondition["type"]
+): Types.StageCondition => {
+  switch (type) {
+    case "PreviousStage":
+      return {
+        type,
+        params: { result: Types.StageResult.Succeeded },
+      };
+    case "StackState":
+      return {
+        type,
+        params: { stack: "", state: Types.StackState.Running },
+      };
+    case "StackChanged":
+      return { type, params: { stack: "" } };
+    case "DeploymentState":
+      return {
+        type,
+        params: { deployment: "", state: Types.DeploymentState.Running },
+      };
+    case "BuildState":
+      return {
+        type,
+        params: { build: "", state: Types.BuildState.Ok },
+      };
+    case "Variable":
+      return { type, params: { name: "", value: "" } };
+  }
+};
+
+const StageConditionConfig = ({
+  stage,
+  setStage,
+  disabled,
+}: {
+  stage: Types.ProcedureStage;
+  setStage: (stage: Types.ProcedureStage) => void;
+  disabled: boolean;
+}) => {
+  const condition = stage.condition;
+  const setCondition = (condition: Types.StageCondition | undefined) =>
+    setStage({ ...stage, condition });
+  return (
+    <div className="flex flex-wrap gap-4 items-center text-sm text-muted-foreground">
+      <div className="flex gap-2 items-center">
+        Run if:
+        <EnumSelector
+          value={condition?.type ?? "Always"}
+          options={["Always", ...CONDITION_TYPES]}
+          onSelect={(type) =>
+            setCondition(
+              type === "Always"
+                ? undefined
+                : default_condition(type as Types.StageCondition["type"])
+            )
+          }
+          disabled={disabled}
+        />
+      </div>
+      {condition?.type === "PreviousStage" && (
+        <EnumSelector
+          value={condition.params.result}
+          options={Object.values(Types.StageResult)}
+          onSelect={(result) =>
+            setCondition({ ...condition, params: { result } })
+          }
+          disabled={disabled}
+        />
+      )}
+      {(condition?.type === "StackState" ||
+        condition?.type === "StackChanged") && (
+        <ResourceSelector
+          type="Stack"
+          selected={condition.params.stack}
+          onSelect={(stack) =>
+            setCondition({
+              ...condition,
+              params: { ...condition.params, stack },
+            } as Types.StageCondition)
+          }
+          disabled={disabled}
+        />
+      )}
+      {condition?.type === "StackState" && (
+        <EnumSelector
+          value={condition.params.state}
+          options={Object.values(Types.StackState)}
+          onSelect={(state) =>
+            setCondition({
+              ...condition,
+              params: { ...condition.params, state },
+            })
+          }
+          disabled={disabled}
+        />
+      )}
+      {condition?.type === "DeploymentState" && (
+        <>
+          <ResourceSelector
+            type="Deployment"
+            selected={condition.params.deployment}
+            onSelect={(deployment) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, deployment },
+              })
+            }
+            disabled={disabled}
+          />
+          <EnumSelector
+            value={condition.params.state}
+            options={Object.values(Types.DeploymentState)}
+            onSelect={(state) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, state },
+              })
+            }
+            disabled={disabled}
+          />
+        </>
+      )}
+      {condition?.type === "BuildState" && (
+        <>
+          <ResourceSelector
+            type="Build"
+            selected={condition.params.build}
+            onSelect={(build) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, build },
+              })
+            }
+            disabled={disabled}
+          />
+          <EnumSelector
+            value={condition.params.state}
+            options={Object.values(Types.BuildState)}
+            onSelect={(state) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, state },
+              })
+            }
+            disabled={disabled}
+          />
+        </>
+      )}
+      {condition?.type === "Variable" && (
+        <>
+          <Input
+            className="w-[200px]"
+            placeholder="Variable name"
+            value={condition.params.name}
+            onChange={(e) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, name: e.target.value },
+              })
+            }
+            disabled={disabled}
+          />
+          <Input
+            className="w-[200px]"
+            placeholder="Expected value"
+            value={condition.params.value}
+            onChange={(e) =>
+              setCondition({
+                ...condition,
+                params: { ...condition.params, value: e.target.value },
+              })
+            }
+            disabled={disabled}
+          />
+        </>
+      )}
+    </div>
+  );
+};
+
 const new_stage = () => ({
   name: "Stage",
   enabled: true,
@@ -655,12 +1033,19 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
   RunProcedure: {
     params: { procedure: "" },
     Component: ({ params, setParams, disabled }) => (
-      <ResourceSelector
-        type="Procedure"
-        selected={params.procedure}
-        onSelect={(procedure) => setParams({ procedure })}
-        disabled={disabled}
-      />
+      <div className="flex items-center gap-4">
+        <ResourceSelector
+          type="Procedure"
+          selected={params.procedure}
+          onSelect={(procedure) => setParams({ ...params, procedure })}
+          disabled={disabled}
+        />
+        <ExecutionArgs
+          args={params.args}
+          set={(args) => setParams({ ...params, args })}
+          disabled={disabled}
+        />
+      </div>
     ),
   },
   BatchRunProcedure: {
@@ -683,12 +1068,19 @@ const TARGET_COMPONENTS: ExecutionConfigs = {
   RunAction: {
     params: { action: "" },
     Component: ({ params, setParams, disabled }) => (
-      <ResourceSelector
-        type="Action"
-        selected={params.action}
-        onSelect={(action) => setParams({ action })}
-        disabled={disabled}
-      />
+      <div className="flex items-center gap-4">
+        <ResourceSelector
+          type="Action"
+          selected={params.action}
+          onSelect={(action) => setParams({ ...params, action })}
+          disabled={disabled}
+        />
+        <ExecutionArgs
+          args={params.args}
+          set={(args) => setParams({ ...params, args })}
+          disabled={disabled}
+        />
+      </div>
     ),
   },
   BatchRunAction: {
@@ -8,6 +8,7 @@ import { RequiredResourceComponents } from "@types";
 import { Route } from "lucide-react";
 import { ProcedureConfig } from "./config";
 import { ProcedureTable } from "./table";
+import { ProcedureRuns } from "./runs";
 import { DeleteResource, NewResource } from "../common";
 import {
   procedure_state_intention,
@@ -18,6 +19,11 @@ import { Types } from "komodo_client";
 import { DashboardPieChart } from "@pages/home/dashboard";
 import { RenameResource } from "@components/config/util";
 import { GroupActions } from "@components/group-actions";
+import {
+  ArgumentsInput,
+  filled_args,
+} from "@components/config/parameters";
+import { useState } from "react";
 
 const useProcedure = (id?: string) =>
   useRead("ListProcedures", {}).data?.find((d) => d.id === id);
@@ -95,21 +101,35 @@ export const ProcedureComponents: RequiredResourceComponents = {
       ).data?.running;
       const { mutate, isPending } = useExecute("RunProcedure");
       const procedure = useProcedure(id);
+      const parameters =
+        useRead("GetProcedure", { procedure: id }).data?.config?.parameters ?? [];
+      const [args, setArgs] = useState<Record<string, string>>({});
       if (!procedure) return null;
       return (
         <ActionWithDialog
           name={procedure.name}
           title={running ? "Running" : "Run Procedure"}
           icon={<Route className="h-4 w-4" />}
-          onClick={() => mutate({ procedure: id })}
+          onClick={() => mutate({ procedure: id, args: filled_args(args) })}
           disabled={running || isPending}
           loading={running}
+          additional={
+            parameters.length > 0 && (
+              <ArgumentsInput
+                parameters={parameters}
+                args={args}
+                set={setArgs}
+              />
+            )
+          }
         />
       );
     },
   },
 
-  Page: {},
+  Page: {
+    Runs: ({ id }) => <ProcedureRuns id={id} />,
+  },
 
   Config: ProcedureConfig,
 
@@ -0,0 +1,100 @@
+import { Section } from "@components/layouts";
+import { UpdateDetails } from "@components/updates/details";
+import { StatusBadge } from "@components/util";
+import { fmt_date, fmt_duration } from "@lib/formatting";
+import { useRead } from "@lib/hooks";
+import { procedure_state_intention } from "@lib/color";
+import { Types } from "komodo_client";
+import { Card } from "@ui/card";
+import { Check, History, X } from "lucide-react";
+
+const RUNS_SHOWN = 5;
+
+export const ProcedureRuns = ({ id }: { id: string }) => {
+  const runs = useRead(
+    "ListProcedureRuns",
+    { procedure: id },
+    { refetchInterval: 5000 }
+  ).data?.runs;
+  if (!runs?.length) return null;
+  return (
+    <Section title="Runs" icon={<History className="w-4 h-4" />}>
+      <div className="flex flex-col gap-4">
+        {runs.slice(0, RUNS_SHOWN).map((run) => (
+          <ProcedureRunCard key={run._id?.$oid} run={run} />
+        ))}
+      </div>
+    </Section>
+  );
+};
+
+const ProcedureRunCard = ({ run }: { run: Types.ProcedureRun }) => (
+  <Card className="p-4 flex flex-col gap-4 text-sm">
+    <UpdateDetails id={run.update_id}>
+      <div className="flex items-center gap-4 cursor-pointer">
+        <StatusBadge
+          text={run.state}
+          intent={procedure_state_intention(run.state)}
+        />
+        <div>{fmt_date(new Date(run.start_ts))}</div>
+        {run.end_ts && (
+          <div className="text-muted-foreground">
+            {fmt_duration(run.start_ts, run.end_ts)}
+          </div>
+        )}
+      </div>
+    </UpdateDetails>
+    <div className="flex flex-col gap-2">
+      {run.stages.map((stage, i) => (
+        <div key={i} className="flex items-center gap-4 flex-wrap">
+          <div className="w-[200px] truncate" title={stage.name}>
+            {stage.cleanup ? "Cleanup: " : ""}
+            {stage.name}
+          </div>
+          <div className="w-[100px] text-muted-foreground">
+            {stage.result}
+          </div>
+          <div className="w-[150px] text-muted-foreground">
+            {fmt_duration(stage.start_ts, stage.end_ts)}
+          </div>
+          <div className="flex items-center gap-2 flex-wrap">
+            {stage.executions.map((execution, i) => (
+              <ExecutionRun key={i} execution={execution} />
+            ))}
+          </div>
+        </div>
+      ))}
+    </div>
+  </Card>
+);
+
+const ExecutionRun = ({
+  execution,
+}: {
+  execution: Types.ProcedureExecutionRun;
+}) => {
+  const label = (
+    <div
+      className="flex items-center gap-1 px-2 py-1 rounded-md bg-accent/50"
+      title={execution.error}
+    >
+      {execution.success ? (
+        <Check className="w-3 stroke-green-500" />
+      ) : (
+        <X className="w-3 stroke-red-500" />
+      )}
+      {execution.execution.type}
+      {execution.attempts > 1 && (
+        <span className="text-muted-foreground">
+          ({execution.attempts} attempts)
+        </span>
+      )}
+    </div>
+  );
+  if (!execution.update_id) return label;
+  return (
+    <UpdateDetails id={execution.update_id}>
+      <div className="cursor-pointer">{label}</div>
+    </UpdateDetails>
+  );
+};
@@ -217,6 +217,45 @@ export const ResourceSyncPending = ({
           </Card>
         );
       })}
+      {/* Pending Maintenance Window Update */}
+      {sync?.info?.maintenance_window_updates?.map((data, i) => {
+        return (
+          <Card key={i}>
+            <CardHeader
+              className={cn(
+                "font-mono pb-2",
+                text_color_class_by_intention(diff_type_intention(data.type))
+              )}
+            >
+              {data.type} Maintenance Window
+            </CardHeader>
+            <CardContent>
+              {data.type === "Create" && (
+                <MonacoEditor
+                  value={data.data.proposed}
+                  language="toml"
+                  readOnly
+                />
+              )}
+              {data.type === "Update" && (
+                <MonacoDiffEditor
+                  original={data.data.current}
+                  modified={data.data.proposed}
+                  language="toml"
+                  readOnly
+                />
+              )}
+              {data.type === "Delete" && (
+                <MonacoEditor
+                  value={data.data.current}
+                  language="toml"
+                  readOnly
+                />
+              )}
+            </CardContent>
+          </Card>
+        );
+      })}
     </Section>
   );
 };
@@ -1,7 +1,10 @@
 import { Config } from "@components/config";
-import { ConfigList } from "@components/config/util";
+import { ConfigItem, ConfigList } from "@components/config/util";
 import { useInvalidate, useLocalStorage, useRead, useWrite } from "@lib/hooks";
 import { Types } from "komodo_client";
+import { Button } from "@ui/button";
+import { Input } from "@ui/input";
+import { MinusCircle, PlusCircle } from "lucide-react";
 import { ReactNode } from "react";
 
 export const ServerConfig = ({
@@ -110,6 +113,11 @@ export const ServerConfig = ({
                 description:
                   "Whether to store historical CPU, RAM, and disk usage.",
               },
+              record_container_stats: {
+                label: "Record Container Stats",
+                description:
+                  "Whether to store historical CPU, RAM, network, and block IO usage for each container.",
+              },
             },
           },
           {
@@ -196,6 +204,59 @@ export const ServerConfig = ({
                 description:
                   "Send a 'Critical' alert if the disk usage in % is above these thresholds",
               },
+              disk_thresholds: (disk_thresholds, set) => (
+                <DiskThresholdsConfig
+                  thresholds={disk_thresholds!}
+                  set={(disk_thresholds) => set({ disk_thresholds })}
+                  disabled={disabled}
+                />
+              ),
+            },
+          },
+          {
+            label: "Network",
+            labelHidden: true,
+            components: {
+              send_network_alerts: {
+                label: "Send Network Alerts",
+                description:
+                  "Send an alert if the throughput on any network interface stays above the configured thresholds.",
+              },
+              network_warning: {
+                description:
+                  "Send a 'Warning' alert if the throughput in MB/s is above this threshold. 0 to disable.",
+              },
+              network_critical: {
+                description:
+                  "Send a 'Critical' alert if the throughput in MB/s is above this threshold. 0 to disable.",
+              },
+              network_sustained_minutes: {
+                label: "Sustained Minutes",
+                description:
+                  "How long the throughput must stay above the threshold before alerting.",
+              },
+            },
+          },
+          {
+            label: "Processes",
+            labelHidden: true,
+            components: {
+              send_process_alerts: {
+                label: "Send Process Alerts",
+                description:
+                  "Send an alert if any of the required processes are not running.",
+              },
+              required_processes: (values, set) => (
+                <ConfigList
+                  label="Required Processes"
+                  description="Process names which should always be running, eg. sshd or tailscaled."
+                  field="required_processes"
+                  values={values ?? []}
+                  set={set}
+                  disabled={disabled}
+                  placeholder="sshd"
+                />
+              ),
             },
           },
         ],
@@ -203,3 +264,80 @@ export const ServerConfig = ({
     />
   );
 };
+
+const DiskThresholdsConfig = ({
+  thresholds,
+  set,
+  disabled,
+}: {
+  thresholds: Types.DiskThreshold[];
+  set: (thresholds: Types.DiskThreshold[]) => void;
+  disabled: boolean;
+}) => {
+  const update = (i: number, threshold: Partial<Types.DiskThreshold>) =>
+    set(
+      thresholds.map((t, index) => (index === i ? { ...t, ...threshold } : t))
+    );
+  return (
+    <ConfigItem
+      label="Mount Thresholds"
+      description="Override the disk thresholds for specific mount points."
+    >
+      <div className="flex flex-col gap-2 w-fit">
+        {thresholds.map((threshold, i) => (
+          <div key={i} className="flex items-center gap-4">
+            <Input
+              className="w-[200px]"
+              placeholder="/mnt/data"
+              value={threshold.mount}
+              onChange={(e) => update(i, { mount: e.target.value })}
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">Warning</div>
+            <Input
+              className="w-[100px]"
+              type="number"
+              value={threshold.warning}
+              onChange={(e) =>
+                update(i, { warning: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">Critical</div>
+            <Input
+              className="w-[100px]"
+              type="number"
+              value={threshold.critical}
+              onChange={(e) =>
+                update(i, { critical: Number(e.target.value) })
+              }
+              disabled={disabled}
+            />
+            {!disabled && (
+              <Button
+                variant="secondary"
+                onClick={() =>
+                  set(thresholds.filter((_, index) => index !== i))
+                }
+              >
+                <MinusCircle className="w-4 h-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        {!disabled && (
+          <Button
+            variant="secondary"
+            className="flex items-center gap-2 w-[200px]"
+            onClick={() =>
+              set([...thresholds, { mount: "", warning: 75, critical: 95 }])
+            }
+          >
+            <PlusCircle className="w-4 h-4" />
+            Add Mount
+          </Button>
+        )}
+      </div>
+    </ConfigItem>
+  );
+};
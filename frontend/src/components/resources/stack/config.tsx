@@ -5,6 +5,7 @@ import {
   ConfigItem,
   ConfigList,
   ConfigSwitch,
+  EndpointProbesConfig,
   InputList,
   ProviderSelectorConfig,
   SystemCommand,
@@ -34,7 +35,9 @@ import { ConfirmButton, ShowHideButton } from "@components/util";
 import { MonacoEditor } from "@components/monaco";
 import { useToast } from "@ui/use-toast";
 import { text_color_class_by_intention } from "@lib/color";
-import { Ban, CirclePlus } from "lucide-react";
+import { Button } from "@ui/button";
+import { Input } from "@ui/input";
+import { Ban, CirclePlus, MinusCircle, PlusCircle } from "lucide-react";
 
 type StackMode = "UI Defined" | "Files On Server" | "Git Repo" | undefined;
 const STACK_MODES: StackMode[] = ["UI Defined", "Files On Server", "Git Repo"];
@@ -359,6 +362,32 @@ export const StackConfig = ({
         ),
       },
     },
+    {
+      label: "Usage Alerts",
+      labelHidden: true,
+      components: {
+        service_thresholds: (thresholds, set) => (
+          <ServiceThresholdsConfig
+            thresholds={thresholds ?? []}
+            set={(service_thresholds) => set({ service_thresholds })}
+            disabled={disabled}
+          />
+        ),
+      },
+    },
+    {
+      label: "Endpoint Probes",
+      labelHidden: true,
+      components: {
+        probes: (probes, set) => (
+          <EndpointProbesConfig
+            probes={probes ?? []}
+            set={(probes) => set({ probes })}
+            disabled={disabled}
+          />
+        ),
+      },
+    },
     {
       label: "Pull Images",
       labelHidden: true,
@@ -819,6 +848,94 @@ export const StackConfig = ({
   );
 };
 
+const ServiceThresholdsConfig = ({
+  thresholds,
+  set,
+  disabled,
+}: {
+  thresholds: Types.StackServiceThresholds[];
+  set: (thresholds: Types.StackServiceThresholds[]) => void;
+  disabled: boolean;
+}) => {
+  const update = (
+    i: number,
+    threshold: Partial<Types.StackServiceThresholds>
+  ) =>
+    set(
+      thresholds.map((t, index) => (index === i ? { ...t, ...threshold } : t))
+    );
+  const number_input = (
+    i: number,
+    field: Exclude<keyof Types.StackServiceThresholds, "service">
+  ) => (
+    <Input
+      className="w-[100px]"
+      type="number"
+      value={thresholds[i][field]}
+      onChange={(e) => update(i, { [field]: Number(e.target.value) })}
+      disabled={disabled}
+    />
+  );
+  return (
+    <ConfigItem
+      label="Usage Alerts"
+      boldLabel
+      description="Alert when a service container's CPU % or memory MB is above these warning / critical thresholds. Set to 0 to disable."
+    >
+      <div className="flex flex-col gap-2 w-fit">
+        {thresholds.map((threshold, i) => (
+          <div key={i} className="flex items-center gap-4">
+            <Input
+              className="w-[200px]"
+              placeholder="Service name"
+              value={threshold.service}
+              onChange={(e) => update(i, { service: e.target.value })}
+              disabled={disabled}
+            />
+            <div className="text-muted-foreground">CPU</div>
+            {number_input(i, "cpu_warning")}
+            {number_input(i, "cpu_critical")}
+            <div className="text-muted-foreground">Memory</div>
+            {number_input(i, "mem_warning_mb")}
+            {number_input(i, "mem_critical_mb")}
+            {!disabled && (
+              <Button
+                variant="secondary"
+                onClick={() =>
+                  set(thresholds.filter((_, index) => index !== i))
+                }
+              >
+                <MinusCircle className="w-4 h-4" />
+              </Button>
+            )}
+          </div>
+        ))}
+        {!disabled && (
+          <Button
+            variant="secondary"
+            className="flex items-center gap-2 w-[200px]"
+            onClick={() =>
+              set([
+                ...thresholds,
+                {
+                  service: "",
+                  cpu_warning: 0,
+                  cpu_critical: 0,
+                  mem_warning_mb: 0,
+                  mem_critical_mb: 0,
+                },
+              ])
+            }
+          >
+            <PlusCircle className="w-4 h-4" />
+            Add Service
+          </Button>
+        )}
+      </div>
+    </ConfigItem>
+  );
+};
+
 export const DEFAULT_STACK_FILE_CONTENTS = `## 🦎 Hello Komodo 🦎
 services:
   hello_world:
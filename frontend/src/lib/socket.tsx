@@ -240,6 +240,21 @@ const on_message = (
     ) {
       invalidate(["ListVariables"], ["GetVariable"]);
     }
+
+    if (
+      update.target.type === "System" &&
+      update.operation.includes("MaintenanceWindow")
+    ) {
+      invalidate(["ListMaintenanceWindows"], ["GetMaintenanceWindow"]);
+    }
+
+    if (
+      [Types.Operation.AcknowledgeAlert, Types.Operation.SnoozeAlert].includes(
+        update.operation
+      )
+    ) {
+      invalidate(["GetAlert"]);
+    }
   }
 
   // Run any attached handlers
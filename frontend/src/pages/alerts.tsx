@@ -26,12 +26,38 @@ import { ResourceComponents } from "@components/resources";
 import { ResourceSelector } from "@components/resources/common";
 
 const ALERT_TYPES_BY_RESOURCE: { [key: string]: Types.AlertData["type"][] } = {
-  Server: ["ServerUnreachable", "ServerCpu", "ServerMem", "ServerDisk"],
-  Stack: ["StackStateChange", "StackImageUpdateAvailable", "StackAutoUpdated"],
+  Server: [
+    "ServerUnreachable",
+    "ServerCpu",
+    "ServerMem",
+    "ServerDisk",
+    "ServerNetwork",
+    "ServerProcessMissing",
+    "CertificateExpiring",
+  ],
+  Stack: [
+    "StackStateChange",
+    "ContainerUnhealthy",
+    "ContainerRestartLoop",
+    "ContainerCpu",
+    "ContainerMem",
+    "StackImageUpdateAvailable",
+    "StackAutoUpdated",
+    "CertificateExpiring",
+    "EndpointDown",
+    "EndpointRecovered",
+  ],
   Deployment: [
     "ContainerStateChange",
+    "ContainerUnhealthy",
+    "ContainerRestartLoop",
+    "ContainerCpu",
+    "ContainerMem",
     "DeploymentImageUpdateAvailable",
     "DeploymentAutoUpdated",
+    "CertificateExpiring",
+    "EndpointDown",
+    "EndpointRecovered",
   ],
   Build: ["BuildFailed"],
   Repo: ["RepoBuildFailed"],
@@ -39,8 +65,10 @@ const ALERT_TYPES_BY_RESOURCE: { [key: string]: Types.AlertData["type"][] } = {
 };
 
 const FALLBACK_ALERT_TYPES = [
-  ...Object.values(ALERT_TYPES_BY_RESOURCE).flat(),
-  "AwsBuilderTerminationFailed",
+  ...new Set([
+    ...Object.values(ALERT_TYPES_BY_RESOURCE).flat(),
+    "AwsBuilderTerminationFailed",
+  ]),
 ];
 
 export const AlertsPage = () => {
@@ -0,0 +1,42 @@
+use std::fmt::Write;
+
+/// Writes metrics in the Prometheus text exposition format.
+#[derive(Default)]
+pub struct Metrics(String);
+
+impl Metrics {
+  /// Writes the HELP and TYPE lines for the metric.
+  pub fn header(&mut self, metric: &str, help: &str, kind: &str) {
+    let _ = writeln!(self.0, "# HELP {metric} {help}");
+    let _ = writeln!(self.0, "# TYPE {metric} {kind}");
+  }
+
+  pub fn sample(
+    &mut self,
+    metric: &str,
+    labels: &[(&str, &str)],
+    value: f64,
+  ) {
+    if labels.is_empty() {
+      let _ = writeln!(self.0, "{metric} {value}");
+      return;
+    }
+    let labels = labels
+      .iter()
+      .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+      .collect::<Vec<_>>()
+      .join(",");
+    let _ = writeln!(self.0, "{metric}{{{labels}}} {value}");
+  }
+
+  pub fn into_string(self) -> String {
+    self.0
+  }
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}